@@ -1,4 +1,4 @@
-use astrograph_engine::{analyze_project, AnalysisCache, AnalysisConfig};
+use astrograph_engine::{analyze_project, AnalysisCache, ProjectSettings};
 use base64::Engine;
 use serde::Serialize;
 use std::fs;
@@ -84,10 +84,17 @@ fn analyze_project_dir(
         });
     }
 
-    let cache_path = path_buf.join(".astrograph-cache.json");
-    let cache = load_cache(&cache_path);
+    let settings = ProjectSettings::for_project(path_buf).map_err(|err| {
+        log::error!("Failed to load project config: {err:?}");
+        AnalyzeErrorPayload::AnalysisFailed {
+            message: format!("Invalid project configuration: {err:#}"),
+        }
+    })?;
+    // The desktop app caches unless the project turns it off.
+    let cache_path = settings.cache_path(true);
+    let cache = cache_path.as_ref().and_then(load_cache);
 
-    let config = AnalysisConfig::new(path_buf);
+    let config = settings.config;
     let app = window.app_handle().clone();
     let progress = Some(move |event: astrograph_engine::ProgressEvent| {
         let _ = app.emit(ANALYSIS_PROGRESS_EVENT, &event);
@@ -110,7 +117,9 @@ fn analyze_project_dir(
         }
     })?;
 
-    save_cache(&cache_path, &output.cache);
+    if let Some(cache_path) = &cache_path {
+        save_cache(cache_path, &output.cache);
+    }
 
     Ok(output.result)
 }
@@ -16,7 +16,7 @@ vi.mock("./components/BookmarksPanel", () => ({
 }));
 
 const sampleAnalysis: AnalysisResult = {
-  schema_version: "0.1.0",
+  schema_version: "0.2.0",
   root: "/tmp/sample",
   generated_at: "2026-01-28T00:00:00Z",
   stats: {
@@ -50,7 +50,7 @@ const sampleAnalysis: AnalysisResult = {
       fq_name: "main::main",
       container: null,
       is_exported: true,
-      is_entrypoint: true,
+      entrypoint_kinds: ["main"],
     },
   ],
   calls: [],
@@ -77,6 +77,7 @@ const resetStore = () => {
     graphViewMode: "all",
     layoutType: "cose",
     showEntrypointsOnly: false,
+    includePublicApi: false,
     highlightedPath: [],
   });
 };
@@ -2,6 +2,7 @@ import { ChangeEvent, useEffect, useRef, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { open } from "@tauri-apps/plugin-dialog";
+import { ApiClient, findApiBase } from "./api";
 import GraphView from "./components/GraphView";
 import KeyboardShortcuts from "./components/KeyboardShortcuts";
 import Sidebar from "./components/Sidebar";
@@ -32,6 +33,8 @@ const isTauri = Boolean(import.meta.env.TAURI_PLATFORM);
 const App = () => {
   const loadAnalysis = useAnalysisStore((state) => state.loadAnalysis);
   const analysis = useAnalysisStore((state) => state.analysis);
+  const apiBase = useAnalysisStore((state) => state.apiBase);
+  const setApiBase = useAnalysisStore((state) => state.setApiBase);
   const [error, setError] = useState<string | null>(null);
   const [isDragging, setIsDragging] = useState(false);
   const [isAnalyzing, setIsAnalyzing] = useState(false);
@@ -51,37 +54,40 @@ const App = () => {
     localStorage.setItem(THEME_STORAGE_KEY, theme);
   }, [theme]);
 
-  const loadAnalysisFromText = async (raw: string) => {
-    try {
-      const rawValue = JSON.parse(raw);
-      const result = validateAnalysisResult(rawValue);
+  const loadAnalysisValue = (rawValue: unknown) => {
+    const result = validateAnalysisResult(rawValue);
 
-      if (!result.ok) {
-        console.error("Schema validation failed", result.error, rawValue);
-        setError(
-          "The analysis file is not a valid Astrograph analysis (missing or invalid fields).",
-        );
-        return;
-      }
+    if (!result.ok) {
+      console.error("Schema validation failed", result.error, rawValue);
+      setError(
+        "The analysis file is not a valid Astrograph analysis (missing or invalid fields).",
+      );
+      return;
+    }
 
-      const { value } = result;
+    const { value } = result;
 
-      if (!isSchemaVersionCompatible(value.schema_version)) {
-        console.error(
-          "Schema version mismatch",
-          value.schema_version,
-          `expected ${CURRENT_SCHEMA_VERSION}`,
-        );
-        const message =
-          value.schema_version > CURRENT_SCHEMA_VERSION
-            ? `This analysis file uses schema version ${value.schema_version}, which is newer than this UI supports. Please update Astrograph and try again.`
-            : `This analysis file was generated by an incompatible Astrograph schema version (${value.schema_version}). Please regenerate the analysis with the current CLI.`;
-        setError(message);
-        return;
-      }
+    if (!isSchemaVersionCompatible(value.schema_version)) {
+      console.error(
+        "Schema version mismatch",
+        value.schema_version,
+        `expected ${CURRENT_SCHEMA_VERSION}`,
+      );
+      const message =
+        value.schema_version > CURRENT_SCHEMA_VERSION
+          ? `This analysis file uses schema version ${value.schema_version}, which is newer than this UI supports. Please update Astrograph and try again.`
+          : `This analysis file was generated by an incompatible Astrograph schema version (${value.schema_version}). Please regenerate the analysis with the current CLI.`;
+      setError(message);
+      return;
+    }
 
-      loadAnalysis(value);
-      setError(null);
+    loadAnalysis(value);
+    setError(null);
+  };
+
+  const loadAnalysisFromText = async (raw: string) => {
+    try {
+      loadAnalysisValue(JSON.parse(raw));
     } catch (err) {
       console.error(err);
       setError(
@@ -90,6 +96,36 @@ const App = () => {
     }
   };
 
+  const loadAnalysisFromServer = async (base: string, reanalyze = false) => {
+    const client = new ApiClient(base);
+    try {
+      setIsAnalyzing(true);
+      if (reanalyze) {
+        await client.reanalyze();
+      }
+      loadAnalysisValue(await client.analysis());
+    } catch (err) {
+      console.error(err);
+      const message = err instanceof Error ? err.message : String(err);
+      setError(`Could not load the analysis from ${base}: ${message}`);
+    } finally {
+      setIsAnalyzing(false);
+    }
+  };
+
+  // In a browser, load from `astrograph serve` when the page points at it.
+  useEffect(() => {
+    if (isTauri) {
+      return;
+    }
+    findApiBase().then((base) => {
+      if (base) {
+        setApiBase(base);
+        void loadAnalysisFromServer(base);
+      }
+    });
+  }, []);
+
   const handleFileLoad = async (event: ChangeEvent<HTMLInputElement>) => {
     const file = event.target.files?.[0];
     if (!file) {
@@ -225,6 +261,17 @@ const App = () => {
               {isAnalyzing ? "Analyzing…" : "Analyze project"}
             </button>
           )}
+          {!isTauri && apiBase && (
+            <button
+              type="button"
+              className="file-button"
+              onClick={() => loadAnalysisFromServer(apiBase, true)}
+              disabled={isAnalyzing}
+              title={`Re-run the analysis on ${apiBase}`}
+            >
+              {isAnalyzing ? "Analyzing…" : "Re-analyze"}
+            </button>
+          )}
           <label className="file-button">
             Load analysis
             <input
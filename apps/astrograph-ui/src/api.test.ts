@@ -0,0 +1,91 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+import { ApiClient, ApiError, findApiBase } from "./api";
+
+const jsonResponse = (body: unknown, status = 200) =>
+  new Response(JSON.stringify(body), {
+    status,
+    headers: { "Content-Type": "application/json" },
+  });
+
+afterEach(() => {
+  vi.unstubAllGlobals();
+});
+
+describe("findApiBase", () => {
+  it("uses the api parameter of the page URL", async () => {
+    const fetchMock = vi.fn();
+    vi.stubGlobal("fetch", fetchMock);
+
+    const base = await findApiBase({
+      search: "?api=http://127.0.0.1:7878/api/",
+      protocol: "http:",
+    });
+
+    expect(base).toBe("http://127.0.0.1:7878/api");
+    expect(fetchMock).not.toHaveBeenCalled();
+  });
+
+  it("uses the page's own server when it answers with JSON", async () => {
+    vi.stubGlobal("fetch", vi.fn().mockResolvedValue(jsonResponse({})));
+
+    expect(await findApiBase({ search: "", protocol: "http:" })).toBe("/api");
+  });
+
+  it("ignores servers without the API", async () => {
+    vi.stubGlobal(
+      "fetch",
+      vi.fn().mockResolvedValue(
+        new Response("<!doctype html>", {
+          headers: { "Content-Type": "text/html" },
+        }),
+      ),
+    );
+    expect(await findApiBase({ search: "", protocol: "http:" })).toBeNull();
+
+    vi.stubGlobal("fetch", vi.fn().mockRejectedValue(new TypeError("offline")));
+    expect(await findApiBase({ search: "", protocol: "http:" })).toBeNull();
+    expect(await findApiBase({ search: "", protocol: "file:" })).toBeNull();
+  });
+});
+
+describe("ApiClient", () => {
+  it("reads source files by path", async () => {
+    const fetchMock = vi
+      .fn()
+      .mockResolvedValue(jsonResponse({ content: "fn main() {}" }));
+    vi.stubGlobal("fetch", fetchMock);
+
+    const content = await new ApiClient("/api").source("src/a b.rs");
+
+    expect(content).toBe("fn main() {}");
+    expect(fetchMock).toHaveBeenCalledWith(
+      "/api/source?file=src%2Fa%20b.rs",
+      undefined,
+    );
+  });
+
+  it("reports the server's error message", async () => {
+    vi.stubGlobal(
+      "fetch",
+      vi.fn().mockResolvedValue(jsonResponse({ error: "Unknown file: x" }, 404)),
+    );
+
+    const error = await new ApiClient("/api").source("x").catch((err) => err);
+
+    expect(error).toBeInstanceOf(ApiError);
+    expect(error.message).toBe("Unknown file: x");
+    expect(error.status).toBe(404);
+  });
+
+  it("posts re-analysis requests", async () => {
+    const fetchMock = vi.fn().mockResolvedValue(jsonResponse({}));
+    vi.stubGlobal("fetch", fetchMock);
+
+    await new ApiClient("http://127.0.0.1:7878/api").reanalyze();
+
+    expect(fetchMock).toHaveBeenCalledWith(
+      "http://127.0.0.1:7878/api/reanalyze",
+      { method: "POST" },
+    );
+  });
+});
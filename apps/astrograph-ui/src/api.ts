@@ -0,0 +1,78 @@
+// Client for the local API of `astrograph serve`, which lets the UI run as a
+// plain web viewer: the analysis and source code come from the server rather
+// than from a loaded file or the desktop app.
+
+const API_PARAM = "api";
+
+export class ApiError extends Error {
+  constructor(
+    message: string,
+    readonly status: number,
+  ) {
+    super(message);
+    this.name = "ApiError";
+  }
+}
+
+export class ApiClient {
+  constructor(readonly base: string) {}
+
+  private async request<T>(path: string, init?: RequestInit): Promise<T> {
+    const response = await fetch(`${this.base}${path}`, init);
+    const body = (await response.json().catch(() => null)) as
+      | (T & { error?: string })
+      | null;
+    if (!response.ok) {
+      throw new ApiError(
+        body?.error ?? `Request failed with status ${response.status}`,
+        response.status,
+      );
+    }
+    return body as T;
+  }
+
+  /** The full analysis result, to be validated by the caller. */
+  analysis(): Promise<unknown> {
+    return this.request("/analysis");
+  }
+
+  /** Contents of an analyzed file, by its root-relative path. */
+  async source(file: string): Promise<string> {
+    const snippet = await this.request<{ content: string }>(
+      `/source?file=${encodeURIComponent(file)}`,
+    );
+    return snippet.content;
+  }
+
+  /** Re-runs the analysis on the server. */
+  async reanalyze(): Promise<void> {
+    await this.request("/reanalyze", { method: "POST" });
+  }
+}
+
+const isJsonResponse = (response: Response) =>
+  response.headers.get("content-type")?.includes("application/json") ?? false;
+
+/**
+ * Base URL of the API to load from: the `?api=` parameter of the page URL
+ * (e.g. `?api=http://127.0.0.1:7878/api`), or `/api` when the page itself
+ * is served by `astrograph serve --ui-dir`. `null` when there is no server.
+ */
+export async function findApiBase(
+  location: Pick<Location, "search" | "protocol"> = window.location,
+): Promise<string | null> {
+  const param = new URLSearchParams(location.search).get(API_PARAM);
+  if (param) {
+    return param.replace(/\/+$/, "");
+  }
+  if (location.protocol !== "http:" && location.protocol !== "https:") {
+    return null;
+  }
+  try {
+    // Other static servers answer with their HTML fallback page, if at all.
+    const response = await fetch("/api/stats");
+    return response.ok && isJsonResponse(response) ? "/api" : null;
+  } catch {
+    return null;
+  }
+}
@@ -5,13 +5,12 @@ import { Symbol } from "../types";
 // Check if Tauri is available (same method as App.tsx)
 const isTauri = Boolean(import.meta.env.TAURI_PLATFORM);
 
-// Lazy load SourcePreview only when Tauri is available
-const SourcePreview = isTauri
-  ? lazy(() => import("./SourcePreview"))
-  : null;
+// Source is read through Tauri or from the `astrograph serve` API.
+const SourcePreview = lazy(() => import("./SourcePreview"));
 
 const BookmarksPanel = () => {
   const analysis = useAnalysisStore((state) => state.analysis);
+  const apiBase = useAnalysisStore((state) => state.apiBase);
   const selectedSymbolId = useAnalysisStore((state) => state.selectedSymbolId);
   const bookmarks = useAnalysisStore((state) => state.bookmarks);
   const toggleBookmark = useAnalysisStore((state) => state.toggleBookmark);
@@ -102,11 +101,21 @@ const BookmarksPanel = () => {
         <div className="selection-details">
           <div className="selection-title">{selectedSymbol.name}</div>
           <div className="selection-fq">{selectedSymbol.fq_name}</div>
+          {selectedSymbol.signature && (
+            <code className="selection-signature">
+              {selectedSymbol.signature}
+            </code>
+          )}
+          {selectedSymbol.doc && (
+            <p className="selection-doc">{selectedSymbol.doc}</p>
+          )}
           <div className="selection-meta">
             <span className="meta-badge">{selectedSymbol.kind}</span>
-            {selectedSymbol.is_entrypoint && (
-              <span className="meta-badge entrypoint">entrypoint</span>
-            )}
+            {selectedSymbol.entrypoint_kinds.map((kind) => (
+              <span key={kind} className="meta-badge entrypoint">
+                {kind.replace(/_/g, " ")}
+              </span>
+            ))}
             {selectedSymbol.is_exported && (
               <span className="meta-badge exported">exported</span>
             )}
@@ -206,14 +215,17 @@ const BookmarksPanel = () => {
           )}
 
           {/* Source Preview */}
-          {SourcePreview && selectedSymbol && (
-            <Suspense fallback={null}>
-              <SourcePreview
-                symbol={selectedSymbol}
-                rootPath={analysis?.root ?? null}
-              />
-            </Suspense>
-          )}
+          {(isTauri || apiBase) &&
+            selectedSymbol &&
+            !selectedSymbol.external &&
+            selectedSymbol.kind !== "external" && (
+              <Suspense fallback={null}>
+                <SourcePreview
+                  symbol={selectedSymbol}
+                  rootPath={analysis?.root ?? null}
+                />
+              </Suspense>
+            )}
         </div>
       ) : (
         <div className="empty-state">Select a symbol to inspect details.</div>
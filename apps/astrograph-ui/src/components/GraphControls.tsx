@@ -29,6 +29,10 @@ const GraphControls = ({
   const setShowEntrypointsOnly = useAnalysisStore(
     (state) => state.setShowEntrypointsOnly
   );
+  const includePublicApi = useAnalysisStore((state) => state.includePublicApi);
+  const setIncludePublicApi = useAnalysisStore(
+    (state) => state.setIncludePublicApi
+  );
   const selectedSymbolId = useAnalysisStore((state) => state.selectedSymbolId);
 
   const viewModes: { value: GraphViewMode; label: string }[] = [
@@ -150,6 +154,14 @@ const GraphControls = ({
             />
             <span>Entrypoints only</span>
           </label>
+          <label className="control-checkbox">
+            <input
+              type="checkbox"
+              checked={includePublicApi}
+              onChange={(e) => setIncludePublicApi(e.target.checked)}
+            />
+            <span>Include public API</span>
+          </label>
         </div>
       </div>
     </div>
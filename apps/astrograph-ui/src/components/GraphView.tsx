@@ -1,7 +1,7 @@
 import { useCallback, useEffect, useMemo, useRef } from "react";
 import cytoscape, { Core, ElementDefinition } from "cytoscape";
 import { useAnalysisStore } from "../state/store";
-import { Symbol, CallEdge } from "../types";
+import { Symbol, CallEdge, isEntrypoint, isProgramEntrypoint } from "../types";
 import type { Theme } from "./ThemeToggle";
 import GraphControls from "./GraphControls";
 
@@ -56,11 +56,18 @@ const GraphView = ({ theme }: GraphViewProps) => {
   const showEntrypointsOnly = useAnalysisStore(
     (state) => state.showEntrypointsOnly
   );
+  const includePublicApi = useAnalysisStore((state) => state.includePublicApi);
   const highlightedPath = useAnalysisStore((state) => state.highlightedPath);
   const setHighlightedPath = useAnalysisStore(
     (state) => state.setHighlightedPath
   );
 
+  const isShownEntrypoint = useCallback(
+    (symbol: Symbol) =>
+      includePublicApi ? isEntrypoint(symbol) : isProgramEntrypoint(symbol),
+    [includePublicApi]
+  );
+
   // Get symbols to display based on view mode
   const getVisibleSymbols = useCallback(
     (symbols: Symbol[], calls: CallEdge[]): Set<string> => {
@@ -68,7 +75,7 @@ const GraphView = ({ theme }: GraphViewProps) => {
 
       if (graphViewMode === "entrypoints" || showEntrypointsOnly) {
         // Show only entrypoints and their direct callees
-        const entrypoints = symbols.filter((s) => s.is_entrypoint);
+        const entrypoints = symbols.filter(isShownEntrypoint);
         entrypoints.forEach((s) => visible.add(s.id));
 
         calls.forEach((call) => {
@@ -102,7 +109,13 @@ const GraphView = ({ theme }: GraphViewProps) => {
 
       return visible;
     },
-    [graphViewMode, selectedSymbolId, selectedFile, showEntrypointsOnly]
+    [
+      graphViewMode,
+      selectedSymbolId,
+      selectedFile,
+      showEntrypointsOnly,
+      isShownEntrypoint,
+    ]
   );
 
   const elements = useMemo((): ElementDefinition[] => {
@@ -118,7 +131,7 @@ const GraphView = ({ theme }: GraphViewProps) => {
       .filter((symbol) => visibleSymbols.has(symbol.id))
       .map((symbol: Symbol) => {
         const classes: string[] = [];
-        if (symbol.is_entrypoint) {
+        if (isShownEntrypoint(symbol)) {
           classes.push("entrypoint");
         }
         if (bookmarks[symbol.id]) {
@@ -158,6 +171,9 @@ const GraphView = ({ theme }: GraphViewProps) => {
       )
       .map((call) => {
         const classes: string[] = [];
+        if (call.kind && call.kind !== "call") {
+          classes.push(call.kind);
+        }
         if (
           highlightedSet.has(call.caller_id) &&
           highlightedSet.has(call.callee_id!)
@@ -183,6 +199,7 @@ const GraphView = ({ theme }: GraphViewProps) => {
     selectedSymbolId,
     highlightedPath,
     getVisibleSymbols,
+    isShownEntrypoint,
   ]);
 
   const colors = GRAPH_COLORS[theme];
@@ -216,6 +233,31 @@ const GraphView = ({ theme }: GraphViewProps) => {
           "arrow-scale": 0.8,
         },
       },
+      {
+        selector: "edge.macro",
+        style: {
+          "line-style": "dashed",
+        },
+      },
+      {
+        selector: "edge.defines",
+        style: {
+          "line-style": "dotted",
+        },
+      },
+      {
+        selector: "edge.ffi",
+        style: {
+          "line-style": "dashed",
+          "line-dash-pattern": [8, 3],
+        },
+      },
+      {
+        selector: "edge.renders",
+        style: {
+          "target-arrow-shape": "triangle-tee",
+        },
+      },
       {
         selector: "node.entrypoint",
         style: {
@@ -287,6 +329,13 @@ const GraphView = ({ theme }: GraphViewProps) => {
           shape: "hexagon",
         },
       },
+      {
+        selector: "node[kind='external']",
+        style: {
+          shape: "barrel",
+          "border-style": "dashed",
+        },
+      },
     ],
     [theme]
   );
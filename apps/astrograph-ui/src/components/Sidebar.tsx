@@ -1,6 +1,6 @@
 import { useCallback, useEffect, useMemo, useState } from "react";
 import { useAnalysisStore, type SidebarTab } from "../state/store";
-import { SymbolKind } from "../types";
+import { SymbolKind, isEntrypoint, isProgramEntrypoint } from "../types";
 import FileTree from "./FileTree";
 
 const kindIcons: Record<SymbolKind, string> = {
@@ -13,6 +13,14 @@ const kindIcons: Record<SymbolKind, string> = {
   namespace: "N",
   function: "ƒ",
   method: "m",
+  constant: "K",
+  static: "s",
+  type_alias: "t",
+  union: "U",
+  macro: "!",
+  closure: "λ",
+  module_init: "▶",
+  external: "⇱",
 };
 
 const Sidebar = () => {
@@ -25,6 +33,7 @@ const Sidebar = () => {
   const setSelectedFile = useAnalysisStore((state) => state.setSelectedFile);
   const activeTab = useAnalysisStore((state) => state.activeSidebarTab);
   const setActiveTab = useAnalysisStore((state) => state.setActiveSidebarTab);
+  const includePublicApi = useAnalysisStore((state) => state.includePublicApi);
 
   const [kindFilter, setKindFilter] = useState<SymbolKind | "all">("all");
 
@@ -102,8 +111,10 @@ const Sidebar = () => {
     if (!analysis) {
       return [];
     }
-    return analysis.symbols.filter((symbol) => symbol.is_entrypoint);
-  }, [analysis]);
+    return analysis.symbols.filter((symbol) =>
+      includePublicApi ? isEntrypoint(symbol) : isProgramEntrypoint(symbol)
+    );
+  }, [analysis, includePublicApi]);
 
   const uniqueKinds = useMemo(() => {
     if (!analysis) return [];
@@ -117,7 +128,7 @@ const Sidebar = () => {
     {
       id: "entrypoints",
       label: "Entrypoints",
-      count: analysis?.stats.entrypoint_count,
+      count: analysis ? entrypoints.length : undefined,
     },
   ];
 
@@ -207,7 +218,7 @@ const Sidebar = () => {
               <div className="symbol-info">
                 <span className="symbol-name">
                   {symbol.name}
-                  {symbol.is_entrypoint && (
+                  {isProgramEntrypoint(symbol) && (
                     <span className="entry-badge" title="Entrypoint">
                       ★
                     </span>
@@ -257,7 +268,9 @@ const Sidebar = () => {
               <div className="symbol-info">
                 <span className="symbol-name">{symbol.name}</span>
                 <span className="symbol-fq">{symbol.fq_name}</span>
-                <span className="symbol-path">{symbol.file}</span>
+                <span className="symbol-path">
+                  {symbol.file} · {symbol.entrypoint_kinds.join(", ")}
+                </span>
               </div>
             </button>
           ))}
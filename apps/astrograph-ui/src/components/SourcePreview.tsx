@@ -3,8 +3,16 @@ import Prism from "prismjs";
 import "prismjs/themes/prism-tomorrow.css";
 import "prismjs/components/prism-rust";
 import "prismjs/components/prism-javascript";
+import "prismjs/components/prism-jsx";
 import "prismjs/components/prism-typescript";
 import "prismjs/components/prism-tsx";
+import "prismjs/components/prism-java";
+import "prismjs/components/prism-kotlin";
+import "prismjs/components/prism-c";
+import "prismjs/components/prism-cpp";
+import "prismjs/components/prism-markdown";
+import { ApiClient } from "../api";
+import { useAnalysisStore } from "../state/store";
 import { Symbol, Span } from "../types";
 
 interface SourcePreviewProps {
@@ -21,10 +29,35 @@ function detectLanguage(filePath: string): string {
     case "cjs":
     case "mjs":
       return "javascript";
+    case "jsx":
+      return "jsx";
     case "ts":
       return "typescript";
     case "tsx":
       return "tsx";
+    case "java":
+      return "java";
+    case "kt":
+    case "kts":
+      return "kotlin";
+    case "c":
+      return "c";
+    case "h":
+    case "cpp":
+    case "cc":
+    case "cxx":
+    case "hpp":
+    case "hh":
+    case "hxx":
+      return "cpp";
+    // Markup highlighting covers the script blocks of components.
+    case "vue":
+    case "svelte":
+    case "astro":
+      return "markup";
+    case "md":
+    case "markdown":
+      return "markdown";
     default:
       return "text";
   }
@@ -107,10 +140,28 @@ const SourcePreview = ({ symbol, rootPath }: SourcePreviewProps) => {
   const [content, setContent] = useState<string | null>(null);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  const apiBase = useAnalysisStore((state) => state.apiBase);
 
   const language = useMemo(() => detectLanguage(symbol.file), [symbol.file]);
 
   useEffect(() => {
+    if (!isTauri && apiBase) {
+      setLoading(true);
+      setError(null);
+      setContent(null);
+      new ApiClient(apiBase)
+        .source(symbol.file)
+        .then((fileContent) => {
+          setContent(fileContent);
+          setLoading(false);
+        })
+        .catch((err) => {
+          setError(err instanceof Error ? err.message : "Failed to read file");
+          setLoading(false);
+        });
+      return;
+    }
+
     if (!isTauri) {
       return;
     }
@@ -149,7 +200,7 @@ const SourcePreview = ({ symbol, rootPath }: SourcePreviewProps) => {
         }
         setLoading(false);
       });
-  }, [symbol.file, rootPath]);
+  }, [symbol.file, rootPath, apiBase]);
 
   const highlightedCode = useMemo(() => {
     if (!content) return null;
@@ -182,8 +233,8 @@ const SourcePreview = ({ symbol, rootPath }: SourcePreviewProps) => {
     return { code: syntaxHighlighted, startLine };
   }, [content, symbol.span, language]);
 
-  // Don't render anything if Tauri is not available (e.g., in browser/e2e tests)
-  if (!isTauri) {
+  // Without Tauri or a server there is no way to read the source.
+  if (!isTauri && !apiBase) {
     return null;
   }
 
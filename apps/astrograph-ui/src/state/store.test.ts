@@ -3,7 +3,7 @@ import { useAnalysisStore } from "./store";
 import type { AnalysisResult } from "../types";
 
 const sampleAnalysis: AnalysisResult = {
-  schema_version: "0.1.0",
+  schema_version: "0.2.0",
   root: "/tmp/sample",
   generated_at: "2026-01-28T00:00:00Z",
   stats: {
@@ -37,7 +37,7 @@ const sampleAnalysis: AnalysisResult = {
       fq_name: "main::main",
       container: null,
       is_exported: true,
-      is_entrypoint: true,
+      entrypoint_kinds: ["main"],
     },
   ],
   calls: [],
@@ -54,6 +54,7 @@ const resetStore = () => {
     graphViewMode: "all",
     layoutType: "cose",
     showEntrypointsOnly: false,
+    includePublicApi: false,
     highlightedPath: [],
   });
 };
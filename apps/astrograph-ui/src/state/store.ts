@@ -133,6 +133,8 @@ export type SidebarTab = "symbols" | "files" | "entrypoints";
 
 interface AnalysisState {
   analysis: AnalysisResult | null;
+  /** Base URL of the `astrograph serve` API the analysis came from, if any. */
+  apiBase: string | null;
   selectedSymbolId: string | null;
   selectedFile: string | null;
   search: string;
@@ -140,6 +142,7 @@ interface AnalysisState {
   graphViewMode: GraphViewMode;
   layoutType: LayoutType;
   showEntrypointsOnly: boolean;
+  includePublicApi: boolean;
   highlightedPath: string[];
   activeSidebarTab: SidebarTab;
   loadAnalysis: (data: AnalysisResult) => void;
@@ -151,12 +154,15 @@ interface AnalysisState {
   setGraphViewMode: (mode: GraphViewMode) => void;
   setLayoutType: (layout: LayoutType) => void;
   setShowEntrypointsOnly: (value: boolean) => void;
+  setIncludePublicApi: (value: boolean) => void;
   setHighlightedPath: (path: string[]) => void;
   setActiveSidebarTab: (tab: SidebarTab) => void;
+  setApiBase: (base: string | null) => void;
 }
 
 export const useAnalysisStore = create<AnalysisState>((set, get) => ({
   analysis: null,
+  apiBase: null,
   selectedSymbolId: null,
   selectedFile: null,
   search: "",
@@ -164,6 +170,7 @@ export const useAnalysisStore = create<AnalysisState>((set, get) => ({
   graphViewMode: "all",
   layoutType: "cose",
   showEntrypointsOnly: false,
+  includePublicApi: false,
   highlightedPath: [],
   activeSidebarTab: "symbols",
   loadAnalysis: (data) => {
@@ -220,6 +227,8 @@ export const useAnalysisStore = create<AnalysisState>((set, get) => ({
   setGraphViewMode: (mode) => set({ graphViewMode: mode }),
   setLayoutType: (layout) => set({ layoutType: layout }),
   setShowEntrypointsOnly: (value) => set({ showEntrypointsOnly: value }),
+  setIncludePublicApi: (value) => set({ includePublicApi: value }),
   setHighlightedPath: (path) => set({ highlightedPath: path }),
   setActiveSidebarTab: (tab) => set({ activeSidebarTab: tab }),
+  setApiBase: (base) => set({ apiBase: base }),
 }));
@@ -1,6 +1,20 @@
-export const CURRENT_SCHEMA_VERSION = "0.1.0";
+export const CURRENT_SCHEMA_VERSION = "0.2.0";
 
-export type Language = "rust" | "javascript" | "typescript" | "tsx";
+export type Language =
+  | "rust"
+  | "javascript"
+  | "typescript"
+  | "tsx"
+  | "java"
+  | "kotlin"
+  | "c"
+  | "cpp"
+  | "vue"
+  | "svelte"
+  | "astro"
+  | "markdown"
+  // Languages added through an engine plugin, by name.
+  | (string & {});
 
 export type SymbolKind =
   | "class"
@@ -11,7 +25,15 @@ export type SymbolKind =
   | "module"
   | "namespace"
   | "function"
-  | "method";
+  | "method"
+  | "constant"
+  | "static"
+  | "type_alias"
+  | "union"
+  | "macro"
+  | "closure"
+  | "module_init"
+  | "external";
 
 export interface Span {
   start_line: number;
@@ -25,6 +47,14 @@ export interface FileInfo {
   language: Language;
   hash: string;
   byte_size: number;
+  external?: boolean;
+}
+
+export type Modifier = "async" | "unsafe" | "const" | "static" | "extern" | "abstract";
+
+export interface Parameter {
+  name: string;
+  type_name?: string | null;
 }
 
 export interface Symbol {
@@ -36,14 +66,42 @@ export interface Symbol {
   fq_name: string;
   container?: string | null;
   is_exported: boolean;
-  is_entrypoint: boolean;
+  entrypoint_kinds: EntrypointKind[];
+  entrypoint_rules?: string[];
+  signature?: string | null;
+  parameters?: Parameter[];
+  return_type?: string | null;
+  generics?: string | null;
+  modifiers?: Modifier[];
+  doc?: string | null;
+  external?: boolean;
+  declaration?: boolean;
 }
 
+export type EntrypointKind =
+  | "main"
+  | "public_api"
+  | "test"
+  | "framework_handler"
+  | "ffi_export"
+  | "module_init"
+  | "manual";
+
+export const isEntrypoint = (symbol: Symbol): boolean =>
+  symbol.entrypoint_kinds.length > 0;
+
+/** Entrypoints that something actually runs, as opposed to plain public API. */
+export const isProgramEntrypoint = (symbol: Symbol): boolean =>
+  symbol.entrypoint_kinds.some((kind) => kind !== "public_api");
+
+export type CallKind = "call" | "macro" | "defines" | "ffi" | "renders";
+
 export interface CallEdge {
   id: string;
   caller_id: string;
   callee_name: string;
   callee_id?: string | null;
+  kind?: CallKind;
   file: string;
   span: Span;
 }
@@ -53,6 +111,8 @@ export interface AnalysisStats {
   symbol_count: number;
   call_count: number;
   entrypoint_count: number;
+  program_entrypoint_count?: number;
+  diagnostic_count?: number;
   reused_cache_files: number;
   reanalyzed_files: number;
 }
@@ -66,6 +126,16 @@ export interface AnalysisResult {
   symbols: Symbol[];
   calls: CallEdge[];
   entrypoints: string[];
+  diagnostics?: Diagnostic[];
+}
+
+export type DiagnosticKind = "syntax_error" | "encoding" | "unsupported" | "oversized";
+
+export interface Diagnostic {
+  file: string;
+  kind: DiagnosticKind;
+  message: string;
+  span: Span | null;
 }
 
 export interface Bookmark {
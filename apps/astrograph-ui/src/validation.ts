@@ -26,11 +26,9 @@ const isBoolean = (value: unknown): value is boolean =>
 const isStringArray = (value: unknown): value is string[] =>
   Array.isArray(value) && value.every(isString);
 
+// Plugins can add languages, so any name is accepted.
 const isLanguage = (value: unknown): value is FileInfo["language"] =>
-  value === "rust" ||
-  value === "javascript" ||
-  value === "typescript" ||
-  value === "tsx";
+  isString(value) && value.length > 0;
 
 const isSpan = (value: unknown): value is Symbol["span"] => {
   if (!isObject(value)) return false;
@@ -83,7 +81,8 @@ const isSymbolArray = (value: unknown): value is Symbol[] =>
       isString(item.fq_name) &&
       containerOk &&
       isBoolean(item.is_exported) &&
-      isBoolean(item.is_entrypoint)
+      Array.isArray(item.entrypoint_kinds) &&
+      item.entrypoint_kinds.every(isString)
     );
   });
 
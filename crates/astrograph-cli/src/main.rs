@@ -1,6 +1,9 @@
+mod serve;
+
 use anyhow::{Context, Result};
-use astrograph_engine::{analyze_project, AnalysisCache, AnalysisConfig};
-use clap::Parser;
+use astrograph_engine::config::OutputFormat;
+use astrograph_engine::{analyze_project, AnalysisCache, ProjectSettings};
+use clap::{Args, Parser, Subcommand};
 use std::fs;
 use std::path::PathBuf;
 use std::process;
@@ -9,28 +12,151 @@ use std::process;
 #[command(
     name = "astrograph",
     version,
-    about = "Static analysis CLI for Astrograph"
+    about = "Static analysis CLI for Astrograph",
+    args_conflicts_with_subcommands = true
 )]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    analysis: AnalysisArgs,
+
+    /// Output JSON file path [default: `output.path` from astrograph.toml,
+    /// else analysis.json].
+    #[arg(long)]
+    out: Option<PathBuf>,
+}
+
+const DEFAULT_OUT: &str = "analysis.json";
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Analyze once and serve the result over a local HTTP/JSON API.
+    Serve(ServeArgs),
+}
+
+#[derive(Debug, Args)]
+struct AnalysisArgs {
     /// Root directory of the repository to analyze.
     #[arg(long, default_value = ".")]
     root: PathBuf,
 
-    /// Output JSON file path.
-    #[arg(long, default_value = "analysis.json")]
-    out: PathBuf,
-
-    /// Optional cache file path for incremental analysis.
+    /// Cache file for incremental analysis [default: from the `[cache]`
+    /// section of astrograph.toml, if any; otherwise no cache].
     #[arg(long)]
     cache: Option<PathBuf>,
 
-    /// Mark entrypoints manually (repeatable).
+    /// Neither read nor write the cache, even when astrograph.toml sets one.
+    #[arg(long, conflicts_with = "cache")]
+    no_cache: bool,
+
+    /// Mark entrypoints manually (repeatable). Accepts globs, `re:` regexes,
+    /// a `kind:` filter and a `file-glob::` scope, e.g.
+    /// `function:src/handlers/**::handle_*`.
     #[arg(long = "entrypoint")]
     entrypoints: Vec<String>,
 
     /// Follow symlinks while scanning.
     #[arg(long)]
     follow_symlinks: bool,
+
+    /// Walk hidden (dot) files and directories too.
+    #[arg(long)]
+    include_hidden: bool,
+
+    /// Analyze JavaScript and TypeScript code blocks in Markdown files.
+    #[arg(long)]
+    include_markdown: bool,
+
+    /// Skip files larger than this many bytes; 0 analyzes files of any size
+    /// [default: 1048576].
+    #[arg(long, value_name = "BYTES")]
+    max_file_size: Option<u64>,
+
+    /// Skip directories with this name, in addition to the defaults (repeatable).
+    #[arg(long = "ignore-dir")]
+    ignore_dirs: Vec<String>,
+
+    /// Analyze this crate from the Cargo registry cache as a read-only
+    /// library, as `name` or `name@version` (repeatable).
+    #[arg(long = "library-crate")]
+    library_crates: Vec<String>,
+
+    /// Analyze this package from `node_modules` as a read-only library
+    /// (repeatable).
+    #[arg(long = "library-package")]
+    library_packages: Vec<String>,
+
+    /// List every skipped path with the rule that skipped it, and every
+    /// diagnostic.
+    #[arg(long, short)]
+    verbose: bool,
+}
+
+#[derive(Debug, Args)]
+struct ServeArgs {
+    #[command(flatten)]
+    analysis: AnalysisArgs,
+
+    /// Address to bind the HTTP server to.
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port to listen on.
+    #[arg(long, default_value_t = 7878)]
+    port: u16,
+
+    /// Re-analyze when files under the root change.
+    #[arg(long)]
+    watch: bool,
+
+    /// Origin allowed to call the API from a browser (e.g.
+    /// `http://localhost:5173`); repeat for several. Other origins are refused.
+    #[arg(long = "allow-origin", value_name = "ORIGIN")]
+    allow_origins: Vec<String>,
+
+    /// Directory with a built UI (e.g. apps/astrograph-ui/dist) to serve at
+    /// `/`, where it loads the analysis from this server.
+    #[arg(long)]
+    ui_dir: Option<PathBuf>,
+}
+
+impl AnalysisArgs {
+    /// Settings from the project's `astrograph.toml` with flags applied on top.
+    fn settings(&self) -> Result<ProjectSettings> {
+        let mut settings = ProjectSettings::for_project(&self.root)?;
+        let config = &mut settings.config;
+        config.follow_symlinks |= self.follow_symlinks;
+        config.include_hidden |= self.include_hidden;
+        config.include_markdown |= self.include_markdown;
+        if let Some(size) = self.max_file_size {
+            config.max_file_size = (size > 0).then_some(size);
+        }
+        config.ignored_dirs.extend(self.ignore_dirs.iter().cloned());
+        config.report_skipped = self.verbose;
+        config
+            .libraries
+            .crates
+            .extend(self.library_crates.iter().cloned());
+        config
+            .libraries
+            .packages
+            .extend(self.library_packages.iter().cloned());
+        config
+            .manual_entrypoints
+            .extend(self.entrypoints.iter().cloned());
+        Ok(settings)
+    }
+
+    /// The cache file: `--cache` (relative to the working directory), else
+    /// the project's `[cache]` section. The CLI does not cache otherwise.
+    fn cache_path(&self, settings: &ProjectSettings) -> Option<PathBuf> {
+        if self.no_cache {
+            return None;
+        }
+        self.cache.clone().or_else(|| settings.cache_path(false))
+    }
 }
 
 fn main() {
@@ -43,37 +169,74 @@ fn main() {
 fn run() -> Result<()> {
     let cli = Cli::parse();
 
-    println!("Analyzing {} ...", cli.root.display());
-
-    let mut config = AnalysisConfig::new(&cli.root);
-    config.follow_symlinks = cli.follow_symlinks;
-    config.manual_entrypoints = cli.entrypoints;
+    match cli.command {
+        Some(Command::Serve(args)) => serve::run(args),
+        None => analyze(cli.analysis, cli.out),
+    }
+}
 
-    let cache = load_cache(cli.cache.as_ref())?;
-    let output = analyze_project(config, cache, None::<fn(astrograph_engine::ProgressEvent)>)?;
+fn analyze(args: AnalysisArgs, out: Option<PathBuf>) -> Result<()> {
+    println!("Analyzing {} ...", args.root.display());
 
-    println!("Writing analysis to {} ...", cli.out.display());
+    let settings = args.settings()?;
+    let out = out
+        .or_else(|| settings.output_path())
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_OUT));
+    let cache_path = args.cache_path(&settings);
+    let cache = load_cache(cache_path.as_ref())?;
+    let output = analyze_project(
+        settings.config,
+        cache,
+        None::<fn(astrograph_engine::ProgressEvent)>,
+    )?;
 
-    let json = serde_json::to_string_pretty(&output.result)?;
-    fs::write(&cli.out, json).with_context(|| format!("Failed to write {}", cli.out.display()))?;
+    print_skipped(&output.skipped);
+    if args.verbose {
+        print_diagnostics(&output.result.diagnostics);
+    }
+    println!("Writing analysis to {} ...", out.display());
 
-    if let Some(cache_path) = cli.cache {
-        println!("Writing cache to {} ...", cache_path.display());
+    let json = match settings.project.output.format {
+        OutputFormat::Pretty => serde_json::to_string_pretty(&output.result)?,
+        OutputFormat::Compact => serde_json::to_string(&output.result)?,
+    };
+    fs::write(&out, json).with_context(|| format!("Failed to write {}", out.display()))?;
 
-        let cache_json = serde_json::to_string_pretty(&output.cache)?;
-        fs::write(&cache_path, cache_json)
-            .with_context(|| format!("Failed to write {}", cache_path.display()))?;
+    if let Some(cache_path) = &cache_path {
+        save_cache(cache_path, &output.cache)?;
     }
 
     println!("Astrograph analysis complete.");
     println!("Files: {}", output.result.stats.file_count);
     println!("Symbols: {}", output.result.stats.symbol_count);
     println!("Calls: {}", output.result.stats.call_count);
-    println!("Entrypoints: {}", output.result.stats.entrypoint_count);
+    println!(
+        "Entrypoints: {} ({} excluding public API)",
+        output.result.stats.entrypoint_count, output.result.stats.program_entrypoint_count
+    );
+    println!("Diagnostics: {}", output.result.stats.diagnostic_count);
 
     Ok(())
 }
 
+fn print_skipped(skipped: &[astrograph_engine::SkippedPath]) {
+    for entry in skipped {
+        println!("Skipped {} ({})", entry.path, entry.rule);
+    }
+}
+
+fn print_diagnostics(diagnostics: &[astrograph_engine::Diagnostic]) {
+    for diagnostic in diagnostics {
+        match &diagnostic.span {
+            Some(span) => println!(
+                "{}:{}:{}: {}",
+                diagnostic.file, span.start_line, span.start_col, diagnostic.message
+            ),
+            None => println!("{}: {}", diagnostic.file, diagnostic.message),
+        }
+    }
+}
+
 fn load_cache(path: Option<&PathBuf>) -> Result<Option<AnalysisCache>> {
     let Some(path) = path else {
         return Ok(None);
@@ -87,6 +250,13 @@ fn load_cache(path: Option<&PathBuf>) -> Result<Option<AnalysisCache>> {
     Ok(Some(cache))
 }
 
+fn save_cache(path: &PathBuf, cache: &AnalysisCache) -> Result<()> {
+    println!("Writing cache to {} ...", path.display());
+
+    let cache_json = serde_json::to_string_pretty(cache)?;
+    fs::write(path, cache_json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
 fn format_user_friendly_error(err: &anyhow::Error) -> String {
     if let Some(io_err) = err.downcast_ref::<std::io::Error>() {
         use std::io::ErrorKind;
@@ -98,6 +268,9 @@ fn format_user_friendly_error(err: &anyhow::Error) -> String {
             ErrorKind::PermissionDenied => {
                 "Permission denied when accessing files. Check your file permissions.".to_string()
             }
+            ErrorKind::AddrInUse => {
+                "The requested port is already in use. Pick another one with --port.".to_string()
+            }
             _ => err.to_string(),
         };
     }
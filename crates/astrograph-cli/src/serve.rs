@@ -0,0 +1,787 @@
+use crate::{load_cache, print_skipped, save_cache, ServeArgs};
+use anyhow::{anyhow, Result};
+use astrograph_engine::{
+    analyze_project, AnalysisCache, AnalysisConfig, AnalysisIndex, EntrypointKind, ProgressEvent,
+    SymbolKind, SymbolQuery, WatchFilter,
+};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::net::IpAddr;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::Duration;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const DEFAULT_SEARCH_LIMIT: usize = 50;
+const DEFAULT_PATH_DEPTH: usize = 12;
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+/// Threads answering requests, so a slow one (a `POST /api/reanalyze`, a
+/// large `/api/analysis`) does not hold up the others.
+const REQUEST_THREADS: usize = 4;
+
+/// Shared server state: the current analysis plus what is needed to redo it.
+struct ServerState {
+    config: AnalysisConfig,
+    cache_path: Option<PathBuf>,
+    index: RwLock<Arc<AnalysisIndex>>,
+    cache: Mutex<AnalysisCache>,
+}
+
+impl ServerState {
+    fn index(&self) -> Arc<AnalysisIndex> {
+        self.index
+            .read()
+            .map(|index| Arc::clone(&index))
+            .unwrap_or_else(|poisoned| Arc::clone(&poisoned.into_inner()))
+    }
+
+    fn reanalyze(&self) -> Result<()> {
+        let mut cache = self
+            .cache
+            .lock()
+            .map_err(|_| anyhow!("Cache lock poisoned"))?;
+        let output = analyze_project(
+            self.config.clone(),
+            Some(cache.clone()),
+            None::<fn(ProgressEvent)>,
+        )?;
+        if let Some(cache_path) = &self.cache_path {
+            save_cache(cache_path, &output.cache)?;
+        }
+        *cache = output.cache;
+
+        let index = Arc::new(AnalysisIndex::new(output.result));
+        let mut current = self
+            .index
+            .write()
+            .map_err(|_| anyhow!("Index lock poisoned"))?;
+        *current = index;
+        Ok(())
+    }
+}
+
+pub fn run(args: ServeArgs) -> Result<()> {
+    println!("Analyzing {} ...", args.analysis.root.display());
+
+    let settings = args.analysis.settings()?;
+    let cache_path = args.analysis.cache_path(&settings);
+    let config = settings.config;
+    let cache = load_cache(cache_path.as_ref())?;
+    let output = analyze_project(config.clone(), cache, None::<fn(ProgressEvent)>)?;
+    print_skipped(&output.skipped);
+    if let Some(cache_path) = &cache_path {
+        save_cache(cache_path, &output.cache)?;
+    }
+
+    let stats = &output.result.stats;
+    println!(
+        "Indexed {} files, {} symbols, {} calls.",
+        stats.file_count, stats.symbol_count, stats.call_count
+    );
+
+    let state = Arc::new(ServerState {
+        config,
+        cache_path,
+        index: RwLock::new(Arc::new(AnalysisIndex::new(output.result))),
+        cache: Mutex::new(output.cache),
+    });
+
+    // The watcher must outlive the request loop, so keep it bound here.
+    let _watcher = if args.watch {
+        Some(watch_root(Arc::clone(&state))?)
+    } else {
+        None
+    };
+
+    let address = format!("{}:{}", args.host, args.port);
+    let server = Server::http(&address).map_err(|err| match err.downcast::<std::io::Error>() {
+        Ok(io_err) => anyhow::Error::from(*io_err),
+        Err(err) => anyhow!(err),
+    })?;
+    println!("Serving Astrograph API on http://{address}/api");
+    if args.ui_dir.is_some() {
+        println!("Serving UI on http://{address}/");
+    }
+
+    let server = Arc::new(server);
+    let access = Arc::new(Access {
+        host: args.host.clone(),
+        origins: args.allow_origins.clone(),
+    });
+    let ui_dir = Arc::new(args.ui_dir.clone());
+    let threads: Vec<_> = (0..REQUEST_THREADS)
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let state = Arc::clone(&state);
+            let access = Arc::clone(&access);
+            let ui_dir = Arc::clone(&ui_dir);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    let response = handle_request(&request, &state, &access, ui_dir.as_deref());
+                    if let Err(err) = request.respond(response) {
+                        eprintln!("Failed to send response: {err}");
+                    }
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread
+            .join()
+            .map_err(|_| anyhow!("Request thread panicked"))?;
+    }
+
+    Ok(())
+}
+
+fn watch_root(state: Arc<ServerState>) -> Result<RecommendedWatcher> {
+    let root = state.config.root.canonicalize()?;
+    // Changes the analysis would skip, such as build output under `target/`
+    // or `node_modules/`, do not trigger a run.
+    let filter = WatchFilter::new(&state.config)?;
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            let relevant = event.paths.iter().any(|path| filter.is_relevant(path));
+            if relevant {
+                let _ = sender.send(());
+            }
+        }
+    })?;
+    watcher.watch(&root, RecursiveMode::Recursive)?;
+    println!("Watching {} for changes ...", root.display());
+
+    thread::spawn(move || {
+        while receiver.recv().is_ok() {
+            // Coalesce bursts of events (editor saves, branch switches) into one run.
+            while receiver.recv_timeout(WATCH_DEBOUNCE).is_ok() {}
+            match state.reanalyze() {
+                Ok(()) => {
+                    let index = state.index();
+                    println!(
+                        "Re-analyzed: {} files, {} symbols, {} calls.",
+                        index.result().stats.file_count,
+                        index.result().stats.symbol_count,
+                        index.result().stats.call_count
+                    );
+                }
+                Err(err) => eprintln!("Re-analysis failed: {err}"),
+            }
+        }
+    });
+
+    Ok(watcher)
+}
+
+type HttpResponse = Response<std::io::Cursor<Vec<u8>>>;
+
+/// Which browsers may talk to the server. The API serves source code, so
+/// pages from other origins can only read it when listed, and requests for
+/// unknown host names (DNS rebinding) are refused.
+struct Access {
+    /// The host the server is bound to.
+    host: String,
+    /// Origins allowed to make cross-origin requests.
+    origins: Vec<String>,
+}
+
+impl Access {
+    /// Whether a `Host` header names this server: `localhost`, an IP address,
+    /// the bound host or the host of an allowed origin.
+    fn allows_host(&self, host: &str) -> bool {
+        let name = host_name(host);
+        name.eq_ignore_ascii_case("localhost")
+            || name.parse::<IpAddr>().is_ok()
+            || name.eq_ignore_ascii_case(host_name(&self.host))
+            || self.origins.iter().any(|origin| {
+                let origin_host = origin
+                    .split_once("://")
+                    .map_or(origin.as_str(), |(_, rest)| rest);
+                name.eq_ignore_ascii_case(host_name(origin_host))
+            })
+    }
+
+    /// Whether a page on `origin` may call the API: an allowed origin, or
+    /// this server itself as named by the request's `host` (the UI served
+    /// with `--ui-dir`).
+    fn allows_origin(&self, origin: &str, host: Option<&str>) -> bool {
+        let origin = origin.trim_end_matches('/');
+        let same_origin = host.is_some_and(|host| {
+            origin
+                .split_once("://")
+                .is_some_and(|(_, authority)| authority.eq_ignore_ascii_case(host))
+        });
+        same_origin
+            || self
+                .origins
+                .iter()
+                .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+    }
+}
+
+/// The name part of a `host[:port]` value, without IPv6 brackets.
+fn host_name(host: &str) -> &str {
+    if let Some(rest) = host.strip_prefix('[') {
+        return rest.split(']').next().unwrap_or(rest);
+    }
+    match host.rsplit_once(':') {
+        // A bare IPv6 address has several colons and no port.
+        Some((name, port)) if !name.contains(':') && port.bytes().all(|b| b.is_ascii_digit()) => {
+            name
+        }
+        _ => host,
+    }
+}
+
+fn request_header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+fn handle_request(
+    request: &Request,
+    state: &ServerState,
+    access: &Access,
+    ui_dir: Option<&Path>,
+) -> HttpResponse {
+    let host = request_header(request, "Host");
+    if host.is_some_and(|host| !access.allows_host(host)) {
+        return json_response(403, &json!({ "error": "Host not allowed" }));
+    }
+    // Browsers send `Origin` on cross-origin requests; tools such as curl do
+    // not, and are always allowed.
+    let origin = request_header(request, "Origin");
+    if origin.is_some_and(|origin| !access.allows_origin(origin, host)) {
+        return json_response(403, &json!({ "error": "Origin not allowed" }));
+    }
+
+    let url = request.url();
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let params: HashMap<String, String> = form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect();
+
+    if request.method() == &Method::Options {
+        return with_cors(
+            Response::from_data(Vec::new()).with_status_code(204),
+            origin,
+        );
+    }
+
+    if let Some(api_path) = path.strip_prefix("/api") {
+        let segments: Vec<String> = api_path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(percent_decode)
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let result = route_api(request.method(), &segments, &params, state);
+        let response = match result {
+            Ok(value) => json_response(200, &value),
+            Err(ApiError { status, message }) => {
+                json_response(status, &json!({ "error": message }))
+            }
+        };
+        return with_cors(response, origin);
+    }
+
+    match ui_dir {
+        Some(dir) if request.method() == &Method::Get => serve_static(dir, path),
+        _ => json_response(404, &json!({ "error": "Not found" })),
+    }
+}
+
+/// A file of the built UI. Paths leaving `dir` are refused, and unknown
+/// routes fall back to `index.html` for client-side routing.
+fn serve_static(dir: &Path, url_path: &str) -> HttpResponse {
+    let relative = percent_decode(url_path.trim_start_matches('/'));
+    let relative = Path::new(&relative);
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return json_response(400, &json!({ "error": "Invalid path" }));
+    }
+
+    let mut file = dir.join(relative);
+    if !file.is_file() {
+        file = dir.join("index.html");
+    }
+    match fs::read(&file) {
+        Ok(bytes) => {
+            Response::from_data(bytes).with_header(header("Content-Type", content_type(&file)))
+        }
+        Err(_) => json_response(404, &json!({ "error": "Not found" })),
+    }
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+    {
+        "html" => "text/html; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "json" => "application/json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "ico" => "image/x-icon",
+        "woff2" => "font/woff2",
+        _ => "application/octet-stream",
+    }
+}
+
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self {
+            status: 400,
+            message: message.into(),
+        }
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            status: 404,
+            message: message.into(),
+        }
+    }
+}
+
+fn route_api(
+    method: &Method,
+    segments: &[&str],
+    params: &HashMap<String, String>,
+    state: &ServerState,
+) -> Result<Value, ApiError> {
+    if method == &Method::Post {
+        return match segments {
+            ["reanalyze"] => {
+                state.reanalyze().map_err(|err| ApiError {
+                    status: 500,
+                    message: format!("Re-analysis failed: {err}"),
+                })?;
+                Ok(json!(state.index().result().stats))
+            }
+            _ => Err(ApiError::not_found("Unknown endpoint")),
+        };
+    }
+    if method != &Method::Get {
+        return Err(ApiError {
+            status: 405,
+            message: "Method not allowed".to_string(),
+        });
+    }
+
+    let index = state.index();
+    match segments {
+        ["analysis"] => Ok(json!(index.result())),
+        ["stats"] => Ok(json!(index.result().stats)),
+        ["entrypoints"] => {
+            let kind = params
+                .get("kind")
+                .map(|kind| parse_entrypoint_kind(kind))
+                .transpose()?;
+            let symbols: Vec<_> = index
+                .result()
+                .entrypoints
+                .iter()
+                .filter_map(|id| index.symbol(id))
+                .filter(|symbol| {
+                    kind.as_ref()
+                        .is_none_or(|kind| symbol.entrypoint_kinds.contains(kind))
+                })
+                .collect();
+            Ok(json!(symbols))
+        }
+        ["files"] => Ok(json!(index.files())),
+        ["files", "symbols"] => {
+            let file = required_param(params, "path")?;
+            if index.file(file).is_none() {
+                return Err(ApiError::not_found(format!("Unknown file: {file}")));
+            }
+            Ok(json!(index.symbols_in_file(file)))
+        }
+        ["symbols"] => {
+            let kind = params
+                .get("kind")
+                .map(|kind| parse_kind(kind))
+                .transpose()?;
+            let limit = optional_usize(params, "limit")?.unwrap_or(DEFAULT_SEARCH_LIMIT);
+            let query = SymbolQuery {
+                text: params.get("q").cloned().unwrap_or_default(),
+                kind,
+                file: params.get("file").cloned(),
+                limit: Some(limit),
+            };
+            Ok(json!(index.search(&query)))
+        }
+        ["symbols", id] => index
+            .symbol(id)
+            .map(|symbol| json!(symbol))
+            .ok_or_else(|| ApiError::not_found(format!("Unknown symbol: {id}"))),
+        ["symbols", id, "callers"] => {
+            ensure_symbol(&index, id)?;
+            Ok(json!(index.callers(id)))
+        }
+        ["symbols", id, "callees"] => {
+            ensure_symbol(&index, id)?;
+            Ok(json!(index.callees(id)))
+        }
+        ["path"] => {
+            let from = required_param(params, "from")?;
+            let to = required_param(params, "to")?;
+            ensure_symbol(&index, from)?;
+            ensure_symbol(&index, to)?;
+            let max_depth = optional_usize(params, "max_depth")?.unwrap_or(DEFAULT_PATH_DEPTH);
+            let path = index.shortest_path(from, to, max_depth).map(|ids| {
+                ids.iter()
+                    .filter_map(|id| index.symbol(id))
+                    .collect::<Vec<_>>()
+            });
+            Ok(json!({ "from": from, "to": to, "path": path }))
+        }
+        ["source"] => source_snippet(&index, params),
+        _ => Err(ApiError::not_found("Unknown endpoint")),
+    }
+}
+
+fn source_snippet(
+    index: &AnalysisIndex,
+    params: &HashMap<String, String>,
+) -> Result<Value, ApiError> {
+    let file = required_param(params, "file")?;
+    // Only files that were part of the analysis can be read, which also keeps
+    // requests from escaping the analysis root.
+    if index.file(file).is_none() {
+        return Err(ApiError::not_found(format!("Unknown file: {file}")));
+    }
+
+    let path = Path::new(&index.result().root).join(file);
+    let content = fs::read_to_string(&path).map_err(|err| ApiError {
+        status: 500,
+        message: format!("Error reading file: {err}"),
+    })?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    let start_line = optional_usize(params, "start_line")?.unwrap_or(1).max(1);
+    let end_line = optional_usize(params, "end_line")?
+        .unwrap_or(lines.len())
+        .min(lines.len());
+    let snippet = if start_line <= end_line {
+        lines[start_line - 1..end_line].join("\n")
+    } else {
+        String::new()
+    };
+
+    Ok(json!({
+        "file": file,
+        "start_line": start_line,
+        "end_line": end_line,
+        "content": snippet,
+    }))
+}
+
+fn ensure_symbol(index: &AnalysisIndex, id: &str) -> Result<(), ApiError> {
+    index
+        .symbol(id)
+        .map(|_| ())
+        .ok_or_else(|| ApiError::not_found(format!("Unknown symbol: {id}")))
+}
+
+fn required_param<'a>(
+    params: &'a HashMap<String, String>,
+    name: &str,
+) -> Result<&'a str, ApiError> {
+    params
+        .get(name)
+        .map(String::as_str)
+        .ok_or_else(|| ApiError::bad_request(format!("Missing query parameter '{name}'")))
+}
+
+fn optional_usize(params: &HashMap<String, String>, name: &str) -> Result<Option<usize>, ApiError> {
+    params
+        .get(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| ApiError::bad_request(format!("Invalid number for '{name}'")))
+        })
+        .transpose()
+}
+
+fn parse_kind(value: &str) -> Result<SymbolKind, ApiError> {
+    serde_json::from_value(Value::String(value.to_string()))
+        .map_err(|_| ApiError::bad_request(format!("Unknown symbol kind: {value}")))
+}
+
+fn parse_entrypoint_kind(value: &str) -> Result<EntrypointKind, ApiError> {
+    serde_json::from_value(Value::String(value.to_string()))
+        .map_err(|_| ApiError::bad_request(format!("Unknown entrypoint kind: {value}")))
+}
+
+fn percent_decode(segment: &str) -> String {
+    percent_encoding::percent_decode_str(segment)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+fn json_response(status: u16, value: &Value) -> HttpResponse {
+    let body = serde_json::to_vec(value).unwrap_or_default();
+    Response::from_data(body)
+        .with_status_code(status)
+        .with_header(header("Content-Type", "application/json"))
+}
+
+/// Lets `origin` read the response when it is an allowed origin; requests
+/// from other origins were already refused.
+fn with_cors(response: HttpResponse, origin: Option<&str>) -> HttpResponse {
+    let Some(origin) = origin else {
+        return response;
+    };
+    response
+        .with_header(header("Access-Control-Allow-Origin", origin))
+        .with_header(header("Vary", "Origin"))
+        .with_header(header("Access-Control-Allow-Methods", "GET, POST, OPTIONS"))
+        .with_header(header("Access-Control-Allow-Headers", "Content-Type"))
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("static header is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tiny_http::TestRequest;
+
+    fn serve(files: &[(&str, &str)]) -> (TempDir, ServerState) {
+        let root = tempfile::tempdir().unwrap();
+        for (path, contents) in files {
+            let path = root.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        let config = AnalysisConfig::new(root.path());
+        let output = analyze_project(config.clone(), None, None::<fn(ProgressEvent)>).unwrap();
+        let state = ServerState {
+            config,
+            cache_path: None,
+            index: RwLock::new(Arc::new(AnalysisIndex::new(output.result))),
+            cache: Mutex::new(output.cache),
+        };
+        (root, state)
+    }
+
+    fn access() -> Access {
+        Access {
+            host: "127.0.0.1".to_string(),
+            origins: vec!["http://localhost:5173".to_string()],
+        }
+    }
+
+    /// Status, body and `Access-Control-Allow-Origin` of a request.
+    fn send(
+        state: &ServerState,
+        method: Method,
+        path: &str,
+        headers: &[(&str, &str)],
+    ) -> (u16, Value, Option<String>) {
+        let request = headers.iter().fold(
+            TestRequest::new().with_method(method).with_path(path),
+            |request, (name, value)| request.with_header(header(name, value)),
+        );
+        let response = handle_request(&request.into(), state, &access(), None);
+        let allowed_origin = response
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv("Access-Control-Allow-Origin"))
+            .map(|header| header.value.to_string());
+        let status = response.status_code().0;
+        let body = serde_json::from_slice(&response.into_reader().into_inner()).unwrap();
+        (status, body, allowed_origin)
+    }
+
+    fn get(state: &ServerState, path: &str) -> (u16, Value) {
+        let (status, body, _) = send(state, Method::Get, path, &[("Host", "127.0.0.1:7878")]);
+        (status, body)
+    }
+
+    const FILES: &[(&str, &str)] = &[
+        (
+            "src/app.ts",
+            "export function main() {\n  helper();\n}\n\nfunction helper() {}\n",
+        ),
+        ("src/notes.txt", "not analyzed\n"),
+        ("secret.env", "TOKEN=1\n"),
+    ];
+
+    #[test]
+    fn hosts_and_origins_are_checked() {
+        let access = access();
+        for host in [
+            "localhost:7878",
+            "127.0.0.1:7878",
+            "[::1]:7878",
+            "LOCALHOST",
+        ] {
+            assert!(access.allows_host(host), "{host}");
+        }
+        for host in [
+            "evil.example",
+            "evil.example:7878",
+            "localhost.evil.example",
+        ] {
+            assert!(!access.allows_host(host), "{host}");
+        }
+        assert!(access.allows_origin("http://localhost:5173/", None));
+        assert!(!access.allows_origin("http://localhost:5174", None));
+        assert!(!access.allows_origin("null", None));
+        // Pages served by the server itself (`--ui-dir`) are same-origin.
+        assert!(access.allows_origin("http://127.0.0.1:7878", Some("127.0.0.1:7878")));
+        assert!(!access.allows_origin("http://127.0.0.1:7879", Some("127.0.0.1:7878")));
+
+        let (_root, state) = serve(FILES);
+        let stats = |headers: &[(&str, &str)]| send(&state, Method::Get, "/api/stats", headers);
+        assert_eq!(stats(&[("Host", "rebind.evil.example")]).0, 403);
+        assert_eq!(
+            stats(&[
+                ("Host", "127.0.0.1:7878"),
+                ("Origin", "http://evil.example")
+            ])
+            .0,
+            403
+        );
+        let (status, _, allowed) = stats(&[
+            ("Host", "127.0.0.1:7878"),
+            ("Origin", "http://localhost:5173"),
+        ]);
+        assert_eq!(status, 200);
+        assert_eq!(allowed.as_deref(), Some("http://localhost:5173"));
+        let (status, _, allowed) = stats(&[("Host", "127.0.0.1:7878")]);
+        assert_eq!(status, 200);
+        assert_eq!(allowed, None);
+    }
+
+    #[test]
+    fn source_only_reads_analyzed_files() {
+        let (_root, state) = serve(FILES);
+
+        let (status, body) = get(&state, "/api/source?file=src/app.ts&start_line=5");
+        assert_eq!(status, 200);
+        assert_eq!(body["content"], "function helper() {}");
+
+        for file in [
+            "src/notes.txt",
+            "secret.env",
+            "src/../secret.env",
+            "../etc/passwd",
+        ] {
+            let (status, _) = get(&state, &format!("/api/source?file={file}"));
+            assert_eq!(status, 404, "{file}");
+        }
+        assert_eq!(get(&state, "/api/source").0, 400);
+    }
+
+    #[test]
+    fn source_line_ranges_are_clamped() {
+        let (_root, state) = serve(FILES);
+        let range = |query: &str| {
+            let (status, body) = get(&state, &format!("/api/source?file=src/app.ts&{query}"));
+            assert_eq!(status, 200, "{query}");
+            (
+                body["start_line"].as_u64().unwrap(),
+                body["end_line"].as_u64().unwrap(),
+                body["content"].as_str().unwrap().to_string(),
+            )
+        };
+
+        assert_eq!(
+            range("start_line=0&end_line=1"),
+            (1, 1, "export function main() {".to_string())
+        );
+        assert_eq!(range("start_line=4&end_line=99").1, 5);
+        assert_eq!(range("start_line=99").2, "");
+        assert_eq!(range("start_line=3&end_line=2").2, "");
+        let (status, _) = get(&state, "/api/source?file=src/app.ts&start_line=-1");
+        assert_eq!(status, 400);
+    }
+
+    #[test]
+    fn ui_files_are_served_from_the_ui_dir() {
+        let (_root, state) = serve(FILES);
+        let ui = tempfile::tempdir().unwrap();
+        fs::create_dir_all(ui.path().join("assets")).unwrap();
+        fs::write(ui.path().join("index.html"), "<!doctype html>").unwrap();
+        fs::write(ui.path().join("assets/app.js"), "render();").unwrap();
+        let get = |path: &str| {
+            let request = TestRequest::new()
+                .with_path(path)
+                .with_header(header("Host", "127.0.0.1:7878"));
+            let response = handle_request(&request.into(), &state, &access(), Some(ui.path()));
+            let content_type = response
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv("Content-Type"))
+                .map(|header| header.value.to_string())
+                .unwrap_or_default();
+            let body = String::from_utf8(response.into_reader().into_inner()).unwrap();
+            (content_type, body)
+        };
+
+        assert_eq!(
+            get("/"),
+            (
+                "text/html; charset=utf-8".to_string(),
+                "<!doctype html>".to_string()
+            )
+        );
+        assert_eq!(get("/assets/app.js").1, "render();");
+        assert!(get("/assets/app.js").0.starts_with("text/javascript"));
+        assert_eq!(get("/symbols/abc").1, "<!doctype html>");
+        assert!(get("/..%2Fsecret.env").1.contains("Invalid path"));
+        assert!(get("/api/stats").0.starts_with("application/json"));
+    }
+
+    #[test]
+    fn unknown_routes_and_symbols_are_not_found() {
+        let (_root, state) = serve(FILES);
+
+        assert_eq!(get(&state, "/").0, 404);
+        assert_eq!(get(&state, "/api/unknown").0, 404);
+        assert_eq!(get(&state, "/api/symbols/missing").0, 404);
+        assert_eq!(get(&state, "/api/symbols/missing/callers").0, 404);
+        assert_eq!(get(&state, "/api/files/symbols?path=src/notes.txt").0, 404);
+        assert_eq!(get(&state, "/api/path?from=missing&to=missing").0, 404);
+        assert_eq!(get(&state, "/api/symbols?kind=widget").0, 400);
+        assert_eq!(get(&state, "/api/symbols?limit=many").0, 400);
+        let (status, _, _) = send(
+            &state,
+            Method::Delete,
+            "/api/stats",
+            &[("Host", "127.0.0.1:7878")],
+        );
+        assert_eq!(status, 405);
+
+        let (status, symbols) = get(&state, "/api/symbols?q=helper");
+        assert_eq!(status, 200);
+        let id = symbols[0]["id"].as_str().unwrap();
+        let (status, callers) = get(&state, &format!("/api/symbols/{id}/callers"));
+        assert_eq!(status, 200);
+        assert_eq!(callers.as_array().map(Vec::len), Some(1));
+    }
+}
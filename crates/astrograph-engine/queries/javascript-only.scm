@@ -0,0 +1,6 @@
+; Definitions in JavaScript files whose nodes the TypeScript grammars name
+; differently.
+
+(field_definition
+  property: [(property_identifier) (private_property_identifier)] @name
+  value: [(arrow_function) (function_expression)]) @definition.method
@@ -0,0 +1,23 @@
+; Definitions and calls extracted from JavaScript files, and from TypeScript
+; files together with `typescript.scm`.
+
+(class_declaration name: (_) @name) @definition.class
+
+(function_declaration name: (_) @name) @definition.function
+(method_definition name: (_) @name) @definition.method
+(variable_declarator
+  name: (_) @name
+  value: [(arrow_function) (function_expression)]) @definition.function
+(pair
+  key: [(property_identifier) (private_property_identifier)] @name
+  value: [(arrow_function) (function_expression)]) @definition.method
+(assignment_expression
+  left: (member_expression
+    object: (_) @exports
+    property: (property_identifier) @name)
+  right: [(arrow_function) (function_expression)]
+  (#match? @exports "^(module\\.)?exports$")) @definition.function
+[(arrow_function) (function_expression) (generator_function)] @definition.closure
+
+(call_expression function: (_) @name) @reference.call
+(new_expression constructor: (_) @name) @reference.call
@@ -0,0 +1,4 @@
+; Components rendered by JSX elements, in JavaScript and TSX files.
+
+(jsx_opening_element name: (_) @name) @reference.render
+(jsx_self_closing_element name: (_) @name) @reference.render
@@ -0,0 +1,21 @@
+; Definitions and calls extracted from Rust files.
+
+(mod_item name: (identifier) @name) @definition.module
+(trait_item name: (type_identifier) @name) @definition.trait
+(impl_item) @definition.impl
+
+(function_item name: (identifier) @name) @definition.function
+(function_signature_item name: (identifier) @name) @definition.method
+(closure_expression) @definition.closure
+(macro_definition name: (identifier) @name) @definition.macro
+
+(struct_item name: (type_identifier) @name) @definition.struct
+(enum_item name: (type_identifier) @name) @definition.enum
+(union_item name: (type_identifier) @name) @definition.union
+(type_item name: (type_identifier) @name) @definition.type
+(associated_type name: (type_identifier) @name) @definition.type
+(const_item name: (identifier) @name) @definition.constant
+(static_item name: (identifier) @name) @definition.static
+
+(call_expression function: (_) @name) @reference.call
+(macro_invocation macro: (_) @name) @reference.macro
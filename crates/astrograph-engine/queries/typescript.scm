@@ -0,0 +1,18 @@
+; TypeScript additions to `javascript.scm`.
+
+(abstract_class_declaration name: (_) @name) @definition.class
+(interface_declaration name: (_) @name) @definition.interface
+(enum_declaration name: (_) @name) @definition.enum
+(type_alias_declaration name: (_) @name) @definition.type
+(internal_module name: (_) @name) @definition.namespace
+(module name: (_) @name) @definition.namespace
+
+(public_field_definition
+  name: [(property_identifier) (private_property_identifier)] @name
+  value: [(arrow_function) (function_expression)]) @definition.method
+
+; Overloads, abstract methods and ambient declarations.
+(function_signature name: (_) @name) @definition.function
+(method_signature name: (_) @name) @definition.method
+(abstract_method_signature name: (_) @name) @definition.method
+(variable_declarator name: (identifier) @name !value) @definition.constant
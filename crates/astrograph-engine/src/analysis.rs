@@ -1,15 +1,30 @@
 use crate::cache::AnalysisCache;
-use crate::language::{detect_language, supported_extensions};
-use crate::model::{AnalysisResult, AnalysisStats, CallEdge, FileInfo, ParsedFile, Symbol};
-use crate::parser::analyze_file;
-use anyhow::{anyhow, Result};
-use ignore::{DirEntry, WalkBuilder};
+use crate::entrypoint::{
+    compile_patterns, CompiledPattern, EntrypointDetector, EntrypointPattern, TAURI_COMMAND,
+};
+use crate::external::{external_symbol, js_call_target, package_name, rust_call_target};
+use crate::imports::{node_package_name, ImportResolver};
+use crate::language::LanguageRegistry;
+use crate::library::{locate_libraries, LibraryConfig, LIBRARY_PATH_PREFIX};
+use crate::model::{
+    AnalysisResult, AnalysisStats, BindingExport, CallEdge, CallKind, Diagnostic, DiagnosticKind,
+    EntrypointHint, EntrypointKind, FileInfo, ImportBinding, Language, Modifier, ParsedFile,
+    Symbol, SymbolKind,
+};
+use crate::modules::{join_module, normalize, qualify_rust_path, ModDecl, ModulePaths};
+use crate::parser::{analyze_file, span_from_range};
+use crate::tags::{compile_queries, QueryFile, TagQuery};
+use anyhow::{anyhow, bail, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::Gitignore;
+use ignore::{DirEntry, Match, WalkBuilder};
 use rayon::prelude::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
@@ -22,13 +37,91 @@ pub struct ProgressEvent {
     pub total: u32,
 }
 
-const SCHEMA_VERSION: &str = "0.1.0";
+const SCHEMA_VERSION: &str = "0.2.0";
 
 #[derive(Debug, Clone)]
 pub struct AnalysisConfig {
     pub root: PathBuf,
     pub follow_symlinks: bool,
+    /// Entrypoints in the compact pattern form accepted by `--entrypoint`;
+    /// see [`EntrypointPattern::parse`].
     pub manual_entrypoints: Vec<String>,
+    pub entrypoint_patterns: Vec<EntrypointPattern>,
+    /// Globs over root-relative paths; when non-empty, only matching files
+    /// are analyzed.
+    pub include: Vec<String>,
+    /// Globs over root-relative paths of files to skip.
+    pub exclude: Vec<String>,
+    /// Languages to analyze; empty means every supported language.
+    pub languages: Vec<Language>,
+    pub resolution: ResolutionConfig,
+    /// Directory names skipped wherever they appear; starts as
+    /// [`DEFAULT_IGNORED_DIRS`] and can be replaced or extended.
+    pub ignored_dirs: Vec<String>,
+    /// Walk hidden (dot) files and directories too. `.github` is always walked.
+    pub include_hidden: bool,
+    /// Analyze JavaScript and TypeScript code blocks in Markdown files.
+    /// Listing [`Language::Markdown`] in `languages` enables it too.
+    pub include_markdown: bool,
+    /// Files larger than this many bytes are skipped with an
+    /// [`Oversized`](DiagnosticKind::Oversized) diagnostic; starts as
+    /// [`DEFAULT_MAX_FILE_SIZE`], `None` analyzes files of any size.
+    pub max_file_size: Option<u64>,
+    /// Record every skipped path with the rule that skipped it in
+    /// [`AnalysisOutput::skipped`].
+    pub report_skipped: bool,
+    /// Dependency sources analyzed alongside the project.
+    pub libraries: LibraryConfig,
+    /// Analyzers of the supported languages; starts with the built-in ones
+    /// and can be extended with
+    /// [`LanguageRegistry::register`].
+    pub analyzers: LanguageRegistry,
+    /// Project query files adding to or replacing the tags queries of
+    /// languages, in order.
+    pub queries: Vec<QueryFile>,
+}
+
+/// Directory names skipped by default.
+pub const DEFAULT_IGNORED_DIRS: &[&str] = &[
+    ".git",
+    "target",
+    "node_modules",
+    "dist",
+    "build",
+    ".turbo",
+    ".idea",
+    ".vscode",
+    ".cargo",
+];
+
+/// Files over 1 MiB, typically generated or bundled code, are skipped by
+/// default.
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// A path left out of the analysis and the rule responsible.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedPath {
+    pub path: String,
+    pub rule: String,
+}
+
+/// How call names are matched to symbols.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResolutionConfig {
+    /// Allow calls to resolve to symbols defined in other files.
+    pub cross_file: bool,
+    /// When a name matches symbols in several files, prefer the caller's file.
+    pub prefer_same_file: bool,
+}
+
+impl Default for ResolutionConfig {
+    fn default() -> Self {
+        Self {
+            cross_file: true,
+            prefer_same_file: false,
+        }
+    }
 }
 
 impl AnalysisConfig {
@@ -37,14 +130,41 @@ impl AnalysisConfig {
             root: root.into(),
             follow_symlinks: false,
             manual_entrypoints: Vec::new(),
+            entrypoint_patterns: Vec::new(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            languages: Vec::new(),
+            resolution: ResolutionConfig::default(),
+            ignored_dirs: DEFAULT_IGNORED_DIRS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            include_hidden: false,
+            include_markdown: false,
+            max_file_size: Some(DEFAULT_MAX_FILE_SIZE),
+            report_skipped: false,
+            libraries: LibraryConfig::default(),
+            analyzers: LanguageRegistry::default(),
+            queries: Vec::new(),
         }
     }
+
+    /// The language a file is analyzed as, by extension. Markdown files are
+    /// not analyzed unless `include_markdown` or `languages` enables them.
+    pub fn detect(&self, path: &Path) -> Option<Language> {
+        let language = self.analyzers.detect(path)?;
+        let markdown = self.include_markdown || self.languages.contains(&Language::Markdown);
+        (language != Language::Markdown || markdown).then_some(language)
+    }
 }
 
 #[derive(Debug)]
 pub struct AnalysisOutput {
     pub result: AnalysisResult,
     pub cache: AnalysisCache,
+    /// Skipped paths, when [`AnalysisConfig::report_skipped`] is set. Paths
+    /// excluded by `.gitignore`/`.astrographignore` are not listed.
+    pub skipped: Vec<SkippedPath>,
 }
 
 pub fn analyze_project<F>(
@@ -56,47 +176,139 @@ where
     F: Fn(ProgressEvent),
 {
     let root = config.root.canonicalize()?;
+    let manual_patterns: Vec<EntrypointPattern> = config
+        .manual_entrypoints
+        .iter()
+        .map(|value| EntrypointPattern::parse(value))
+        .chain(config.entrypoint_patterns.iter().cloned())
+        .collect();
+    let manual_patterns = compile_patterns(&manual_patterns)?;
+    let supported = config.analyzers.languages();
+    if let Some(language) = config
+        .languages
+        .iter()
+        .find(|language| !supported.contains(language))
+    {
+        bail!("Unsupported language `{}`", language.name());
+    }
+    let queries = compile_queries(&config.analyzers, &config.queries)?;
     let root_string = root.to_string_lossy().to_string();
 
-    let mut cache = cache.unwrap_or_else(|| AnalysisCache::new(SCHEMA_VERSION, &root_string));
+    let mut cache = cache
+        .filter(|cache| cache.is_compatible(SCHEMA_VERSION, &root_string))
+        .unwrap_or_else(|| AnalysisCache::new(SCHEMA_VERSION, &root_string));
     let cached_files = cache.files.clone();
 
-    let files = collect_files(&root, config.follow_symlinks, progress.as_ref())?;
-    let total_files = files.len() as u32;
-    let files_set: HashSet<String> = files
+    let (files, skipped) = collect_files(&root, &config, progress.as_ref())?;
+    let libraries = locate_libraries(
+        &root,
+        &config.libraries,
+        &config.languages,
+        &config.analyzers,
+    )?;
+    let project_files: HashSet<&PathBuf> = files.iter().collect();
+    let mut roots = vec![root.clone()];
+    let mut sources: Vec<SourceFile> = files
         .iter()
-        .filter_map(|path| path.strip_prefix(&root).ok())
-        .map(|path| path.to_string_lossy().to_string())
-        .collect();
-
-    let file_outcomes: Vec<FileOutcome> = if let Some(ref progress_fn) = progress {
-        let mut outcomes = Vec::with_capacity(files.len());
-        for (i, path) in files.iter().enumerate() {
-            let relative = path
+        .map(|path| SourceFile {
+            path: path.clone(),
+            relative: path
                 .strip_prefix(&root)
                 .unwrap_or(path)
                 .to_string_lossy()
-                .replace('\\', "/");
+                .replace('\\', "/"),
+            library: false,
+        })
+        .collect();
+    for library in &libraries {
+        roots.push(library.root.clone());
+        sources.extend(
+            library
+                .files
+                .iter()
+                .filter(|path| !project_files.contains(path))
+                .map(|path| SourceFile {
+                    path: path.clone(),
+                    relative: library.relative_path(path),
+                    library: true,
+                }),
+        );
+    }
+    let mut diagnostics = Vec::new();
+    if let Some(max_file_size) = config.max_file_size {
+        sources.retain(|source| {
+            let size = fs::metadata(&source.path).map_or(0, |metadata| metadata.len());
+            if size <= max_file_size {
+                return true;
+            }
+            if !source.library {
+                diagnostics.push(Diagnostic {
+                    file: source.relative.clone(),
+                    kind: DiagnosticKind::Oversized,
+                    message: format!(
+                        "Not analyzed: {size} bytes is over the {max_file_size} byte limit"
+                    ),
+                    span: None,
+                });
+            }
+            false
+        });
+    }
+    let paths: Vec<PathBuf> = sources.iter().map(|source| source.path.clone()).collect();
+    let known_modules: HashMap<String, Vec<ModDecl>> = cached_files
+        .values()
+        .filter(|cached| cached.language == Language::Rust)
+        .map(|cached| (cached.hash.clone(), cached.mod_declarations.clone()))
+        .collect();
+    let module_paths = ModulePaths::discover(&roots, &paths, &known_modules);
+    let total_files = sources.len() as u32;
+    let files_set: HashSet<String> = sources
+        .iter()
+        .map(|source| source.relative.clone())
+        .collect();
+
+    let file_outcomes: Vec<FileOutcome> = if let Some(ref progress_fn) = progress {
+        let mut outcomes = Vec::with_capacity(sources.len());
+        for (i, source) in sources.iter().enumerate() {
             progress_fn(ProgressEvent {
                 phase: "analyzing".to_string(),
-                current_file: relative.clone(),
+                current_file: source.relative.clone(),
                 processed: i as u32,
                 total: total_files,
             });
-            let outcome = analyze_path(path, &root, &cached_files)?;
+            let outcome = analyze_path(
+                source,
+                &cached_files,
+                &module_paths,
+                &config.analyzers,
+                &queries,
+            )?;
             outcomes.push(outcome);
         }
         outcomes
     } else {
-        files
+        sources
             .par_iter()
-            .map(|path| analyze_path(path, &root, &cached_files))
+            .map(|source| {
+                analyze_path(
+                    source,
+                    &cached_files,
+                    &module_paths,
+                    &config.analyzers,
+                    &queries,
+                )
+            })
             .collect::<Result<Vec<_>>>()?
     };
 
     let mut file_infos = Vec::new();
     let mut symbols = Vec::new();
     let mut calls = Vec::new();
+    let mut entrypoint_hints = Vec::new();
+    let mut file_modules = HashMap::new();
+    let mut file_languages = HashMap::new();
+    let mut imports = HashMap::new();
+    let mut binding_exports = Vec::new();
     let mut reused_cache_files = 0;
     let mut reanalyzed_files = 0;
 
@@ -112,22 +324,104 @@ where
             language: outcome.language,
             hash: outcome.hash.clone(),
             byte_size: outcome.byte_size,
+            external: outcome.library,
         });
 
-        symbols.extend(outcome.parsed.symbols.clone());
+        if outcome.library {
+            // Library code is only there to resolve calls into; nothing in it
+            // is an entrypoint of the project.
+            symbols.extend(outcome.parsed.symbols.iter().cloned().map(|mut symbol| {
+                symbol.external = true;
+                symbol.entrypoint_kinds.clear();
+                symbol.entrypoint_rules.clear();
+                symbol
+            }));
+        } else {
+            symbols.extend(outcome.parsed.symbols.clone());
+            entrypoint_hints.extend(outcome.parsed.entrypoint_hints.clone());
+            diagnostics.extend(outcome.parsed.diagnostics.iter().cloned());
+        }
         calls.extend(outcome.parsed.calls.clone());
+        file_modules.insert(outcome.path.clone(), outcome.module_path.clone());
+        file_languages.insert(outcome.path.clone(), outcome.code_language);
+        if !outcome.parsed.imports.is_empty() {
+            imports.insert(outcome.path.clone(), outcome.parsed.imports.clone());
+        }
+        binding_exports.extend(outcome.parsed.binding_exports.iter().cloned());
 
-        cache.upsert(outcome.path, outcome.hash, outcome.language, outcome.parsed);
+        cache.upsert(
+            outcome.path,
+            outcome.hash,
+            outcome.language,
+            outcome.module_path,
+            outcome.query_hash,
+            outcome.parsed,
+        );
     }
 
     cache.files.retain(|path, _| files_set.contains(path));
 
-    resolve_calls(&mut calls, &symbols);
-    apply_manual_entrypoints(&mut symbols, &config.manual_entrypoints);
+    pair_declarations(&mut symbols, &mut calls, &file_languages);
+    apply_manual_entrypoints(&mut symbols, &manual_patterns);
+
+    let absolute_paths: HashMap<&str, &Path> = sources
+        .iter()
+        .map(|source| (source.relative.as_str(), source.path.as_path()))
+        .collect();
+    let lookup = (!imports.is_empty()).then(|| {
+        ExportLookup::new(
+            &roots,
+            &paths,
+            &absolute_paths,
+            &symbols,
+            &imports,
+            &file_modules,
+        )
+    });
+    let cross_file_lookup = lookup.as_ref().filter(|_| config.resolution.cross_file);
+    let hinted = resolve_entrypoint_hints(
+        &symbols,
+        &entrypoint_hints,
+        &imports,
+        &file_languages,
+        cross_file_lookup,
+    );
+    if let Some(lookup) = cross_file_lookup {
+        resolve_imported_calls(&mut calls, &imports, &file_languages, lookup);
+    }
+    if config.resolution.cross_file {
+        link_binding_calls(
+            &mut calls,
+            &binding_exports,
+            &symbols,
+            &imports,
+            &file_languages,
+            &module_paths,
+            lookup.as_ref(),
+        );
+    }
+    resolve_calls(
+        &mut calls,
+        &symbols,
+        &file_modules,
+        &file_languages,
+        &config.resolution,
+    );
+    let externals = link_external_calls(
+        &mut calls,
+        &imports,
+        &file_languages,
+        &module_paths,
+        &absolute_paths,
+        lookup.as_ref().map(|lookup| &lookup.resolver),
+    );
+    symbols.extend(externals);
+    apply_entrypoint_hints(&mut symbols, &hinted);
+    link_ffi_declarations(&symbols, &mut calls, &file_languages);
 
     let mut entrypoints: Vec<String> = symbols
         .iter()
-        .filter(|symbol| symbol.is_entrypoint)
+        .filter(|symbol| symbol.is_entrypoint())
         .map(|symbol| symbol.id.clone())
         .collect();
     entrypoints.sort();
@@ -141,6 +435,18 @@ where
             .then(a.id.cmp(&b.id))
     });
     file_infos.sort_by(|a, b| a.path.cmp(&b.path));
+    diagnostics.sort_by(|a, b| {
+        a.file.cmp(&b.file).then(
+            a.span
+                .as_ref()
+                .map(|span| (span.start_line, span.start_col))
+                .cmp(
+                    &b.span
+                        .as_ref()
+                        .map(|span| (span.start_line, span.start_col)),
+                ),
+        )
+    });
 
     let generated_at = OffsetDateTime::now_utc()
         .format(&Rfc3339)
@@ -151,6 +457,11 @@ where
         symbol_count: symbols.len(),
         call_count: calls.len(),
         entrypoint_count: entrypoints.len(),
+        program_entrypoint_count: symbols
+            .iter()
+            .filter(|symbol| symbol.is_program_entrypoint())
+            .count(),
+        diagnostic_count: diagnostics.len(),
         reused_cache_files,
         reanalyzed_files,
     };
@@ -164,64 +475,144 @@ where
         symbols,
         calls,
         entrypoints,
+        diagnostics,
     };
 
-    Ok(AnalysisOutput { result, cache })
+    Ok(AnalysisOutput {
+        result,
+        cache,
+        skipped,
+    })
+}
+
+/// A file to analyze and the path recorded for it in the output.
+#[derive(Debug)]
+struct SourceFile {
+    path: PathBuf,
+    relative: String,
+    library: bool,
 }
 
 #[derive(Debug)]
 struct FileOutcome {
     path: String,
+    library: bool,
     language: crate::model::Language,
+    /// The language the file's code is parsed as; differs from `language`
+    /// for files embedding code, such as Vue components.
+    code_language: Language,
     hash: String,
     byte_size: usize,
+    module_path: String,
+    query_hash: String,
     parsed: ParsedFile,
     from_cache: bool,
 }
 
 fn analyze_path(
-    path: &Path,
-    root: &Path,
+    source: &SourceFile,
     cache_files: &HashMap<String, crate::cache::CachedFile>,
+    module_paths: &ModulePaths,
+    analyzers: &LanguageRegistry,
+    queries: &HashMap<Language, TagQuery>,
 ) -> Result<FileOutcome> {
-    let language = detect_language(path).ok_or_else(|| anyhow!("Unsupported file"))?;
+    let path = source.path.as_path();
+    let (language, analyzer) = analyzers
+        .detect(path)
+        .and_then(|language| Some((language, analyzers.get(language)?)))
+        .ok_or_else(|| anyhow!("Unsupported file"))?;
     let bytes = fs::read(path)?;
+    // Embedded ranges index the text the parser sees.
+    let text = String::from_utf8_lossy(&bytes);
+    let embedded = analyzer.embedded(text.as_bytes());
+    let (code_language, code_analyzer) = match &embedded {
+        Some(code) => (
+            code.language,
+            analyzers.get(code.language).ok_or_else(|| {
+                anyhow!("Unsupported embedded language `{}`", code.language.name())
+            })?,
+        ),
+        None => (language, analyzer),
+    };
+    let tags = queries.get(&code_language);
+    let query_hash = tags.map(|query| query.hash.clone()).unwrap_or_default();
     let hash = hash_bytes(&bytes);
     let byte_size = bytes.len();
-    let relative_path = path
-        .strip_prefix(root)
-        .unwrap_or(path)
-        .to_string_lossy()
-        .replace('\\', "/");
+    let relative_path = source.relative.clone();
+    let module_path = module_paths.module_path(path);
+    let mod_declarations = module_paths.mod_declarations(path);
 
     if let Some(cached) = cache_files.get(&relative_path) {
-        if cached.hash == hash && cached.language == language {
+        if cached.hash == hash
+            && cached.language == language
+            && cached.module_path == module_path
+            && cached.query_hash == query_hash
+        {
             return Ok(FileOutcome {
                 path: relative_path,
+                library: source.library,
                 language,
+                code_language,
                 hash,
                 byte_size,
+                module_path,
+                query_hash,
                 parsed: ParsedFile {
                     symbols: cached.symbols.clone(),
                     calls: cached.calls.clone(),
+                    entrypoint_hints: cached.entrypoint_hints.clone(),
+                    imports: cached.imports.clone(),
+                    binding_exports: cached.binding_exports.clone(),
+                    diagnostics: cached.diagnostics.clone(),
+                    mod_declarations,
                 },
                 from_cache: true,
             });
         }
     }
 
-    let parsed = analyze_file(path, root, language)?;
+    let mut parsed = analyze_file(
+        &bytes,
+        &relative_path,
+        code_analyzer,
+        tags,
+        &module_path,
+        embedded.as_ref().map(|code| code.ranges.as_slice()),
+    )?;
+    parsed.mod_declarations = mod_declarations;
+    for (lang, range) in embedded.iter().flat_map(|code| &code.unsupported) {
+        parsed.diagnostics.push(Diagnostic {
+            file: relative_path.clone(),
+            kind: DiagnosticKind::Unsupported,
+            message: format!("`{lang}` code is not analyzed"),
+            span: Some(span_from_range(text.as_bytes(), range)),
+        });
+    }
     Ok(FileOutcome {
         path: relative_path,
+        library: source.library,
         language,
+        code_language,
         hash,
         byte_size,
+        module_path,
+        query_hash,
         parsed,
         from_cache: false,
     })
 }
 
-fn resolve_calls(calls: &mut [CallEdge], symbols: &[Symbol]) {
+/// Resolves call names to symbols. Qualified names prefer an exact fully
+/// qualified match (after expanding `crate::`, `self::` and `super::`), then
+/// symbols whose fq name ends with the path, then any symbol with the last
+/// segment as its name.
+fn resolve_calls(
+    calls: &mut [CallEdge],
+    symbols: &[Symbol],
+    file_modules: &HashMap<String, String>,
+    languages: &HashMap<String, Language>,
+    resolution: &ResolutionConfig,
+) {
     let mut by_name: HashMap<String, Vec<&Symbol>> = HashMap::new();
     let mut by_fq: HashMap<String, Vec<&Symbol>> = HashMap::new();
 
@@ -234,19 +625,82 @@ fn resolve_calls(calls: &mut [CallEdge], symbols: &[Symbol]) {
     }
 
     for call in calls {
-        let callee_name = call.callee_name.clone();
-        let mut candidates = Vec::new();
+        // Cross-language calls only resolve to binding exports.
+        if call.callee_id.is_some() || call.kind == CallKind::Ffi {
+            continue;
+        }
+        let callee_name = call.callee_name.as_str();
+        let wants_macro = call.kind == CallKind::Macro;
+        let eligible = |symbol: &&&Symbol| {
+            // Macro invocations only resolve to macros, and calls never do.
+            (symbol.kind == SymbolKind::Macro) == wants_macro
+                && (resolution.cross_file || symbol.file == call.file)
+        };
+        let named = by_name
+            .get(split_last_segment(callee_name))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
 
-        if callee_name.contains("::") || callee_name.contains('.') {
-            if let Some(list) = by_fq.get(&callee_name) {
-                candidates.extend(list.iter().cloned());
-            }
-            let short = split_last_segment(&callee_name);
-            if let Some(list) = by_name.get(short) {
-                candidates.extend(list.iter().cloned());
-            }
-        } else if let Some(list) = by_name.get(&callee_name) {
-            candidates.extend(list.iter().cloned());
+        let tiers: Vec<Vec<&Symbol>> = if callee_name.contains("::") || callee_name.contains('.') {
+            let language = languages.get(call.file.as_str()).copied();
+            let qualified = if matches!(language, Some(Language::Java | Language::Kotlin)) {
+                // `Util.helper` can match `com::example::Util::helper`.
+                callee_name.replace('.', "::")
+            } else {
+                file_modules
+                    .get(&call.file)
+                    .and_then(|module| qualify_rust_path(callee_name, module))
+                    .unwrap_or_else(|| callee_name.to_string())
+            };
+            let suffix = format!("::{qualified}");
+            let exact = by_fq.get(&qualified).map(Vec::as_slice).unwrap_or_default();
+            vec![
+                exact.iter().filter(eligible).copied().collect(),
+                named
+                    .iter()
+                    .filter(|symbol| symbol.fq_name.ends_with(&suffix))
+                    .filter(eligible)
+                    .copied()
+                    .collect(),
+                named.iter().filter(eligible).copied().collect(),
+            ]
+        } else {
+            vec![named.iter().filter(eligible).copied().collect()]
+        };
+        let mut candidates = tiers
+            .into_iter()
+            .find(|tier| !tier.is_empty())
+            .unwrap_or_default();
+
+        // Project calls prefer project symbols and library calls library
+        // symbols, so a common name in a library does not capture calls.
+        let library_caller = call.file.starts_with(LIBRARY_PATH_PREFIX);
+        if candidates
+            .iter()
+            .any(|symbol| symbol.external == library_caller)
+        {
+            candidates.retain(|symbol| symbol.external == library_caller);
+        }
+
+        // A name defined in several languages resolves within the caller's
+        // language family first; foreign functions are linked separately.
+        let family = languages
+            .get(call.file.as_str())
+            .copied()
+            .map(language_family);
+        let same_family = |symbol: &&Symbol| {
+            languages
+                .get(symbol.file.as_str())
+                .copied()
+                .map(language_family)
+                == family
+        };
+        if candidates.iter().any(same_family) {
+            candidates.retain(same_family);
+        }
+
+        if resolution.prefer_same_file && candidates.iter().any(|symbol| symbol.file == call.file) {
+            candidates.retain(|symbol| symbol.file == call.file);
         }
 
         if !candidates.is_empty() {
@@ -256,42 +710,673 @@ fn resolve_calls(calls: &mut [CallEdge], symbols: &[Symbol]) {
     }
 }
 
+/// Points unresolved calls into dependencies at one synthetic `external`
+/// symbol per crate or package, returning those symbols.
+fn link_external_calls(
+    calls: &mut [CallEdge],
+    imports: &HashMap<String, Vec<ImportBinding>>,
+    languages: &HashMap<String, Language>,
+    module_paths: &ModulePaths,
+    absolute_paths: &HashMap<&str, &Path>,
+    resolver: Option<&ImportResolver>,
+) -> Vec<Symbol> {
+    let mut externals: BTreeMap<(&str, String), Symbol> = BTreeMap::new();
+    for call in calls.iter_mut() {
+        if call.callee_id.is_some() || matches!(call.kind, CallKind::Defines | CallKind::Ffi) {
+            continue;
+        }
+        let Some(language) = languages.get(call.file.as_str()).copied() else {
+            continue;
+        };
+        let bindings = imports
+            .get(&call.file)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let Some(path) = absolute_paths.get(call.file.as_str()).copied() else {
+            continue;
+        };
+        let target = match language {
+            Language::Rust => rust_call_target(call, bindings, module_paths.dependencies(path)),
+            Language::JavaScript | Language::TypeScript | Language::Tsx => {
+                js_call_target(call, bindings, |binding| {
+                    resolver
+                        .is_some_and(|resolver| resolver.resolve(path, &binding.source).is_some())
+                })
+            }
+            _ => None,
+        };
+        if let Some(name) = target {
+            let ecosystem = language_family(language);
+            let symbol = externals
+                .entry((ecosystem, name))
+                .or_insert_with_key(|(ecosystem, name)| external_symbol(ecosystem, name));
+            call.callee_id = Some(symbol.id.clone());
+        }
+    }
+    externals.into_values().collect()
+}
+
+/// Links JavaScript and TypeScript calls to the Rust functions they reach
+/// through a binding framework, turning them into `ffi` edges: Tauri
+/// `invoke("command")` edges to the `#[tauri::command]` of that name, and
+/// calls through an import no analyzed file resolves (`greet()` after
+/// `import { greet } from "./pkg"`, `addon.sumAll()` after
+/// `import * as addon from "addon"`) to the `#[wasm_bindgen]` or `#[napi]`
+/// export of that name in a crate the import reaches.
+fn link_binding_calls(
+    calls: &mut [CallEdge],
+    exports: &[BindingExport],
+    symbols: &[Symbol],
+    imports: &HashMap<String, Vec<ImportBinding>>,
+    languages: &HashMap<String, Language>,
+    module_paths: &ModulePaths,
+    lookup: Option<&ExportLookup<'_>>,
+) {
+    if exports.is_empty() {
+        return;
+    }
+    let files: HashMap<&str, &str> = symbols
+        .iter()
+        .map(|symbol| (symbol.id.as_str(), symbol.file.as_str()))
+        .collect();
+    let mut commands: HashMap<&str, Vec<&str>> = HashMap::new();
+    // Exported functions by name, with the Rust file defining them.
+    let mut functions: HashMap<&str, Vec<(&str, &str)>> = HashMap::new();
+    let mut crates: HashMap<&str, BindingCrate> = HashMap::new();
+    for export in exports {
+        let id = export.symbol_id.as_str();
+        if export.rule == TAURI_COMMAND.rule() {
+            commands.entry(export.name.as_str()).or_default().push(id);
+            continue;
+        }
+        let Some(file) = files.get(id).copied() else {
+            continue;
+        };
+        if let Some(path) = lookup.and_then(|lookup| lookup.absolute_paths.get(file)) {
+            crates
+                .entry(file)
+                .or_insert_with(|| BindingCrate::locate(path, module_paths));
+        }
+        functions
+            .entry(export.name.as_str())
+            .or_default()
+            .push((id, file));
+    }
+    let claimed: HashSet<&str> = crates
+        .values()
+        .flat_map(|krate| krate.names.iter().map(String::as_str))
+        .collect();
+
+    for call in calls {
+        if call.callee_id.is_some()
+            || !matches!(
+                languages.get(call.file.as_str()).copied(),
+                Some(Language::JavaScript | Language::TypeScript | Language::Tsx)
+            )
+        {
+            continue;
+        }
+        let targets: Vec<&str> = match call.kind {
+            CallKind::Ffi => commands
+                .get(call.callee_name.as_str())
+                .cloned()
+                .unwrap_or_default(),
+            CallKind::Call => {
+                let Some(lookup) = lookup else {
+                    continue;
+                };
+                let mut segments = call.callee_name.split('.');
+                let local = segments.next().unwrap_or_default();
+                let Some(binding) = imports.get(&call.file).and_then(|bindings| {
+                    bindings
+                        .iter()
+                        .find(|binding| !binding.reexport && binding.local == local)
+                }) else {
+                    continue;
+                };
+                let name = match (segments.next(), segments.next()) {
+                    (Some(member), None) if binding.imported == "*" => member,
+                    (None, _) if !matches!(binding.imported.as_str(), "*" | "default") => {
+                        binding.imported.as_str()
+                    }
+                    _ => continue,
+                };
+                let Some(from) = lookup.absolute_paths.get(call.file.as_str()) else {
+                    continue;
+                };
+                functions
+                    .get(name)
+                    .into_iter()
+                    .flatten()
+                    .filter(|(_, file)| {
+                        crates.get(file).is_some_and(|krate| {
+                            krate.reached_by(&binding.source, from, &claimed, &lookup.resolver)
+                        })
+                    })
+                    .map(|(id, _)| *id)
+                    .collect()
+            }
+            _ => continue,
+        };
+        if let Some(target) = targets.iter().min() {
+            call.callee_id = Some(target.to_string());
+            call.kind = CallKind::Ffi;
+        }
+    }
+}
+
+/// A Rust crate exporting bindings, as JavaScript imports it: by a path into
+/// its directory (such as wasm-pack's `pkg` output), or by package name (the
+/// Cargo package for wasm-pack, the `package.json` next to it for napi).
+struct BindingCrate {
+    dir: PathBuf,
+    /// Package names, with `_` for `-`.
+    names: Vec<String>,
+}
+
+impl BindingCrate {
+    fn locate(path: &Path, module_paths: &ModulePaths) -> Self {
+        let (dir, cargo_name) = match module_paths.package(path) {
+            Some((dir, name)) => (dir.to_path_buf(), Some(name.to_string())),
+            // Without a manifest, the directory holding `src`.
+            None => {
+                let dir = path
+                    .ancestors()
+                    .find(|dir| dir.ends_with("src"))
+                    .and_then(Path::parent)
+                    .or_else(|| path.parent())
+                    .unwrap_or(Path::new(""));
+                (dir.to_path_buf(), None)
+            }
+        };
+        let names = cargo_name
+            .into_iter()
+            .chain(node_package_name(&dir))
+            .map(|name| name.replace('-', "_"))
+            .collect();
+        Self { dir, names }
+    }
+
+    /// Whether importing `specifier` from `from` reaches this crate. A bare
+    /// specifier naming no binding crate reaches it unless the importing
+    /// package declares it as an npm dependency.
+    fn reached_by(
+        &self,
+        specifier: &str,
+        from: &Path,
+        claimed: &HashSet<&str>,
+        resolver: &ImportResolver,
+    ) -> bool {
+        if specifier.starts_with('.') {
+            return from
+                .parent()
+                .is_some_and(|dir| normalize(&dir.join(specifier)).starts_with(&self.dir));
+        }
+        if specifier.starts_with('/') {
+            return false;
+        }
+        let package = package_name(specifier);
+        let key = package.replace('-', "_");
+        if self.names.contains(&key) {
+            return true;
+        }
+        !claimed.contains(key.as_str()) && !resolver.declares_dependency(from, &package)
+    }
+}
+
+/// Languages whose code calls each other directly.
+fn language_family(language: Language) -> &'static str {
+    match language {
+        Language::Rust => "rust",
+        Language::JavaScript
+        | Language::TypeScript
+        | Language::Tsx
+        | Language::Vue
+        | Language::Svelte
+        | Language::Astro
+        | Language::Markdown => "js",
+        Language::Java | Language::Kotlin => "jvm",
+        Language::C | Language::Cpp => "c",
+        Language::Custom(name) => name,
+    }
+}
+
+fn is_c_family(symbol: &Symbol, languages: &HashMap<String, Language>) -> bool {
+    matches!(
+        languages.get(symbol.file.as_str()).copied(),
+        Some(Language::C | Language::Cpp)
+    )
+}
+
+fn is_typescript(symbol: &Symbol, languages: &HashMap<String, Language>) -> bool {
+    matches!(
+        languages.get(symbol.file.as_str()).copied(),
+        Some(Language::TypeScript | Language::Tsx)
+    )
+}
+
+/// Merges C and C++ declarations (header prototypes, in-class method
+/// declarations) and TypeScript overload signatures into their definitions
+/// so each function is one symbol. A declaration pairs with the definition
+/// of the same fq name, preferring one with the same parameter types;
+/// `static` definitions and TypeScript implementations only pair within
+/// their own file. The definition takes the declaration's kind, visibility,
+/// modifiers and any doc or generics it lacks, and calls from the
+/// declaration move to it. Unpaired declarations are kept.
+fn pair_declarations(
+    symbols: &mut Vec<Symbol>,
+    calls: &mut [CallEdge],
+    languages: &HashMap<String, Language>,
+) {
+    let mut definitions: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, symbol) in symbols.iter().enumerate() {
+        if !symbol.declaration
+            && matches!(symbol.kind, SymbolKind::Function | SymbolKind::Method)
+            && (is_c_family(symbol, languages) || is_typescript(symbol, languages))
+        {
+            definitions
+                .entry(symbol.fq_name.clone())
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let parameter_types = |symbol: &Symbol| -> Vec<Option<String>> {
+        symbol
+            .parameters
+            .iter()
+            .map(|param| param.type_name.clone())
+            .collect()
+    };
+    let mut paired: HashMap<String, String> = HashMap::new();
+    for index in 0..symbols.len() {
+        let declaration = &symbols[index];
+        let typescript = is_typescript(declaration, languages);
+        if !declaration.declaration || !(typescript || is_c_family(declaration, languages)) {
+            continue;
+        }
+        let Some(candidates) = definitions.get(&declaration.fq_name) else {
+            continue;
+        };
+        let visible: Vec<usize> = candidates
+            .iter()
+            .copied()
+            .filter(|&candidate| {
+                let definition = &symbols[candidate];
+                definition.external == declaration.external
+                    && (!(typescript || definition.modifiers.contains(&Modifier::Static))
+                        || definition.file == declaration.file)
+            })
+            .collect();
+        let types = parameter_types(declaration);
+        let matching: Vec<usize> = visible
+            .iter()
+            .copied()
+            .filter(|&candidate| parameter_types(&symbols[candidate]) == types)
+            .collect();
+        let target = match (matching.as_slice(), visible.as_slice()) {
+            ([only], _) | ([], [only]) => *only,
+            _ => continue,
+        };
+
+        let declaration = symbols[index].clone();
+        let definition = &mut symbols[target];
+        definition.kind = declaration.kind;
+        if definition.container.is_none() {
+            definition.container = declaration.container;
+        }
+        if declaration.is_exported && !definition.is_exported {
+            definition.is_exported = true;
+            definition.mark_entrypoint(EntrypointKind::PublicApi, "exported");
+        }
+        definition.modifiers.extend(declaration.modifiers);
+        definition.modifiers.sort();
+        definition.modifiers.dedup();
+        if definition.doc.is_none() {
+            definition.doc = declaration.doc;
+        }
+        if definition.generics.is_none() {
+            definition.generics = declaration.generics;
+        }
+        paired.insert(declaration.id, definition.id.clone());
+    }
+
+    if paired.is_empty() {
+        return;
+    }
+    symbols.retain(|symbol| !paired.contains_key(&symbol.id));
+    for call in calls {
+        if let Some(definition) = paired.get(&call.caller_id) {
+            call.caller_id = definition.clone();
+        }
+    }
+}
+
+/// Links foreign function declarations to their definitions in another
+/// language with `ffi` edges: functions in Rust `extern` blocks to non-static
+/// C/C++ functions with C linkage, and such C/C++ declarations left unpaired
+/// to Rust `extern` functions of the same name.
+fn link_ffi_declarations(
+    symbols: &[Symbol],
+    calls: &mut Vec<CallEdge>,
+    languages: &HashMap<String, Language>,
+) {
+    let mut c_definitions: HashMap<&str, Vec<&Symbol>> = HashMap::new();
+    let mut rust_definitions: HashMap<&str, Vec<&Symbol>> = HashMap::new();
+    for symbol in symbols {
+        if symbol.declaration || symbol.kind != SymbolKind::Function {
+            continue;
+        }
+        match languages.get(symbol.file.as_str()).copied() {
+            Some(language @ (Language::C | Language::Cpp))
+                if !symbol.modifiers.contains(&Modifier::Static)
+                    && has_c_linkage(symbol, language) =>
+            {
+                c_definitions.entry(&symbol.name).or_default().push(symbol);
+            }
+            Some(Language::Rust) if symbol.modifiers.contains(&Modifier::Extern) => {
+                rust_definitions
+                    .entry(&symbol.name)
+                    .or_default()
+                    .push(symbol);
+            }
+            _ => {}
+        }
+    }
+
+    for declaration in symbols.iter().filter(|symbol| symbol.declaration) {
+        let definitions = match languages.get(declaration.file.as_str()).copied() {
+            Some(Language::Rust) => &c_definitions,
+            Some(language @ (Language::C | Language::Cpp))
+                if has_c_linkage(declaration, language) =>
+            {
+                &rust_definitions
+            }
+            _ => continue,
+        };
+        let Some(definition) = definitions
+            .get(declaration.name.as_str())
+            .and_then(|candidates| {
+                candidates
+                    .iter()
+                    .min_by(|a, b| a.fq_name.cmp(&b.fq_name).then(a.id.cmp(&b.id)))
+            })
+        else {
+            continue;
+        };
+        calls.push(CallEdge {
+            id: hash_bytes(format!("ffi:{}:{}", declaration.id, definition.id).as_bytes()),
+            caller_id: declaration.id.clone(),
+            callee_name: declaration.name.clone(),
+            callee_id: Some(definition.id.clone()),
+            kind: CallKind::Ffi,
+            file: declaration.file.clone(),
+            span: declaration.span.clone(),
+        });
+    }
+}
+
+/// Whether a C/C++ function has an unmangled name Rust can link against: any
+/// C function, and C++ functions outside namespaces and classes or in an
+/// `extern "C"` block.
+fn has_c_linkage(symbol: &Symbol, language: Language) -> bool {
+    language == Language::C
+        || symbol.container.is_none()
+        || symbol.modifiers.contains(&Modifier::Extern)
+}
+
+/// Re-export chains longer than this are not followed.
+const MAX_REEXPORT_DEPTH: usize = 8;
+
+/// Resolves JavaScript and TypeScript calls through the caller's `import`
+/// bindings: `format()` after `import { format } from "@app/utils"`, or
+/// `api.fetch()` after `import * as api from "./api"`.
+fn resolve_imported_calls(
+    calls: &mut [CallEdge],
+    imports: &HashMap<String, Vec<ImportBinding>>,
+    languages: &HashMap<String, Language>,
+    lookup: &ExportLookup<'_>,
+) {
+    for call in calls {
+        if call.callee_id.is_some()
+            || !matches!(call.kind, CallKind::Call | CallKind::Renders)
+            || !matches!(
+                languages.get(call.file.as_str()).copied(),
+                Some(Language::JavaScript | Language::TypeScript | Language::Tsx)
+            )
+        {
+            continue;
+        }
+        if let Some(symbol) = find_imported(&call.callee_name, &call.file, imports, lookup) {
+            call.callee_id = Some(symbol.id.clone());
+        }
+    }
+}
+
+/// The symbol `name` refers to through one of `file`'s `import` bindings.
+fn find_imported<'a>(
+    name: &str,
+    file: &str,
+    imports: &HashMap<String, Vec<ImportBinding>>,
+    lookup: &ExportLookup<'a>,
+) -> Option<&'a Symbol> {
+    let mut segments = name.split('.');
+    let local = segments.next().unwrap_or_default();
+    let binding = imports
+        .get(file)?
+        .iter()
+        .find(|binding| !binding.reexport && binding.local == local)?;
+    // Default imports are looked up under their local name.
+    let head = match binding.imported.as_str() {
+        "*" => None,
+        "default" => Some(local),
+        imported => Some(imported),
+    };
+    let path: Vec<&str> = head.into_iter().chain(segments).collect();
+    if path.is_empty() {
+        return None;
+    }
+    lookup.find(file, &binding.source, &path, 0)
+}
+
+/// Finds the symbol a module exports under a path, following re-exports.
+struct ExportLookup<'a> {
+    absolute_paths: &'a HashMap<&'a str, &'a Path>,
+    relative_paths: HashMap<&'a Path, &'a str>,
+    resolver: ImportResolver,
+    /// Symbols by file and fq name.
+    symbols: HashMap<&'a str, HashMap<&'a str, &'a Symbol>>,
+    imports: &'a HashMap<String, Vec<ImportBinding>>,
+    file_modules: &'a HashMap<String, String>,
+}
+
+impl<'a> ExportLookup<'a> {
+    fn new(
+        roots: &[PathBuf],
+        files: &[PathBuf],
+        absolute_paths: &'a HashMap<&'a str, &'a Path>,
+        symbols: &'a [Symbol],
+        imports: &'a HashMap<String, Vec<ImportBinding>>,
+        file_modules: &'a HashMap<String, String>,
+    ) -> Self {
+        let mut by_file: HashMap<&str, HashMap<&str, &Symbol>> = HashMap::new();
+        for symbol in symbols {
+            by_file
+                .entry(symbol.file.as_str())
+                .or_default()
+                .entry(symbol.fq_name.as_str())
+                .or_insert(symbol);
+        }
+        Self {
+            absolute_paths,
+            relative_paths: absolute_paths
+                .iter()
+                .map(|(relative, path)| (*path, *relative))
+                .collect(),
+            resolver: ImportResolver::discover(roots, files),
+            symbols: by_file,
+            imports,
+            file_modules,
+        }
+    }
+
+    fn find(&self, from: &str, specifier: &str, path: &[&str], depth: usize) -> Option<&'a Symbol> {
+        if depth > MAX_REEXPORT_DEPTH {
+            return None;
+        }
+        let from = self.absolute_paths.get(from)?;
+        let target = self.resolver.resolve(from, specifier)?;
+        let target = *self.relative_paths.get(target.as_path())?;
+        let module = self.file_modules.get(target)?;
+        let fq_name = join_module(module, &path.join("::"));
+        if let Some(symbol) = self
+            .symbols
+            .get(target)
+            .and_then(|symbols| symbols.get(fq_name.as_str()))
+        {
+            return Some(symbol);
+        }
+
+        let reexports = self
+            .imports
+            .get(target)?
+            .iter()
+            .filter(|binding| binding.reexport);
+        for binding in reexports {
+            let next: Vec<&str> = match (binding.local.as_str(), binding.imported.as_str()) {
+                ("*", "*") => path.to_vec(),
+                (local, "*") if local == path[0] => path[1..].to_vec(),
+                (local, imported) if local == path[0] => std::iter::once(imported)
+                    .chain(path[1..].iter().copied())
+                    .collect(),
+                _ => continue,
+            };
+            if next.is_empty() {
+                continue;
+            }
+            if let Some(symbol) = self.find(target, &binding.source, &next, depth + 1) {
+                return Some(symbol);
+            }
+        }
+        None
+    }
+}
+
 fn split_last_segment(value: &str) -> &str {
-    value
-        .rsplit(|c| c == ':' || c == '.')
-        .next()
-        .unwrap_or(value)
+    value.rsplit([':', '.']).next().unwrap_or(value)
+}
+
+/// Resolves the symbols framework registrations refer to, like calls: the
+/// one an `import` binding names, else symbols of that name in the
+/// registering file, else the only symbol of that name in the registering
+/// file's language family. Returns the hints to apply by symbol id.
+fn resolve_entrypoint_hints<'h>(
+    symbols: &[Symbol],
+    hints: &'h [EntrypointHint],
+    imports: &HashMap<String, Vec<ImportBinding>>,
+    languages: &HashMap<String, Language>,
+    lookup: Option<&ExportLookup<'_>>,
+) -> HashMap<String, Vec<&'h EntrypointHint>> {
+    let mut by_name: HashMap<&str, Vec<&Symbol>> = HashMap::new();
+    for symbol in symbols {
+        if !symbol.external && !matches!(symbol.kind, SymbolKind::Closure | SymbolKind::ModuleInit)
+        {
+            by_name
+                .entry(symbol.name.as_str())
+                .or_default()
+                .push(symbol);
+        }
+    }
+
+    let mut hinted: HashMap<String, Vec<&EntrypointHint>> = HashMap::new();
+    for hint in hints {
+        if let Some(symbol) =
+            lookup.and_then(|lookup| find_imported(&hint.name, &hint.file, imports, lookup))
+        {
+            hinted.entry(symbol.id.clone()).or_default().push(hint);
+            continue;
+        }
+
+        let named = by_name
+            .get(split_last_segment(&hint.name))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let mut targets: Vec<&Symbol> = named
+            .iter()
+            .filter(|symbol| symbol.file == hint.file)
+            .copied()
+            .collect();
+        if targets.is_empty() {
+            let family = languages.get(&hint.file).copied().map(language_family);
+            let mut candidates: Vec<&Symbol> = named
+                .iter()
+                .filter(|symbol| {
+                    languages.get(&symbol.file).copied().map(language_family) == family
+                })
+                .copied()
+                .collect();
+            // An overloaded function counts once.
+            if candidates.iter().any(|symbol| !symbol.declaration) {
+                candidates.retain(|symbol| !symbol.declaration);
+            }
+            if candidates.len() == 1 {
+                targets = candidates;
+            }
+        }
+        for symbol in targets {
+            hinted.entry(symbol.id.clone()).or_default().push(hint);
+        }
+    }
+    hinted
 }
 
-fn apply_manual_entrypoints(symbols: &mut [Symbol], manual_entrypoints: &[String]) {
-    if manual_entrypoints.is_empty() {
+/// Marks the symbols [`resolve_entrypoint_hints`] resolved.
+fn apply_entrypoint_hints(symbols: &mut [Symbol], hinted: &HashMap<String, Vec<&EntrypointHint>>) {
+    for symbol in symbols {
+        for hint in hinted.get(&symbol.id).into_iter().flatten() {
+            symbol.mark_entrypoint(hint.kind, &hint.rule);
+        }
+    }
+}
+
+fn apply_manual_entrypoints(symbols: &mut [Symbol], patterns: &[CompiledPattern]) {
+    if patterns.is_empty() {
         return;
     }
 
-    let manual: HashSet<&str> = manual_entrypoints.iter().map(String::as_str).collect();
     for symbol in symbols {
-        if manual.contains(symbol.name.as_str()) || manual.contains(symbol.fq_name.as_str()) {
-            symbol.is_entrypoint = true;
+        if !symbol.external && patterns.iter().any(|pattern| pattern.matches(symbol)) {
+            symbol.mark_entrypoint(EntrypointKind::Manual, "manual");
         }
     }
 }
 
 fn collect_files<F>(
     root: &Path,
-    follow_symlinks: bool,
+    config: &AnalysisConfig,
     progress: Option<&F>,
-) -> Result<Vec<PathBuf>>
+) -> Result<(Vec<PathBuf>, Vec<SkippedPath>)>
 where
     F: Fn(ProgressEvent),
 {
     let mut files = Vec::new();
-    let supported = supported_extensions();
+    let skipped = Arc::new(Mutex::new(Vec::new()));
+    let supported = config.analyzers.extensions();
+    let rules = FileRules::new(config)?;
 
     let mut builder = WalkBuilder::new(root);
-    builder.follow_links(follow_symlinks);
+    builder.follow_links(config.follow_symlinks);
+    // Hidden paths are handled by `skip_rule` so the rule can be reported.
+    builder.hidden(false);
     builder.git_ignore(true).git_global(true).git_exclude(true);
     builder.add_custom_ignore_filename(".astrographignore");
-    builder.filter_entry(|entry| !is_ignored(entry));
+    let filter = DirFilter {
+        root: root.to_path_buf(),
+        ignored_dirs: config.ignored_dirs.clone(),
+        include_hidden: config.include_hidden,
+        skipped: config.report_skipped.then(|| Arc::clone(&skipped)),
+    };
+    builder.filter_entry(move |entry| filter.allows(entry));
 
     for entry in builder.build() {
         let entry = entry?;
@@ -308,12 +1393,21 @@ where
         if let Some(ext) = ext {
             if supported.contains(&ext.as_str()) {
                 let path_buf = path.to_path_buf();
+                let relative = path_buf
+                    .strip_prefix(root)
+                    .unwrap_or(&path_buf)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                let Some(language) = config.detect(path) else {
+                    continue;
+                };
+                if let Some(rule) = rules.skip_rule(config, &relative, language) {
+                    if config.report_skipped {
+                        record_skipped(&skipped, relative, rule);
+                    }
+                    continue;
+                }
                 if let Some(progress_fn) = progress {
-                    let relative = path_buf
-                        .strip_prefix(root)
-                        .unwrap_or(&path_buf)
-                        .to_string_lossy()
-                        .replace('\\', "/");
                     progress_fn(ProgressEvent {
                         phase: "collecting".to_string(),
                         current_file: relative,
@@ -326,34 +1420,195 @@ where
         }
     }
 
-    Ok(files)
+    let skipped = std::mem::take(&mut *skipped.lock().unwrap_or_else(|err| err.into_inner()));
+    Ok((files, skipped))
 }
 
-fn is_ignored(entry: &DirEntry) -> bool {
-    if let Some(name) = entry.file_name().to_str() {
-        let is_dir = entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false);
-        let ignored = [
-            ".git",
-            "target",
-            "node_modules",
-            "dist",
-            "build",
-            ".turbo",
-            ".idea",
-            ".vscode",
-            ".cargo",
-        ];
-        if is_dir && ignored.contains(&name) {
-            return true;
+/// The `languages`, `include` and `exclude` settings, checked per file.
+struct FileRules {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl FileRules {
+    fn new(config: &AnalysisConfig) -> Result<Self> {
+        Ok(Self {
+            include: (!config.include.is_empty())
+                .then(|| build_globset(&config.include))
+                .transpose()?,
+            exclude: build_globset(&config.exclude)?,
+        })
+    }
+
+    /// Why a root-relative file detected as `language` is skipped, if it is.
+    fn skip_rule(
+        &self,
+        config: &AnalysisConfig,
+        relative: &str,
+        language: Language,
+    ) -> Option<String> {
+        if !config.languages.is_empty() && !config.languages.contains(&language) {
+            return Some("language".to_string());
+        }
+        if self
+            .include
+            .as_ref()
+            .is_some_and(|set| !set.is_match(relative))
+        {
+            return Some("include".to_string());
+        }
+        self.exclude
+            .matches(relative)
+            .first()
+            .map(|index| format!("exclude:{}", config.exclude[*index]))
+    }
+}
+
+/// Decides which changed paths can affect the analysis of a project, by the
+/// same rules the file walk applies: ignored and hidden directories,
+/// `.gitignore`/`.astrographignore`, languages, `include` and `exclude`.
+pub struct WatchFilter {
+    config: AnalysisConfig,
+    root: PathBuf,
+    dirs: DirFilter,
+    rules: FileRules,
+    /// `.gitignore` files only apply inside a Git repository.
+    in_git: bool,
+}
+
+impl WatchFilter {
+    pub fn new(config: &AnalysisConfig) -> Result<Self> {
+        let root = config.root.canonicalize()?;
+        Ok(Self {
+            dirs: DirFilter {
+                root: root.clone(),
+                ignored_dirs: config.ignored_dirs.clone(),
+                include_hidden: config.include_hidden,
+                skipped: None,
+            },
+            rules: FileRules::new(config)?,
+            in_git: root.ancestors().any(|dir| dir.join(".git").exists()),
+            config: config.clone(),
+            root,
+        })
+    }
+
+    /// Whether a change to the file at the absolute `path` would be analyzed.
+    pub fn is_relevant(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let mut names = relative.iter().peekable();
+        while let Some(name) = names.next() {
+            let is_dir = names.peek().is_some();
+            let skipped = name
+                .to_str()
+                .is_none_or(|name| self.dirs.rule(name, is_dir).is_some());
+            if skipped {
+                return false;
+            }
         }
-        if is_dir && name.starts_with('.') && name != ".github" {
+        let Some(language) = self.config.detect(path) else {
+            return false;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        self.rules
+            .skip_rule(&self.config, &relative, language)
+            .is_none()
+            && !self.ignored(path)
+    }
+
+    /// Whether an ignore file between the root and `path` excludes it. The
+    /// closest file decides, and `.astrographignore` beats `.gitignore`.
+    fn ignored(&self, path: &Path) -> bool {
+        let ignore_files: &[&str] = if self.in_git {
+            &[".astrographignore", ".gitignore"]
+        } else {
+            &[".astrographignore"]
+        };
+        for dir in path.ancestors().skip(1) {
+            for name in ignore_files {
+                let file = dir.join(name);
+                if !file.is_file() {
+                    continue;
+                }
+                let (matcher, _) = Gitignore::new(&file);
+                match matcher.matched_path_or_any_parents(path, false) {
+                    Match::None => {}
+                    matched => return matched.is_ignore(),
+                }
+            }
+            if dir == self.root {
+                break;
+            }
+        }
+        false
+    }
+}
+
+fn build_globset(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|err| anyhow!("Invalid glob `{pattern}`: {err}"))?;
+        builder.add(glob);
+    }
+    Ok(builder.build()?)
+}
+
+/// Built-in directory rules, applied while walking so skipped directories
+/// are never descended into.
+struct DirFilter {
+    root: PathBuf,
+    ignored_dirs: Vec<String>,
+    include_hidden: bool,
+    skipped: Option<Arc<Mutex<Vec<SkippedPath>>>>,
+}
+
+impl DirFilter {
+    fn allows(&self, entry: &DirEntry) -> bool {
+        let Some(rule) = self.skip_rule(entry) else {
             return true;
+        };
+        if let Some(skipped) = &self.skipped {
+            let relative = entry
+                .path()
+                .strip_prefix(&self.root)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+            record_skipped(skipped, relative, rule);
         }
+        false
     }
-    false
+
+    fn skip_rule(&self, entry: &DirEntry) -> Option<String> {
+        if entry.depth() == 0 {
+            return None;
+        }
+        let name = entry.file_name().to_str()?;
+        let is_dir = entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false);
+        self.rule(name, is_dir)
+    }
+
+    fn rule(&self, name: &str, is_dir: bool) -> Option<String> {
+        if is_dir && self.ignored_dirs.iter().any(|ignored| ignored == name) {
+            return Some(format!("ignored-dir:{name}"));
+        }
+        if !self.include_hidden && name.starts_with('.') && name != ".github" {
+            return Some("hidden".to_string());
+        }
+        None
+    }
+}
+
+fn record_skipped(skipped: &Mutex<Vec<SkippedPath>>, path: String, rule: String) {
+    skipped
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .push(SkippedPath { path, rule });
 }
 
-fn hash_bytes(bytes: &[u8]) -> String {
+pub(crate) fn hash_bytes(bytes: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(bytes);
     hex::encode(hasher.finalize())
@@ -1,10 +1,22 @@
-use crate::model::{CallEdge, Language, ParsedFile, Symbol};
+use crate::model::{
+    BindingExport, CallEdge, Diagnostic, EntrypointHint, ImportBinding, Language, ParsedFile,
+    Symbol,
+};
+use crate::modules::ModDecl;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Version of the per-file extraction output stored in the cache.
+///
+/// Bump this whenever the parser starts producing different symbols or calls
+/// for the same source, so entries written by older builds are not reused.
+pub const PARSER_VERSION: u32 = 20;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisCache {
     pub schema_version: String,
+    #[serde(default)]
+    pub parser_version: u32,
     pub root: String,
     pub files: HashMap<String, CachedFile>,
 }
@@ -15,25 +27,67 @@ pub struct CachedFile {
     pub language: Language,
     pub symbols: Vec<Symbol>,
     pub calls: Vec<CallEdge>,
+    /// Module path the symbols were qualified with; entries are re-parsed
+    /// when the file's module path changes.
+    #[serde(default)]
+    pub module_path: String,
+    /// `mod` declarations of a Rust file, reused to discover module paths
+    /// while its content hash is unchanged.
+    #[serde(default)]
+    pub mod_declarations: Vec<ModDecl>,
+    #[serde(default)]
+    pub entrypoint_hints: Vec<EntrypointHint>,
+    #[serde(default)]
+    pub imports: Vec<ImportBinding>,
+    #[serde(default)]
+    pub binding_exports: Vec<BindingExport>,
+    /// Hash of the tags query the file was parsed with, if any.
+    #[serde(default)]
+    pub query_hash: String,
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl AnalysisCache {
     pub fn new(schema_version: &str, root: &str) -> Self {
         Self {
             schema_version: schema_version.to_string(),
+            parser_version: PARSER_VERSION,
             root: root.to_string(),
             files: HashMap::new(),
         }
     }
 
-    pub fn upsert(&mut self, path: String, hash: String, language: Language, parsed: ParsedFile) {
+    /// Whether entries in this cache can be reused for the given schema and root.
+    pub fn is_compatible(&self, schema_version: &str, root: &str) -> bool {
+        self.schema_version == schema_version
+            && self.parser_version == PARSER_VERSION
+            && self.root == root
+    }
+
+    pub fn upsert(
+        &mut self,
+        path: String,
+        hash: String,
+        language: Language,
+        module_path: String,
+        query_hash: String,
+        parsed: ParsedFile,
+    ) {
         self.files.insert(
             path,
             CachedFile {
                 hash,
                 language,
+                module_path,
+                mod_declarations: parsed.mod_declarations,
+                query_hash,
                 symbols: parsed.symbols,
                 calls: parsed.calls,
+                entrypoint_hints: parsed.entrypoint_hints,
+                imports: parsed.imports,
+                binding_exports: parsed.binding_exports,
+                diagnostics: parsed.diagnostics,
             },
         );
     }
@@ -0,0 +1,270 @@
+use crate::analysis::{AnalysisConfig, ResolutionConfig};
+use crate::entrypoint::EntrypointPattern;
+use crate::library::LibraryConfig;
+use crate::model::Language;
+use crate::tags::QueryFile;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the project configuration file looked up at the analysis root.
+pub const PROJECT_CONFIG_FILE: &str = "astrograph.toml";
+
+/// Cache file used when the project does not set one.
+pub const DEFAULT_CACHE_FILE: &str = ".astrograph-cache.json";
+
+/// Project-level settings read from `astrograph.toml`.
+///
+/// ```toml
+/// languages = ["rust", "typescript", "tsx"]
+///
+/// [files]
+/// include = ["src/**", "crates/**"]
+/// exclude = ["**/*.test.ts", "**/fixtures/**"]
+/// follow_symlinks = false
+/// include_hidden = false
+/// include_markdown = false
+/// max_file_size = 1048576                     # bytes; 0 for no limit
+/// ignored_dirs = ["target", "node_modules"]   # replaces the defaults
+/// extra_ignored_dirs = ["vendor"]             # adds to them
+///
+/// [entrypoints]
+/// patterns = ["main", "method:src/api/**::handle_*", "re:^on[A-Z]"]
+///
+/// [[entrypoints.rules]]
+/// name = "handle_*"
+/// file = "src/handlers/**"
+/// kind = "function"
+///
+/// [resolution]
+/// cross_file = true
+/// prefer_same_file = true
+///
+/// [libraries]
+/// crates = ["serde", "tokio@1.37.0"]
+/// packages = ["express", "@tanstack/react-query"]
+/// cargo_home = "/opt/cargo"
+///
+/// [output]
+/// path = "analysis.json"
+/// format = "compact"
+///
+/// [cache]
+/// path = ".astrograph-cache.json"
+///
+/// [[queries]]
+/// language = "typescript"
+/// path = "queries/routes.scm"
+/// replace = false   # true replaces the bundled query
+/// ```
+///
+/// Relative paths are resolved against the analysis root.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Languages to analyze; all supported languages when unset.
+    #[serde(default)]
+    pub languages: Vec<Language>,
+    #[serde(default)]
+    pub files: FilesConfig,
+    #[serde(default)]
+    pub entrypoints: EntrypointsConfig,
+    #[serde(default)]
+    pub resolution: Option<ResolutionConfig>,
+    #[serde(default)]
+    pub libraries: LibraryConfig,
+    #[serde(default)]
+    pub output: OutputConfig,
+    /// The `[cache]` section; whether a frontend caches without one is up
+    /// to the frontend.
+    #[serde(default)]
+    pub cache: Option<CacheConfig>,
+    /// Tree-sitter query files adding to or replacing bundled queries.
+    #[serde(default)]
+    pub queries: Vec<QueryFile>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FilesConfig {
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    #[serde(default)]
+    pub include_hidden: bool,
+    /// Analyze code blocks in Markdown files.
+    #[serde(default)]
+    pub include_markdown: bool,
+    /// Skip files larger than this many bytes; 0 analyzes files of any size.
+    pub max_file_size: Option<u64>,
+    /// Replaces the built-in ignored directory names.
+    pub ignored_dirs: Option<Vec<String>>,
+    /// Added to the ignored directory names.
+    #[serde(default)]
+    pub extra_ignored_dirs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EntrypointsConfig {
+    /// Patterns in the compact `--entrypoint` form.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    #[serde(default)]
+    pub rules: Vec<EntrypointPattern>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputConfig {
+    pub path: Option<PathBuf>,
+    #[serde(default)]
+    pub format: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Indented JSON.
+    #[default]
+    Pretty,
+    /// JSON without whitespace, for large projects.
+    Compact,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    pub path: Option<PathBuf>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: None,
+        }
+    }
+}
+
+impl ProjectConfig {
+    /// Loads `astrograph.toml` from `root`, if present.
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let path = root.join(PROJECT_CONFIG_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        Self::from_file(&path).map(Some)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    /// Adds these settings to `config`, keeping anything already set there.
+    pub fn apply(&self, config: &mut AnalysisConfig) {
+        config.follow_symlinks |= self.files.follow_symlinks;
+        config.include_hidden |= self.files.include_hidden;
+        config.include_markdown |= self.files.include_markdown;
+        if let Some(size) = self.files.max_file_size {
+            config.max_file_size = (size > 0).then_some(size);
+        }
+        if let Some(ignored_dirs) = &self.files.ignored_dirs {
+            config.ignored_dirs = ignored_dirs.clone();
+        }
+        config
+            .ignored_dirs
+            .extend(self.files.extra_ignored_dirs.iter().cloned());
+        config
+            .manual_entrypoints
+            .extend(self.entrypoints.patterns.iter().cloned());
+        config
+            .entrypoint_patterns
+            .extend(self.entrypoints.rules.iter().cloned());
+        config.include.extend(self.files.include.iter().cloned());
+        config.exclude.extend(self.files.exclude.iter().cloned());
+        config.languages.extend(self.languages.iter().copied());
+        if let Some(resolution) = &self.resolution {
+            config.resolution = resolution.clone();
+        }
+        config
+            .libraries
+            .crates
+            .extend(self.libraries.crates.iter().cloned());
+        config
+            .libraries
+            .packages
+            .extend(self.libraries.packages.iter().cloned());
+        if let Some(cargo_home) = &self.libraries.cargo_home {
+            config.libraries.cargo_home = Some(config.root.join(cargo_home));
+        }
+        config
+            .queries
+            .extend(self.queries.iter().map(|query| QueryFile {
+                path: config.root.join(&query.path),
+                ..query.clone()
+            }));
+    }
+}
+
+/// What the CLI and the desktop app analyze a project with: its
+/// `astrograph.toml` (or the defaults) applied to an [`AnalysisConfig`].
+///
+/// Frontends apply their own options on top. Relative paths from the file
+/// resolve against the analysis root; paths a frontend takes itself, such as
+/// command-line flags, are the frontend's to resolve.
+#[derive(Debug, Clone)]
+pub struct ProjectSettings {
+    pub config: AnalysisConfig,
+    pub project: ProjectConfig,
+}
+
+impl ProjectSettings {
+    /// Settings for `root`, with its `astrograph.toml` applied, if any.
+    pub fn for_project(root: impl Into<PathBuf>) -> Result<Self> {
+        let mut config = AnalysisConfig::new(root);
+        let project = ProjectConfig::load(&config.root)?.unwrap_or_default();
+        project.apply(&mut config);
+        Ok(Self { config, project })
+    }
+
+    /// `path` from the project file, resolved against the analysis root.
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        self.config.root.join(path)
+    }
+
+    /// The cache file, at `cache.path` or else [`DEFAULT_CACHE_FILE`] in the
+    /// root. Without a `[cache]` section, `by_default` decides whether there
+    /// is one; `None` when the cache is off.
+    pub fn cache_path(&self, by_default: bool) -> Option<PathBuf> {
+        let (enabled, path) = match &self.project.cache {
+            Some(cache) => (cache.enabled, cache.path.as_deref()),
+            None => (by_default, None),
+        };
+        enabled.then(|| self.resolve(path.unwrap_or(Path::new(DEFAULT_CACHE_FILE))))
+    }
+
+    /// The result file set by `output.path`, if any.
+    pub fn output_path(&self) -> Option<PathBuf> {
+        self.project
+            .output
+            .path
+            .as_deref()
+            .map(|path| self.resolve(path))
+    }
+}
+
+impl AnalysisConfig {
+    /// Config for `root` with its `astrograph.toml` applied, if any; see
+    /// [`ProjectSettings::for_project`].
+    pub fn for_project(root: impl Into<PathBuf>) -> Result<Self> {
+        ProjectSettings::for_project(root).map(|settings| settings.config)
+    }
+}
@@ -0,0 +1,258 @@
+use crate::language::{EmbeddedCode, LanguageAnalyzer};
+use crate::model::Language;
+use std::ops::Range;
+use tree_sitter::Language as TsLanguage;
+
+/// Vue, Svelte and Astro components, whose `<script>` blocks (and Astro
+/// frontmatter) are parsed as JavaScript or TypeScript.
+pub(crate) struct ComponentAnalyzer(pub(crate) Language);
+
+impl LanguageAnalyzer for ComponentAnalyzer {
+    fn language(&self) -> Language {
+        self.0
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        match self.0 {
+            Language::Vue => &["vue"],
+            Language::Svelte => &["svelte"],
+            _ => &["astro"],
+        }
+    }
+
+    /// Unused: components are parsed through [`embedded`](Self::embedded).
+    fn grammar(&self) -> TsLanguage {
+        tree_sitter_typescript::language_typescript()
+    }
+
+    fn embedded(&self, source: &[u8]) -> Option<EmbeddedCode> {
+        let mut flavor = ScriptFlavor::default();
+        let mut ranges = Vec::new();
+        let mut template_start = 0;
+        if self.0 == Language::Astro {
+            // Frontmatter and client scripts are TypeScript.
+            flavor.typescript = true;
+            if let Some(frontmatter) = astro_frontmatter(source) {
+                template_start = frontmatter.end;
+                ranges.push(frontmatter);
+            }
+        }
+        let mut unsupported = Vec::new();
+        for (lang, body) in script_blocks(source, template_start) {
+            let lang = lang.unwrap_or_else(|| "js".to_string());
+            if flavor.add(&lang) {
+                ranges.push(body);
+            } else {
+                unsupported.push((lang, body));
+            }
+        }
+        Some(EmbeddedCode {
+            language: flavor.language(),
+            ranges,
+            unsupported,
+        })
+    }
+}
+
+/// JavaScript and TypeScript code blocks in Markdown files.
+pub(crate) struct MarkdownAnalyzer;
+
+impl LanguageAnalyzer for MarkdownAnalyzer {
+    fn language(&self) -> Language {
+        Language::Markdown
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["md", "markdown"]
+    }
+
+    /// Unused: code blocks are parsed through [`embedded`](Self::embedded).
+    fn grammar(&self) -> TsLanguage {
+        tree_sitter_typescript::language_typescript()
+    }
+
+    fn embedded(&self, source: &[u8]) -> Option<EmbeddedCode> {
+        let mut flavor = ScriptFlavor::default();
+        let mut ranges = Vec::new();
+        for (info, body) in fenced_blocks(source) {
+            if flavor.add(&info) {
+                ranges.push(body);
+            }
+        }
+        // Other code blocks are documentation, not unsupported code.
+        Some(EmbeddedCode {
+            language: flavor.language(),
+            ranges,
+            unsupported: Vec::new(),
+        })
+    }
+}
+
+/// The grammar that parses every embedded block of a file: TypeScript when
+/// any block is TypeScript, with JSX when any block uses it.
+#[derive(Debug, Default)]
+struct ScriptFlavor {
+    typescript: bool,
+    jsx: bool,
+}
+
+impl ScriptFlavor {
+    /// Records a block's `lang` attribute or fence info string; false when it
+    /// is not JavaScript or TypeScript.
+    fn add(&mut self, lang: &str) -> bool {
+        match lang.to_ascii_lowercase().as_str() {
+            "js" | "javascript" | "mjs" | "cjs" => {}
+            "jsx" => self.jsx = true,
+            "ts" | "typescript" => self.typescript = true,
+            "tsx" => {
+                self.typescript = true;
+                self.jsx = true;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn language(&self) -> Language {
+        match (self.typescript, self.jsx) {
+            (true, true) => Language::Tsx,
+            (true, false) => Language::TypeScript,
+            // The JavaScript grammar includes JSX.
+            (false, _) => Language::JavaScript,
+        }
+    }
+}
+
+/// `<script>` elements from `offset` on holding code, as their `lang`
+/// attribute and the byte range of their body. Scripts with a
+/// non-JavaScript `type` (JSON-LD, templates) are skipped.
+fn script_blocks(source: &[u8], mut offset: usize) -> Vec<(Option<String>, Range<usize>)> {
+    let mut blocks = Vec::new();
+    while let Some(start) = find_ignore_case(source, b"<script", offset) {
+        let tag_start = start + b"<script".len();
+        offset = tag_start;
+        if !source
+            .get(tag_start)
+            .is_some_and(|byte| byte.is_ascii_whitespace() || *byte == b'>')
+        {
+            continue;
+        }
+        let Some(tag_end) = source[tag_start..].iter().position(|byte| *byte == b'>') else {
+            break;
+        };
+        let attributes = String::from_utf8_lossy(&source[tag_start..tag_start + tag_end]);
+        let body_start = tag_start + tag_end + 1;
+        let Some(body_end) = find_ignore_case(source, b"</script", body_start) else {
+            break;
+        };
+        offset = body_end;
+        let is_code = attribute(&attributes, "type").is_none_or(|kind| {
+            matches!(
+                kind.to_ascii_lowercase().as_str(),
+                "module" | "text/javascript" | "application/javascript" | "text/typescript"
+            )
+        });
+        if is_code && body_start < body_end {
+            blocks.push((attribute(&attributes, "lang"), body_start..body_end));
+        }
+    }
+    blocks
+}
+
+/// The value of `name` in a tag's attribute text, quoted or not.
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let mut rest = attributes;
+    while let Some(index) = rest.find(name) {
+        let before = rest[..index].chars().next_back();
+        let after = rest[index + name.len()..].trim_start();
+        rest = &rest[index + name.len()..];
+        if before.is_some_and(|c| !c.is_whitespace()) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default(),
+        };
+        return Some(value.to_string());
+    }
+    None
+}
+
+/// The code between the `---` fences opening an Astro component.
+fn astro_frontmatter(source: &[u8]) -> Option<Range<usize>> {
+    let mut lines = lines(source);
+    let (first_start, first) = lines.find(|(_, line)| !line.trim_ascii().is_empty())?;
+    if first.trim_ascii() != b"---" {
+        return None;
+    }
+    let start = first_start + first.len() + 1;
+    let (end, _) = lines.find(|(_, line)| line.trim_ascii() == b"---")?;
+    (start <= end).then_some(start..end)
+}
+
+/// Fenced code blocks (```` ``` ```` or `~~~`), as the first word of their
+/// info string and the byte range of their contents.
+fn fenced_blocks(source: &[u8]) -> Vec<(String, Range<usize>)> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(u8, usize, String, usize)> = None;
+    for (start, line) in lines(source) {
+        let indent = line.iter().take_while(|byte| **byte == b' ').count();
+        let trimmed = &line[indent..];
+        let fence = trimmed
+            .first()
+            .copied()
+            .filter(|byte| matches!(byte, b'`' | b'~'));
+        let length = fence.map_or(0, |fence| {
+            trimmed.iter().take_while(|byte| **byte == fence).count()
+        });
+        match (&open, fence) {
+            (None, Some(fence)) if indent < 4 && length >= 3 => {
+                let info = String::from_utf8_lossy(&trimmed[length..]);
+                let info = info
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                open = Some((fence, length, info, start + line.len() + 1));
+            }
+            (Some((open_fence, open_length, _, _)), Some(fence))
+                if fence == *open_fence
+                    && length >= *open_length
+                    && trimmed[length..].trim_ascii().is_empty() =>
+            {
+                if let Some((_, _, info, body_start)) = open.take() {
+                    if body_start < start {
+                        blocks.push((info, body_start..start));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// Lines without their `\n`, with the offset each starts at.
+fn lines(source: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    let mut offset = 0;
+    source.split(|byte| *byte == b'\n').map(move |line| {
+        let start = offset;
+        offset += line.len() + 1;
+        (start, line)
+    })
+}
+
+fn find_ignore_case(source: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    source
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle))
+        .map(|index| from + index)
+}
@@ -0,0 +1,629 @@
+use crate::model::{EntrypointKind, Language, Symbol, SymbolKind};
+use crate::parser::{js_call_function, node_text, rust_attribute_paths, rust_attributes};
+use anyhow::{Context, Result};
+use globset::{Glob, GlobBuilder, GlobMatcher};
+use regex::Regex;
+use serde::Deserialize;
+use tree_sitter::Node;
+
+/// A user-declared entrypoint convention, e.g. "every `handle_*` function in
+/// `src/handlers/**`".
+///
+/// `name` is matched against both the symbol name and its `fq_name`: as a
+/// glob when it contains `*` or `?`, as a regular expression with a `re:`
+/// prefix, and literally otherwise, so names like `{init}` need no escaping.
+/// `file` is a glob over the root-relative file path.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EntrypointPattern {
+    pub name: String,
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub kind: Option<SymbolKind>,
+}
+
+impl EntrypointPattern {
+    /// Parses the compact `[kind:][file-glob::]name` form used by
+    /// `--entrypoint`, e.g. `method:src/handlers/**::handle_*` or
+    /// `re:^on[A-Z]`. The file part is only split off when it contains a `/`,
+    /// so plain fully-qualified names like `app::main` keep working.
+    pub fn parse(value: &str) -> Self {
+        let (kind, rest) = match value.split_once(':') {
+            Some((prefix, rest)) if !rest.starts_with(':') => match parse_symbol_kind(prefix) {
+                Some(kind) => (Some(kind), rest),
+                None => (None, value),
+            },
+            _ => (None, value),
+        };
+
+        if rest.starts_with("re:") {
+            return Self {
+                name: rest.to_string(),
+                file: None,
+                kind,
+            };
+        }
+
+        match rest.rsplit_once("::") {
+            Some((file, name)) if file.contains('/') => Self {
+                name: name.to_string(),
+                file: Some(file.to_string()),
+                kind,
+            },
+            _ => Self {
+                name: rest.to_string(),
+                file: None,
+                kind,
+            },
+        }
+    }
+
+    pub(crate) fn compile(&self) -> Result<CompiledPattern> {
+        let name = match self.name.strip_prefix("re:") {
+            Some(pattern) => NameMatcher::Regex(
+                Regex::new(&format!("^(?:{pattern})$"))
+                    .with_context(|| format!("Invalid entrypoint regex `{pattern}`"))?,
+            ),
+            None if !self.name.contains(['*', '?']) => NameMatcher::Exact(self.name.clone()),
+            None => NameMatcher::Glob(
+                Glob::new(&self.name)
+                    .with_context(|| format!("Invalid entrypoint pattern `{}`", self.name))?
+                    .compile_matcher(),
+            ),
+        };
+        let file = self
+            .file
+            .as_deref()
+            .map(|file| {
+                GlobBuilder::new(file)
+                    .literal_separator(true)
+                    .build()
+                    .map(|glob| glob.compile_matcher())
+                    .with_context(|| format!("Invalid entrypoint file glob `{file}`"))
+            })
+            .transpose()?;
+        Ok(CompiledPattern {
+            name,
+            file,
+            kind: self.kind.clone(),
+        })
+    }
+}
+
+fn parse_symbol_kind(value: &str) -> Option<SymbolKind> {
+    serde_json::from_value(serde_json::Value::String(value.to_string())).ok()
+}
+
+pub(crate) struct CompiledPattern {
+    name: NameMatcher,
+    file: Option<GlobMatcher>,
+    kind: Option<SymbolKind>,
+}
+
+enum NameMatcher {
+    Exact(String),
+    Glob(GlobMatcher),
+    Regex(Regex),
+}
+
+impl CompiledPattern {
+    pub(crate) fn matches(&self, symbol: &Symbol) -> bool {
+        if self.kind.as_ref().is_some_and(|kind| *kind != symbol.kind) {
+            return false;
+        }
+        if self
+            .file
+            .as_ref()
+            .is_some_and(|file| !file.is_match(&symbol.file))
+        {
+            return false;
+        }
+        match &self.name {
+            NameMatcher::Exact(name) => symbol.name == *name || symbol.fq_name == *name,
+            NameMatcher::Glob(glob) => {
+                glob.is_match(&symbol.name) || glob.is_match(&symbol.fq_name)
+            }
+            NameMatcher::Regex(regex) => {
+                regex.is_match(&symbol.name) || regex.is_match(&symbol.fq_name)
+            }
+        }
+    }
+}
+
+pub(crate) fn compile_patterns(patterns: &[EntrypointPattern]) -> Result<Vec<CompiledPattern>> {
+    patterns.iter().map(EntrypointPattern::compile).collect()
+}
+
+/// A best-effort rule recognising framework-specific entrypoints.
+///
+/// Detectors either match a definition directly (`#[test] fn ...`) or pick
+/// the handler arguments out of a registration call (`app.get("/", handler)`).
+/// Handlers written inline are marked when their closure symbol is created;
+/// handlers passed by name are resolved against all symbols after parsing.
+pub trait EntrypointDetector: Sync {
+    /// Rule name recorded in `Symbol::entrypoint_rules`.
+    fn rule(&self) -> &'static str;
+
+    fn kind(&self) -> EntrypointKind;
+
+    fn languages(&self) -> &'static [Language];
+
+    /// Whether the definition at `node` is itself an entrypoint.
+    fn matches_definition(&self, _node: Node, _source: &[u8]) -> bool {
+        false
+    }
+
+    /// Handler nodes registered by the call at `node`.
+    fn handlers<'tree>(&self, _node: Node<'tree>, _source: &[u8]) -> Vec<Node<'tree>> {
+        Vec::new()
+    }
+}
+
+const RUST: &[Language] = &[Language::Rust];
+const JS: &[Language] = &[Language::JavaScript, Language::TypeScript, Language::Tsx];
+
+/// All built-in detectors, in the order their rules are recorded.
+pub static DETECTORS: &[&dyn EntrypointDetector] = &[
+    &RustTest,
+    &AsyncMain,
+    &AttributeHandler,
+    &FfiExport,
+    &TAURI_COMMAND,
+    &WASM_BINDGEN,
+    &NAPI,
+    &ExpressRoute,
+    &ReactRoot,
+    &JsTest,
+];
+
+pub fn detectors_for(language: Language) -> impl Iterator<Item = &'static dyn EntrypointDetector> {
+    DETECTORS
+        .iter()
+        .copied()
+        .filter(move |detector| detector.languages().contains(&language))
+}
+
+/// `#[test]`, `#[tokio::test]`, `#[rstest]` and similar test attributes.
+struct RustTest;
+
+impl EntrypointDetector for RustTest {
+    fn rule(&self) -> &'static str {
+        "rust-test"
+    }
+
+    fn kind(&self) -> EntrypointKind {
+        EntrypointKind::Test
+    }
+
+    fn languages(&self) -> &'static [Language] {
+        RUST
+    }
+
+    fn matches_definition(&self, node: Node, source: &[u8]) -> bool {
+        node.kind() == "function_item"
+            && rust_attribute_paths(node, source)
+                .iter()
+                .any(|path| matches!(last_segment(path), "test" | "rstest" | "bench"))
+    }
+}
+
+/// `#[tokio::main]`, `#[async_std::main]`, `#[actix_web::main]`.
+struct AsyncMain;
+
+impl EntrypointDetector for AsyncMain {
+    fn rule(&self) -> &'static str {
+        "async-main"
+    }
+
+    fn kind(&self) -> EntrypointKind {
+        EntrypointKind::Main
+    }
+
+    fn languages(&self) -> &'static [Language] {
+        RUST
+    }
+
+    fn matches_definition(&self, node: Node, source: &[u8]) -> bool {
+        node.kind() == "function_item"
+            && rust_attribute_paths(node, source)
+                .iter()
+                .any(|path| path.contains("::") && last_segment(path) == "main")
+    }
+}
+
+/// Route attributes such as `#[actix_web::get("/")]` or Rocket's `#[post("/")]`.
+struct AttributeHandler;
+
+const HTTP_METHODS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "route",
+];
+
+impl EntrypointDetector for AttributeHandler {
+    fn rule(&self) -> &'static str {
+        "http-handler"
+    }
+
+    fn kind(&self) -> EntrypointKind {
+        EntrypointKind::FrameworkHandler
+    }
+
+    fn languages(&self) -> &'static [Language] {
+        RUST
+    }
+
+    fn matches_definition(&self, node: Node, source: &[u8]) -> bool {
+        node.kind() == "function_item"
+            && rust_attribute_paths(node, source)
+                .iter()
+                .any(|path| HTTP_METHODS.contains(&last_segment(path)))
+    }
+}
+
+/// `#[no_mangle] extern "C" fn` exported to foreign callers.
+struct FfiExport;
+
+impl EntrypointDetector for FfiExport {
+    fn rule(&self) -> &'static str {
+        "ffi-export"
+    }
+
+    fn kind(&self) -> EntrypointKind {
+        EntrypointKind::FfiExport
+    }
+
+    fn languages(&self) -> &'static [Language] {
+        RUST
+    }
+
+    fn matches_definition(&self, node: Node, source: &[u8]) -> bool {
+        if node.kind() != "function_item" {
+            return false;
+        }
+        let is_extern = node
+            .children(&mut node.walk())
+            .filter(|child| child.kind() == "function_modifiers")
+            .any(|modifiers| {
+                modifiers
+                    .children(&mut modifiers.walk())
+                    .any(|modifier| modifier.kind() == "extern_modifier")
+            });
+        is_extern
+            && rust_attribute_paths(node, source)
+                .iter()
+                .any(|path| path == "no_mangle" || path == "export_name")
+    }
+}
+
+/// Rust functions exposed to JavaScript by a binding framework. Besides
+/// marking them, the parser records the name JavaScript calls them by so
+/// JS/TS calls can be linked across the language boundary.
+pub(crate) struct RustBinding {
+    rule: &'static str,
+    kind: EntrypointKind,
+    /// Attribute paths that mark a function, or an `impl` block whose public
+    /// methods are all exported.
+    attributes: &'static [&'static str],
+    /// Whether the attribute may sit on an `impl` block.
+    on_impl: bool,
+    /// Whether JavaScript sees snake_case names in camelCase.
+    camel_case: bool,
+}
+
+/// `#[tauri::command]` functions, called with `invoke("name")`.
+pub(crate) static TAURI_COMMAND: RustBinding = RustBinding {
+    rule: "tauri-command",
+    kind: EntrypointKind::FrameworkHandler,
+    attributes: &["tauri::command", "command"],
+    on_impl: false,
+    camel_case: false,
+};
+
+/// `#[wasm_bindgen]` exports, imported from the generated package.
+pub(crate) static WASM_BINDGEN: RustBinding = RustBinding {
+    rule: "wasm-bindgen",
+    kind: EntrypointKind::FfiExport,
+    attributes: &["wasm_bindgen", "wasm_bindgen::prelude::wasm_bindgen"],
+    on_impl: true,
+    camel_case: false,
+};
+
+/// `#[napi]` exports of a Node-API addon.
+pub(crate) static NAPI: RustBinding = RustBinding {
+    rule: "napi",
+    kind: EntrypointKind::FfiExport,
+    attributes: &["napi", "napi_derive::napi"],
+    on_impl: true,
+    camel_case: true,
+};
+
+pub(crate) static BINDINGS: &[&RustBinding] = &[&TAURI_COMMAND, &WASM_BINDGEN, &NAPI];
+
+impl RustBinding {
+    /// Name JavaScript uses for the function: a `js_name = "..."` argument,
+    /// else its Rust name (camelCased for napi).
+    pub(crate) fn exported_name(&self, node: Node, source: &[u8], name: &str) -> String {
+        let js_name = rust_attributes(node, source)
+            .into_iter()
+            .filter(|(path, _)| self.attributes.contains(&path.as_str()))
+            .find_map(|(_, arguments)| attribute_argument(&arguments, "js_name"));
+        match js_name {
+            Some(js_name) => js_name,
+            None if self.camel_case => camel_case(name),
+            None => name.to_string(),
+        }
+    }
+
+    fn has_attribute(&self, node: Node, source: &[u8]) -> bool {
+        rust_attribute_paths(node, source)
+            .iter()
+            .any(|path| self.attributes.contains(&path.as_str()))
+    }
+}
+
+impl EntrypointDetector for RustBinding {
+    fn rule(&self) -> &'static str {
+        self.rule
+    }
+
+    fn kind(&self) -> EntrypointKind {
+        self.kind
+    }
+
+    fn languages(&self) -> &'static [Language] {
+        RUST
+    }
+
+    fn matches_definition(&self, node: Node, source: &[u8]) -> bool {
+        if node.kind() != "function_item" {
+            return false;
+        }
+        if self.has_attribute(node, source) {
+            return true;
+        }
+        // `#[wasm_bindgen] impl Counter { pub fn increment(&mut self) {} }`
+        let is_pub = node
+            .children(&mut node.walk())
+            .any(|child| child.kind() == "visibility_modifier");
+        let owner = node
+            .parent()
+            .and_then(|list| list.parent())
+            .filter(|owner| owner.kind() == "impl_item");
+        self.on_impl && is_pub && owner.is_some_and(|owner| self.has_attribute(owner, source))
+    }
+}
+
+/// Value of `key = value` in attribute arguments such as
+/// `js_name = "fooBar"` or `js_name = fooBar`.
+fn attribute_argument(arguments: &str, key: &str) -> Option<String> {
+    arguments.split(',').find_map(|argument| {
+        let (name, value) = argument.split_once('=')?;
+        (name.trim() == key).then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+    for ch in name.chars() {
+        if ch == '_' && !result.is_empty() {
+            upper = true;
+        } else if upper {
+            result.extend(ch.to_uppercase());
+            upper = false;
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+/// The command name of a Tauri `invoke("name", args)` call, including
+/// `invoke<T>(...)` and `window.__TAURI__.core.invoke(...)`.
+pub(crate) fn tauri_invoke_command(node: Node, source: &[u8]) -> Option<String> {
+    if node.kind() != "call_expression" {
+        return None;
+    }
+    let function = js_call_function(node)?;
+    if last_segment(node_text(function, source)) != "invoke" {
+        return None;
+    }
+    let command = *call_arguments(node).first()?;
+    if command.kind() != "string" {
+        return None;
+    }
+    let name = node_text(command, source).trim_matches(['"', '\'']);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Express/Koa-style `app.get("/path", handler)` route registrations.
+struct ExpressRoute;
+
+const ROUTE_METHODS: &[&str] = &[
+    "get", "post", "put", "delete", "del", "patch", "head", "options", "all", "use",
+];
+
+impl EntrypointDetector for ExpressRoute {
+    fn rule(&self) -> &'static str {
+        "express-route"
+    }
+
+    fn kind(&self) -> EntrypointKind {
+        EntrypointKind::FrameworkHandler
+    }
+
+    fn languages(&self) -> &'static [Language] {
+        JS
+    }
+
+    fn handlers<'tree>(&self, node: Node<'tree>, source: &[u8]) -> Vec<Node<'tree>> {
+        let Some(method) = member_call_property(node, source) else {
+            return Vec::new();
+        };
+        if !ROUTE_METHODS.contains(&method) {
+            return Vec::new();
+        }
+        let arguments = call_arguments(node);
+        let has_path = arguments.first().is_some_and(|first| {
+            matches!(first.kind(), "string" | "template_string")
+                && node_text(*first, source)
+                    .trim_start_matches(['"', '\'', '`'])
+                    .starts_with('/')
+        });
+
+        if has_path {
+            arguments
+                .into_iter()
+                .skip(1)
+                .filter(|argument| is_function_node(*argument) || is_reference(*argument))
+                .collect()
+        } else if method == "use" {
+            // `app.use(async (ctx) => ...)`; named middleware is too ambiguous.
+            arguments
+                .into_iter()
+                .filter(|argument| is_function_node(*argument))
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Components rendered from `createRoot(el).render(<App />)` or the legacy
+/// `ReactDOM.render(<App />, el)`.
+struct ReactRoot;
+
+impl EntrypointDetector for ReactRoot {
+    fn rule(&self) -> &'static str {
+        "react-root"
+    }
+
+    fn kind(&self) -> EntrypointKind {
+        EntrypointKind::FrameworkHandler
+    }
+
+    fn languages(&self) -> &'static [Language] {
+        JS
+    }
+
+    fn handlers<'tree>(&self, node: Node<'tree>, source: &[u8]) -> Vec<Node<'tree>> {
+        if member_call_property(node, source) != Some("render") {
+            return Vec::new();
+        }
+        let Some(object) = node
+            .child_by_field_name("function")
+            .and_then(|function| function.child_by_field_name("object"))
+        else {
+            return Vec::new();
+        };
+        let is_root = match object.kind() {
+            "call_expression" => object
+                .child_by_field_name("function")
+                .map(|function| last_segment(node_text(function, source)))
+                .is_some_and(|name| name == "createRoot" || name == "hydrateRoot"),
+            _ => last_segment(node_text(object, source)) == "ReactDOM",
+        };
+        if !is_root {
+            return Vec::new();
+        }
+
+        let mut components = Vec::new();
+        if let Some(element) = call_arguments(node).first() {
+            collect_components(*element, source, &mut components);
+        }
+        components
+    }
+}
+
+/// Jest/Vitest `describe`, `it` and `test` blocks.
+struct JsTest;
+
+impl EntrypointDetector for JsTest {
+    fn rule(&self) -> &'static str {
+        "js-test"
+    }
+
+    fn kind(&self) -> EntrypointKind {
+        EntrypointKind::Test
+    }
+
+    fn languages(&self) -> &'static [Language] {
+        JS
+    }
+
+    fn handlers<'tree>(&self, node: Node<'tree>, source: &[u8]) -> Vec<Node<'tree>> {
+        let Some(function) = node.child_by_field_name("function") else {
+            return Vec::new();
+        };
+        // `describe(...)`, `it.only(...)`, `test.skip(...)`.
+        let base = node_text(function, source)
+            .split('.')
+            .next()
+            .unwrap_or_default();
+        if !matches!(base, "describe" | "it" | "test") {
+            return Vec::new();
+        }
+        call_arguments(node)
+            .into_iter()
+            .filter(|argument| is_function_node(*argument))
+            .collect()
+    }
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit(['.', ':']).next().unwrap_or(path)
+}
+
+/// Property name of a `receiver.property(...)` call.
+fn member_call_property<'a>(node: Node, source: &'a [u8]) -> Option<&'a str> {
+    if node.kind() != "call_expression" {
+        return None;
+    }
+    let function = node.child_by_field_name("function")?;
+    if function.kind() != "member_expression" {
+        return None;
+    }
+    Some(node_text(function.child_by_field_name("property")?, source))
+}
+
+fn call_arguments(node: Node) -> Vec<Node> {
+    let Some(arguments) = node.child_by_field_name("arguments") else {
+        return Vec::new();
+    };
+    let mut cursor = arguments.walk();
+    arguments.named_children(&mut cursor).collect()
+}
+
+pub(crate) fn is_function_node(node: Node) -> bool {
+    node.is_named()
+        && matches!(
+            node.kind(),
+            "arrow_function" | "function" | "function_expression" | "generator_function"
+        )
+}
+
+fn is_reference(node: Node) -> bool {
+    matches!(node.kind(), "identifier" | "member_expression")
+}
+
+/// Capitalised JSX element names, i.e. components rather than DOM tags.
+fn collect_components<'tree>(node: Node<'tree>, source: &[u8], out: &mut Vec<Node<'tree>>) {
+    if matches!(
+        node.kind(),
+        "jsx_opening_element" | "jsx_self_closing_element"
+    ) {
+        if let Some(name) = node.child_by_field_name("name") {
+            let is_component = node_text(name, source)
+                .chars()
+                .next()
+                .is_some_and(char::is_uppercase);
+            if is_component {
+                out.push(name);
+            }
+        }
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        collect_components(child, source, out);
+    }
+}
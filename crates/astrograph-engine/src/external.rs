@@ -0,0 +1,213 @@
+use crate::model::{CallEdge, CallKind, ImportBinding, Span, Symbol, SymbolKind};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// Name of the group for JavaScript runtime globals such as `console` or
+/// `JSON`.
+pub const JS_BUILTINS: &str = "builtins";
+
+/// Standard library crates, always treated as dependencies.
+const RUST_STD_CRATES: &[&str] = &["std", "core", "alloc"];
+
+/// Prelude items usable without a `use`.
+const RUST_PRELUDE: &[&str] = &[
+    "Box",
+    "Vec",
+    "String",
+    "Option",
+    "Some",
+    "None",
+    "Result",
+    "Ok",
+    "Err",
+    "drop",
+    "Default",
+    "ToString",
+    "ToOwned",
+    "From",
+    "Into",
+    "Iterator",
+    "IntoIterator",
+    "Clone",
+];
+
+/// Standard library macros usable without a `use`.
+const RUST_STD_MACROS: &[&str] = &[
+    "println",
+    "print",
+    "eprintln",
+    "eprint",
+    "format",
+    "format_args",
+    "write",
+    "writeln",
+    "vec",
+    "panic",
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "unreachable",
+    "unimplemented",
+    "todo",
+    "matches",
+    "dbg",
+    "concat",
+    "stringify",
+    "include_str",
+    "include_bytes",
+    "env",
+    "option_env",
+    "cfg",
+    "file",
+    "line",
+    "column",
+    "module_path",
+    "thread_local",
+];
+
+const JS_GLOBALS: &[&str] = &[
+    "console",
+    "JSON",
+    "Math",
+    "Object",
+    "Array",
+    "Promise",
+    "Number",
+    "String",
+    "Boolean",
+    "BigInt",
+    "Symbol",
+    "Date",
+    "RegExp",
+    "Error",
+    "TypeError",
+    "Map",
+    "Set",
+    "WeakMap",
+    "WeakSet",
+    "Reflect",
+    "Proxy",
+    "Intl",
+    "URL",
+    "URLSearchParams",
+    "TextEncoder",
+    "TextDecoder",
+    "AbortController",
+    "fetch",
+    "setTimeout",
+    "setInterval",
+    "clearTimeout",
+    "clearInterval",
+    "queueMicrotask",
+    "structuredClone",
+    "parseInt",
+    "parseFloat",
+    "isNaN",
+    "encodeURIComponent",
+    "decodeURIComponent",
+    "globalThis",
+    "window",
+    "document",
+    "process",
+    "Buffer",
+];
+
+/// Dependency an unresolved Rust call goes to: the crate at the root of its
+/// path after expanding `use` bindings, when that is a standard library
+/// crate or one of the package's `dependencies`.
+pub(crate) fn rust_call_target(
+    call: &CallEdge,
+    bindings: &[ImportBinding],
+    dependencies: Option<&HashSet<String>>,
+) -> Option<String> {
+    let path = call.callee_name.trim_start_matches("::");
+    let (first, rest) = path.split_once("::").unwrap_or((path, ""));
+    let binding = bindings
+        .iter()
+        .find(|binding| binding.imported != "*" && binding.local == first);
+
+    let root = match binding {
+        Some(binding) if binding.source.is_empty() => binding.imported.as_str(),
+        Some(binding) => binding.source.split("::").next().unwrap_or_default(),
+        None if call.kind == CallKind::Macro && rest.is_empty() => {
+            return RUST_STD_MACROS.contains(&first).then(|| "std".to_string());
+        }
+        None if RUST_PRELUDE.contains(&first) => "std",
+        None if rest.is_empty() => return None,
+        None => first,
+    };
+    let known = RUST_STD_CRATES.contains(&root)
+        || dependencies.is_some_and(|dependencies| dependencies.contains(root));
+    known.then(|| root.to_string())
+}
+
+/// Dependency an unresolved JavaScript or TypeScript call goes to: the npm
+/// package (or Node builtin module) of its import binding, or
+/// [`JS_BUILTINS`] for runtime globals. `is_local` tells whether an import
+/// resolves to an analyzed file.
+pub(crate) fn js_call_target(
+    call: &CallEdge,
+    bindings: &[ImportBinding],
+    is_local: impl Fn(&ImportBinding) -> bool,
+) -> Option<String> {
+    let first = call.callee_name.split('.').next().unwrap_or_default();
+    let binding = bindings
+        .iter()
+        .find(|binding| !binding.reexport && binding.local == first);
+    match binding {
+        Some(binding) => {
+            let bare = !binding.source.starts_with('.') && !binding.source.starts_with('/');
+            (bare && !is_local(binding)).then(|| package_name(&binding.source))
+        }
+        None => JS_GLOBALS.contains(&first).then(|| JS_BUILTINS.to_string()),
+    }
+}
+
+/// Package part of a bare specifier: `lodash/fp` -> `lodash`,
+/// `@scope/pkg/sub` -> `@scope/pkg`, `node:fs` -> `fs`.
+pub(crate) fn package_name(specifier: &str) -> String {
+    let specifier = specifier.strip_prefix("node:").unwrap_or(specifier);
+    let segments = if specifier.starts_with('@') { 2 } else { 1 };
+    specifier
+        .splitn(segments + 1, '/')
+        .take(segments)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Synthetic symbol standing for an external dependency of an ecosystem
+/// (`rust` for crates, `js` for npm packages), which keeps a crate and a
+/// package of the same name apart.
+pub(crate) fn external_symbol(ecosystem: &str, name: &str) -> Symbol {
+    let fq_name = format!("{{external}}::{ecosystem}::{name}");
+    let mut hasher = Sha256::new();
+    hasher.update(fq_name.as_bytes());
+    Symbol {
+        id: hex::encode(hasher.finalize()),
+        name: name.to_string(),
+        kind: SymbolKind::External,
+        file: String::new(),
+        span: Span {
+            start_line: 0,
+            start_col: 0,
+            end_line: 0,
+            end_col: 0,
+        },
+        fq_name,
+        container: None,
+        is_exported: false,
+        entrypoint_kinds: Vec::new(),
+        entrypoint_rules: Vec::new(),
+        signature: None,
+        parameters: Vec::new(),
+        return_type: None,
+        generics: None,
+        modifiers: Vec::new(),
+        doc: None,
+        external: true,
+        declaration: false,
+    }
+}
@@ -0,0 +1,497 @@
+use crate::language::detect_language;
+use crate::model::Language;
+use crate::modules::{containing_root, normalize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extensions tried, in order, for specifiers that omit them.
+const SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs"];
+
+/// `exports` conditions tried in order; source-like targets come first since
+/// only source files are analyzed.
+const EXPORT_CONDITIONS: &[&str] = &[
+    "source", "types", "import", "module", "default", "require", "node", "browser",
+];
+
+/// Package fields naming the entry file when there is no `exports` map.
+const ENTRY_FIELDS: &[&str] = &["source", "types", "typings", "module", "main"];
+
+/// `package.json` fields listing dependencies.
+const DEPENDENCY_FIELDS: &[&str] = &[
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+/// Build output directories mapped back to `src` when a package entry points
+/// at files that are not analyzed.
+const BUILD_DIRS: &[&str] = &["dist", "build", "lib", "out"];
+
+/// Maps JavaScript and TypeScript import specifiers onto analyzed files.
+///
+/// Relative specifiers are resolved against the importing file. Bare ones go
+/// through the nearest `tsconfig.json`/`jsconfig.json` (`paths`, `baseUrl`,
+/// following `extends` and `references`) and then through the `name` and
+/// `exports` of `package.json` files above the analyzed files, which covers
+/// npm, yarn and pnpm workspace packages.
+#[derive(Debug, Default)]
+pub struct ImportResolver {
+    files: HashSet<PathBuf>,
+    /// Compiler options of the configs found in each directory.
+    configs: HashMap<PathBuf, Vec<TsConfig>>,
+    packages: Vec<NodePackage>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TsConfig {
+    base_url: Option<PathBuf>,
+    /// `paths` patterns with their targets, resolved against `baseUrl` or,
+    /// without one, against `paths_dir`.
+    paths: Vec<(String, Vec<String>)>,
+    /// Directory of the config that set `paths`.
+    paths_dir: PathBuf,
+}
+
+#[derive(Debug)]
+struct NodePackage {
+    name: String,
+    dir: PathBuf,
+    manifest: Value,
+}
+
+impl ImportResolver {
+    /// Reads the configs and packages above `files`, up to the one of
+    /// `roots` each file lies under.
+    pub fn discover(roots: &[PathBuf], files: &[PathBuf]) -> Self {
+        let mut resolver = Self {
+            files: files.iter().map(|path| normalize(path)).collect(),
+            ..Self::default()
+        };
+        let mut visited = HashSet::new();
+        let mut loaded = HashSet::new();
+        for path in files {
+            let is_js = detect_language(path).is_some_and(|language| {
+                matches!(
+                    language,
+                    Language::JavaScript
+                        | Language::TypeScript
+                        | Language::Tsx
+                        | Language::Vue
+                        | Language::Svelte
+                        | Language::Astro
+                )
+            });
+            if !is_js {
+                continue;
+            }
+            let root = containing_root(roots, path);
+            for dir in path
+                .ancestors()
+                .skip(1)
+                .take_while(|dir| dir.starts_with(root))
+            {
+                if !visited.insert(dir.to_path_buf()) {
+                    break;
+                }
+                for name in ["tsconfig.json", "jsconfig.json"] {
+                    resolver.load_config(&dir.join(name), &mut loaded);
+                }
+                if let Some(package) = NodePackage::load(dir) {
+                    resolver.packages.push(package);
+                }
+            }
+        }
+        // Longest names first, so `@app/utils-extra` wins over `@app/utils`.
+        resolver
+            .packages
+            .sort_by(|a, b| b.name.len().cmp(&a.name.len()).then(a.name.cmp(&b.name)));
+        resolver
+    }
+
+    /// Whether the nearest `package.json` above `from` depends on `package`.
+    pub fn declares_dependency(&self, from: &Path, package: &str) -> bool {
+        let nearest = self
+            .packages
+            .iter()
+            .filter(|candidate| from.starts_with(&candidate.dir))
+            .max_by_key(|candidate| candidate.dir.components().count());
+        nearest.is_some_and(|nearest| {
+            DEPENDENCY_FIELDS.iter().any(|field| {
+                nearest
+                    .manifest
+                    .get(field)
+                    .and_then(Value::as_object)
+                    .is_some_and(|dependencies| dependencies.contains_key(package))
+            })
+        })
+    }
+
+    /// The analyzed file `specifier` refers to when imported from `from`.
+    pub fn resolve(&self, from: &Path, specifier: &str) -> Option<PathBuf> {
+        if specifier.starts_with("./")
+            || specifier.starts_with("../")
+            || specifier == "."
+            || specifier == ".."
+        {
+            return self.probe(&from.parent()?.join(specifier));
+        }
+        if specifier.starts_with('/') {
+            return None;
+        }
+        self.resolve_with_config(from, specifier)
+            .or_else(|| self.resolve_package(specifier))
+    }
+
+    fn resolve_with_config(&self, from: &Path, specifier: &str) -> Option<PathBuf> {
+        let configs = from
+            .ancestors()
+            .skip(1)
+            .find_map(|dir| self.configs.get(dir))?;
+        for config in configs {
+            let matched = config
+                .paths
+                .iter()
+                .filter_map(|(pattern, targets)| {
+                    let (prefix, captured) = match_pattern(pattern, specifier)?;
+                    Some((prefix, captured, targets))
+                })
+                .max_by_key(|(prefix, _, _)| prefix.len());
+            if let Some((_, captured, targets)) = matched {
+                let base = config.base_url.as_ref().unwrap_or(&config.paths_dir);
+                let found = targets
+                    .iter()
+                    .find_map(|target| self.probe(&base.join(target.replacen('*', captured, 1))));
+                if found.is_some() {
+                    return found;
+                }
+            }
+            if let Some(found) = config
+                .base_url
+                .as_ref()
+                .and_then(|base_url| self.probe(&base_url.join(specifier)))
+            {
+                return Some(found);
+            }
+        }
+        None
+    }
+
+    fn resolve_package(&self, specifier: &str) -> Option<PathBuf> {
+        let (package, subpath) = self.packages.iter().find_map(|package| {
+            let rest = specifier.strip_prefix(&package.name)?;
+            match rest {
+                "" => Some((package, ".".to_string())),
+                _ => rest
+                    .strip_prefix('/')
+                    .map(|rest| (package, format!("./{rest}"))),
+            }
+        })?;
+
+        if let Some(exports) = package.manifest.get("exports") {
+            return export_targets(exports, &subpath)
+                .iter()
+                .find_map(|target| self.probe_package_target(package, target));
+        }
+        if subpath == "." {
+            return ENTRY_FIELDS
+                .iter()
+                .filter_map(|field| package.manifest.get(field)?.as_str())
+                .chain(["index", "src/index"])
+                .find_map(|target| self.probe_package_target(package, target));
+        }
+        self.probe_package_target(package, &subpath)
+    }
+
+    /// Probes a path inside a package, falling back from build output such
+    /// as `dist/index.js` to the matching `src/index.ts`.
+    fn probe_package_target(&self, package: &NodePackage, target: &str) -> Option<PathBuf> {
+        let path = normalize(&package.dir.join(target));
+        self.probe(&path).or_else(|| {
+            let relative = path.strip_prefix(&package.dir).ok()?;
+            let mut components = relative.components();
+            let first = components.next()?.as_os_str().to_str()?;
+            if !BUILD_DIRS.contains(&first) {
+                return None;
+            }
+            self.probe(&package.dir.join("src").join(components.as_path()))
+        })
+    }
+
+    /// The analyzed file for a path written the way imports write them:
+    /// with or without an extension, with a `.js` extension standing for a
+    /// `.ts` source, as a `.d.ts` declaration, or as a directory index.
+    fn probe(&self, path: &Path) -> Option<PathBuf> {
+        let path = normalize(path);
+        if self.files.contains(&path) {
+            return Some(path);
+        }
+        let text = path.to_string_lossy();
+        let stem = text
+            .strip_suffix(".d.ts")
+            .or_else(|| {
+                [".js", ".jsx", ".mjs", ".cjs"]
+                    .iter()
+                    .find_map(|ext| text.strip_suffix(ext))
+            })
+            .unwrap_or(&text);
+        SOURCE_EXTENSIONS
+            .iter()
+            .map(|ext| PathBuf::from(format!("{stem}.{ext}")))
+            .chain(
+                SOURCE_EXTENSIONS
+                    .iter()
+                    .map(|ext| path.join(format!("index.{ext}"))),
+            )
+            .find(|candidate| self.files.contains(candidate))
+    }
+
+    /// Loads a tsconfig/jsconfig and the configs it references, registering
+    /// each under its directory.
+    fn load_config(&mut self, path: &Path, seen: &mut HashSet<PathBuf>) {
+        let path = normalize(path);
+        if !seen.insert(path.clone()) {
+            return;
+        }
+        let Some(value) = read_jsonc(&path) else {
+            return;
+        };
+        let Some(dir) = path.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        let config = ts_config(&path, &value, &mut HashSet::new());
+        self.configs.entry(dir.clone()).or_default().push(config);
+
+        let references = value
+            .get("references")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        for reference in references {
+            let Some(reference) = reference.get("path").and_then(Value::as_str) else {
+                continue;
+            };
+            let reference = dir.join(reference);
+            let reference = if reference.extension().is_some_and(|ext| ext == "json") {
+                reference
+            } else {
+                reference.join("tsconfig.json")
+            };
+            self.load_config(&reference, seen);
+        }
+    }
+}
+
+/// Compiler options of the config at `path`, merged over the configs it
+/// `extends`.
+fn ts_config(path: &Path, value: &Value, seen: &mut HashSet<PathBuf>) -> TsConfig {
+    let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+    seen.insert(path.to_path_buf());
+
+    let extends: Vec<&str> = match value.get("extends") {
+        Some(Value::String(extends)) => vec![extends.as_str()],
+        Some(Value::Array(extends)) => extends.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    let mut config = TsConfig {
+        paths_dir: dir.clone(),
+        ..TsConfig::default()
+    };
+    for parent in extends {
+        // Configs shipped in packages (`@tsconfig/node18`) carry no paths.
+        if !parent.starts_with('.') {
+            continue;
+        }
+        let mut parent_path = normalize(&dir.join(parent));
+        if parent_path.extension().is_none() {
+            parent_path.set_extension("json");
+        }
+        if seen.contains(&parent_path) {
+            continue;
+        }
+        if let Some(parent_value) = read_jsonc(&parent_path) {
+            let parent = ts_config(&parent_path, &parent_value, seen);
+            if parent.base_url.is_some() {
+                config.base_url = parent.base_url;
+            }
+            if !parent.paths.is_empty() {
+                config.paths = parent.paths;
+                config.paths_dir = parent.paths_dir;
+            }
+        }
+    }
+
+    let options = value.get("compilerOptions");
+    if let Some(base_url) = options
+        .and_then(|options| options.get("baseUrl"))
+        .and_then(Value::as_str)
+    {
+        config.base_url = Some(normalize(&dir.join(base_url)));
+    }
+    if let Some(paths) = options
+        .and_then(|options| options.get("paths"))
+        .and_then(Value::as_object)
+    {
+        config.paths = paths
+            .iter()
+            .map(|(pattern, targets)| {
+                let targets = targets
+                    .as_array()
+                    .map(|targets| {
+                        targets
+                            .iter()
+                            .filter_map(Value::as_str)
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (pattern.clone(), targets)
+            })
+            .collect();
+        config.paths_dir = dir;
+    }
+    config
+}
+
+/// The `name` in `dir/package.json`.
+pub(crate) fn node_package_name(dir: &Path) -> Option<String> {
+    NodePackage::load(dir).map(|package| package.name)
+}
+
+impl NodePackage {
+    fn load(dir: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(dir.join("package.json")).ok()?;
+        let manifest: Value = serde_json::from_str(&contents).ok()?;
+        let name = manifest.get("name")?.as_str()?.to_string();
+        Some(Self {
+            name,
+            dir: dir.to_path_buf(),
+            manifest,
+        })
+    }
+}
+
+/// Matches `specifier` against a `paths` or `exports` pattern with at most
+/// one `*`, returning the literal prefix and the text captured by `*`.
+fn match_pattern<'a>(pattern: &'a str, specifier: &'a str) -> Option<(&'a str, &'a str)> {
+    match pattern.split_once('*') {
+        None => (pattern == specifier).then_some((pattern, "")),
+        Some((prefix, suffix)) => {
+            let captured = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some((prefix, captured))
+        }
+    }
+}
+
+/// Targets of `subpath` (`.` or `./name`) in a package `exports` field, in
+/// order of preference.
+fn export_targets(exports: &Value, subpath: &str) -> Vec<String> {
+    let subpaths = exports
+        .as_object()
+        .filter(|map| map.keys().any(|key| key.starts_with('.')));
+    let Some(subpaths) = subpaths else {
+        // Sugar for `{ ".": exports }`.
+        return if subpath == "." {
+            condition_targets(exports)
+        } else {
+            Vec::new()
+        };
+    };
+    if let Some(target) = subpaths.get(subpath) {
+        return condition_targets(target);
+    }
+    subpaths
+        .iter()
+        .filter_map(|(pattern, target)| {
+            let (prefix, captured) = match_pattern(pattern, subpath)?;
+            pattern.contains('*').then_some((prefix, captured, target))
+        })
+        .max_by_key(|(prefix, _, _)| prefix.len())
+        .map(|(_, captured, target)| {
+            condition_targets(target)
+                .into_iter()
+                .map(|target| target.replace('*', captured))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Flattens a target, conditional object or fallback array into paths.
+fn condition_targets(target: &Value) -> Vec<String> {
+    match target {
+        Value::String(path) => vec![path.clone()],
+        Value::Array(targets) => targets.iter().flat_map(condition_targets).collect(),
+        Value::Object(conditions) => {
+            let preferred = EXPORT_CONDITIONS
+                .iter()
+                .filter_map(|condition| conditions.get(*condition));
+            let others = conditions
+                .iter()
+                .filter(|(condition, _)| !EXPORT_CONDITIONS.contains(&condition.as_str()))
+                .map(|(_, target)| target);
+            preferred
+                .chain(others)
+                .flat_map(condition_targets)
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Reads a JSON file that may contain comments and trailing commas, as
+/// `tsconfig.json` files usually do.
+fn read_jsonc(path: &Path) -> Option<Value> {
+    let contents = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&strip_jsonc(&contents)).ok()
+}
+
+fn strip_jsonc(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+    while let Some(ch) = chars.next() {
+        if in_string {
+            output.push(ch);
+            match ch {
+                '\\' => output.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (ch, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                output.push(ch);
+            }
+            ('/', Some('/')) => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        output.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            (',', _) => {
+                let rest = chars.clone().find(|next| !next.is_whitespace());
+                if !matches!(rest, Some('}' | ']')) {
+                    output.push(ch);
+                }
+            }
+            _ => output.push(ch),
+        }
+    }
+    output
+}
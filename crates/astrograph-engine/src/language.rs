@@ -1,27 +1,188 @@
-use crate::model::Language;
+use crate::embedded::{ComponentAnalyzer, MarkdownAnalyzer};
+use crate::model::{CallKind, Language, Symbol};
+use crate::parser::{
+    CAnalyzer, ContainerInfo, JavaAnalyzer, JsAnalyzer, KotlinAnalyzer, ParseContext, RustAnalyzer,
+};
+use std::fmt;
+use std::ops::Range;
 use std::path::Path;
-use tree_sitter::Language as TsLanguage;
+use std::sync::{Arc, OnceLock};
+use tree_sitter::{Language as TsLanguage, Node};
 
-pub fn detect_language(path: &Path) -> Option<Language> {
-    let ext = path.extension()?.to_string_lossy().to_ascii_lowercase();
-    match ext.as_str() {
-        "rs" => Some(Language::Rust),
-        "js" | "cjs" | "mjs" => Some(Language::JavaScript),
-        "ts" => Some(Language::TypeScript),
-        "tsx" => Some(Language::Tsx),
-        _ => None,
+/// Extracts symbols and calls for one language from its Tree-sitter syntax
+/// tree. The parser walks the tree and, for each node, asks in turn for a
+/// container, a function-like symbol and a call; the first match wins. Every
+/// node then goes through [`visit`](Self::visit), which can record imports
+/// or stop the walk from descending.
+///
+/// Symbols should be built with [`ParseContext::new_symbol`] so their ids and
+/// fully qualified names follow the enclosing containers.
+pub trait LanguageAnalyzer: Send + Sync {
+    fn language(&self) -> Language;
+
+    /// Lowercase file extensions, without the dot.
+    fn extensions(&self) -> &[&'static str];
+
+    fn grammar(&self) -> TsLanguage;
+
+    /// A Tree-sitter query tagging definitions and calls with captures such
+    /// as `@definition.function` and `@reference.call`. When set, only the
+    /// nodes it tags are extracted, and projects can add to it or replace it.
+    fn tags_query(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Whether fully qualified names come from the source (a package or
+    /// namespace declaration) instead of the file's module path.
+    fn names_from_source(&self) -> bool {
+        false
+    }
+
+    /// Whether top-level calls run when the file is loaded, making the
+    /// module-init pseudo-symbol an entrypoint.
+    fn module_init_is_entrypoint(&self) -> bool {
+        false
+    }
+
+    /// For files embedding code in another language, such as the `<script>`
+    /// blocks of a component: the byte ranges of that code and the language
+    /// whose analyzer parses it. Those files are never parsed with
+    /// [`grammar`](Self::grammar).
+    fn embedded(&self, _source: &[u8]) -> Option<EmbeddedCode> {
+        None
+    }
+
+    /// A node that scopes the symbols below it, such as a module or class.
+    fn container(&self, _node: Node, _cx: &mut ParseContext<'_>) -> Option<ContainerInfo> {
+        None
+    }
+
+    /// A function, method or closure defined by `node`.
+    fn function(&self, _node: Node, _cx: &mut ParseContext<'_>) -> Option<Symbol> {
+        None
+    }
+
+    /// The callee name and kind of a call expression.
+    fn call(&self, _node: Node, _source: &[u8]) -> Option<(String, CallKind)> {
+        None
+    }
+
+    /// Fills in the signature, doc comment and modifiers of a symbol created
+    /// for `node`.
+    fn describe(&self, _node: Node, _source: &[u8], _symbol: &mut Symbol) {}
+
+    /// Called for every node that is neither a container nor a function;
+    /// returns whether to walk its children.
+    fn visit(&self, _node: Node, _cx: &mut ParseContext<'_>) -> bool {
+        true
+    }
+}
+
+/// Code embedded in a file of another language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedCode {
+    /// The language the code is parsed as.
+    pub language: Language,
+    /// Byte ranges of the code, in order. Positions stay those of the
+    /// enclosing file.
+    pub ranges: Vec<Range<usize>>,
+    /// Blocks left out because they are in an unsupported language, as that
+    /// language's name and the block's byte range.
+    pub unsupported: Vec<(String, Range<usize>)>,
+}
+
+/// The analyzers used to detect and parse files, by extension.
+#[derive(Clone)]
+pub struct LanguageRegistry {
+    analyzers: Vec<Arc<dyn LanguageAnalyzer>>,
+}
+
+impl LanguageRegistry {
+    /// A registry without any language.
+    pub fn empty() -> Self {
+        Self {
+            analyzers: Vec::new(),
+        }
+    }
+
+    /// Adds an analyzer. It takes precedence over earlier ones for the same
+    /// language or extensions, so built-in languages can be replaced.
+    pub fn register(&mut self, analyzer: impl LanguageAnalyzer + 'static) -> &mut Self {
+        self.analyzers.push(Arc::new(analyzer));
+        self
+    }
+
+    pub fn get(&self, language: Language) -> Option<&dyn LanguageAnalyzer> {
+        self.analyzers
+            .iter()
+            .rev()
+            .find(|analyzer| analyzer.language() == language)
+            .map(Arc::as_ref)
+    }
+
+    pub fn detect(&self, path: &Path) -> Option<Language> {
+        let ext = path.extension()?.to_string_lossy().to_ascii_lowercase();
+        self.analyzers
+            .iter()
+            .rev()
+            .find(|analyzer| analyzer.extensions().contains(&ext.as_str()))
+            .map(|analyzer| analyzer.language())
+    }
+
+    pub fn languages(&self) -> Vec<Language> {
+        let mut languages = Vec::new();
+        for analyzer in &self.analyzers {
+            if !languages.contains(&analyzer.language()) {
+                languages.push(analyzer.language());
+            }
+        }
+        languages
+    }
+
+    pub fn extensions(&self) -> Vec<&'static str> {
+        let mut extensions = Vec::new();
+        for analyzer in &self.analyzers {
+            for ext in analyzer.extensions() {
+                if !extensions.contains(ext) {
+                    extensions.push(*ext);
+                }
+            }
+        }
+        extensions
     }
 }
 
-pub fn supported_extensions() -> Vec<&'static str> {
-    vec!["rs", "js", "cjs", "mjs", "ts", "tsx"]
+impl Default for LanguageRegistry {
+    /// The built-in languages.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register(RustAnalyzer)
+            .register(JsAnalyzer(Language::JavaScript))
+            .register(JsAnalyzer(Language::TypeScript))
+            .register(JsAnalyzer(Language::Tsx))
+            .register(JavaAnalyzer)
+            .register(KotlinAnalyzer)
+            .register(CAnalyzer(Language::C))
+            .register(CAnalyzer(Language::Cpp))
+            .register(ComponentAnalyzer(Language::Vue))
+            .register(ComponentAnalyzer(Language::Svelte))
+            .register(ComponentAnalyzer(Language::Astro))
+            .register(MarkdownAnalyzer);
+        registry
+    }
 }
 
-pub fn tree_sitter_language(language: Language) -> TsLanguage {
-    match language {
-        Language::Rust => tree_sitter_rust::language(),
-        Language::JavaScript => tree_sitter_javascript::language(),
-        Language::TypeScript => tree_sitter_typescript::language_typescript(),
-        Language::Tsx => tree_sitter_typescript::language_tsx(),
+impl fmt::Debug for LanguageRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.languages().iter().map(|language| language.name()))
+            .finish()
     }
 }
+
+/// The built-in language of a file, by extension.
+pub fn detect_language(path: &Path) -> Option<Language> {
+    static BUILTIN: OnceLock<LanguageRegistry> = OnceLock::new();
+    BUILTIN.get_or_init(LanguageRegistry::default).detect(path)
+}
@@ -1,9 +1,34 @@
 pub mod analysis;
 pub mod cache;
+pub mod config;
+mod embedded;
+pub mod entrypoint;
+pub mod external;
+pub mod imports;
 pub mod language;
+pub mod library;
 pub mod model;
+pub mod modules;
 pub mod parser;
+pub mod query;
+pub mod tags;
 
-pub use analysis::{analyze_project, AnalysisConfig, AnalysisOutput, ProgressEvent};
+pub use analysis::{
+    analyze_project, AnalysisConfig, AnalysisOutput, ProgressEvent, ResolutionConfig, SkippedPath,
+    WatchFilter, DEFAULT_IGNORED_DIRS, DEFAULT_MAX_FILE_SIZE,
+};
 pub use cache::AnalysisCache;
-pub use model::{AnalysisResult, CallEdge, FileInfo, Language, Symbol, SymbolKind};
+pub use config::{ProjectConfig, ProjectSettings};
+pub use entrypoint::EntrypointPattern;
+pub use language::{EmbeddedCode, LanguageAnalyzer, LanguageRegistry};
+pub use library::LibraryConfig;
+pub use model::{
+    AnalysisResult, BindingExport, CallEdge, CallKind, Diagnostic, DiagnosticKind, EntrypointHint,
+    EntrypointKind, FileInfo, ImportBinding, Language, Modifier, Parameter, Span, Symbol,
+    SymbolKind,
+};
+pub use parser::{ContainerInfo, ContainerKind, ParseContext};
+pub use query::{AnalysisIndex, Neighbor, SymbolQuery};
+pub use tags::QueryFile;
+/// The Tree-sitter version grammars of custom languages must be built for.
+pub use tree_sitter;
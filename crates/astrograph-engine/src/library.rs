@@ -0,0 +1,266 @@
+use crate::language::LanguageRegistry;
+use crate::model::Language;
+use anyhow::{anyhow, Result};
+use ignore::WalkBuilder;
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Prefix of the paths given to library files in the analysis output.
+pub const LIBRARY_PATH_PREFIX: &str = "{library}";
+
+/// Directories skipped inside libraries. Unlike the project defaults this
+/// keeps `dist` and `build`, where npm packages usually ship their code.
+const LIBRARY_IGNORED_DIRS: &[&str] = &["node_modules", "target"];
+
+/// Dependencies whose sources are analyzed as a read-only library layer:
+/// their symbols resolve calls but are flagged `external` and are never
+/// entrypoints.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LibraryConfig {
+    /// Crates from the Cargo registry cache, as `name` or `name@version`.
+    /// Without a version, the one in the project's `Cargo.lock` is used, or
+    /// else the newest one in the cache.
+    pub crates: Vec<String>,
+    /// Packages from the project's `node_modules`.
+    pub packages: Vec<String>,
+    /// Cargo home holding `registry/src`; defaults to `$CARGO_HOME` or
+    /// `~/.cargo`.
+    pub cargo_home: Option<PathBuf>,
+}
+
+impl LibraryConfig {
+    pub fn is_empty(&self) -> bool {
+        self.crates.is_empty() && self.packages.is_empty()
+    }
+}
+
+/// A located library and its source files.
+#[derive(Debug)]
+pub(crate) struct Library {
+    /// Directory module paths are derived against, e.g. the registry index
+    /// directory or `node_modules`.
+    pub root: PathBuf,
+    /// The crate or package directory.
+    pub dir: PathBuf,
+    /// Output path prefix of its files, e.g. `{library}/cargo/serde-1.0.200`.
+    pub prefix: String,
+    pub files: Vec<PathBuf>,
+}
+
+impl Library {
+    /// Output path of one of the library's files.
+    pub fn relative_path(&self, path: &Path) -> String {
+        let relative = path.strip_prefix(&self.dir).unwrap_or(path);
+        format!(
+            "{}/{}",
+            self.prefix,
+            relative.to_string_lossy().replace('\\', "/")
+        )
+    }
+}
+
+/// Finds the configured libraries and lists their files. Missing crates or
+/// packages are an error, so a typo does not silently analyze nothing.
+pub(crate) fn locate_libraries(
+    project_root: &Path,
+    config: &LibraryConfig,
+    languages: &[Language],
+    analyzers: &LanguageRegistry,
+) -> Result<Vec<Library>> {
+    let mut libraries = Vec::new();
+    if !config.crates.is_empty() {
+        let cargo_home = match &config.cargo_home {
+            Some(cargo_home) => cargo_home.clone(),
+            None => default_cargo_home()?,
+        };
+        let locked = locked_versions(project_root);
+        for spec in &config.crates {
+            let (root, dir) = locate_crate(&cargo_home, spec, &locked)?;
+            let name = dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            libraries.push(Library {
+                root,
+                prefix: format!("{LIBRARY_PATH_PREFIX}/cargo/{name}"),
+                files: library_files(&dir, languages, analyzers),
+                dir,
+            });
+        }
+    }
+    for name in &config.packages {
+        let node_modules = project_root.join("node_modules");
+        // pnpm links packages into its store; analyze the real files.
+        let dir = node_modules.join(name).canonicalize().map_err(|_| {
+            anyhow!(
+                "Library package `{name}` not found in {}",
+                node_modules.display()
+            )
+        })?;
+        let depth = if name.starts_with('@') { 2 } else { 1 };
+        let root = dir.ancestors().nth(depth).unwrap_or(&dir).to_path_buf();
+        libraries.push(Library {
+            root,
+            prefix: format!("{LIBRARY_PATH_PREFIX}/npm/{name}"),
+            files: library_files(&dir, languages, analyzers),
+            dir,
+        });
+    }
+    Ok(libraries)
+}
+
+fn default_cargo_home() -> Result<PathBuf> {
+    if let Some(cargo_home) = std::env::var_os("CARGO_HOME") {
+        return Ok(PathBuf::from(cargo_home));
+    }
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .ok_or_else(|| anyhow!("Cannot find the Cargo home; set CARGO_HOME"))?;
+    Ok(PathBuf::from(home).join(".cargo"))
+}
+
+/// Returns the registry index directory and the crate directory for `spec`.
+fn locate_crate(
+    cargo_home: &Path,
+    spec: &str,
+    locked: &[(String, String)],
+) -> Result<(PathBuf, PathBuf)> {
+    let (name, version) = match spec.split_once('@') {
+        Some((name, version)) => (name, Some(version)),
+        None => (spec, None),
+    };
+    let registry = cargo_home.join("registry").join("src");
+    let mut candidates = Vec::new();
+    let indexes = fs::read_dir(&registry)
+        .map_err(|_| anyhow!("Cargo registry cache not found at {}", registry.display()))?;
+    for index in indexes.flatten() {
+        for entry in fs::read_dir(index.path()).into_iter().flatten().flatten() {
+            let dir_name = entry.file_name().to_string_lossy().to_string();
+            let Some((dir_crate, dir_version)) = split_crate_dir(&dir_name) else {
+                continue;
+            };
+            if dir_crate.replace('-', "_") == name.replace('-', "_") {
+                candidates.push((dir_version.to_string(), index.path(), entry.path()));
+            }
+        }
+    }
+
+    let wanted: Vec<&str> = match version {
+        Some(version) => vec![version],
+        None => locked
+            .iter()
+            .filter(|(locked_name, _)| locked_name.replace('-', "_") == name.replace('-', "_"))
+            .map(|(_, version)| version.as_str())
+            .collect(),
+    };
+    let preferred = candidates
+        .iter()
+        .filter(|(candidate, _, _)| wanted.contains(&candidate.as_str()))
+        .max_by(|a, b| compare_versions(&a.0, &b.0));
+    let chosen = match (preferred, version) {
+        (Some(chosen), _) => chosen,
+        (None, Some(version)) => {
+            return Err(anyhow!(
+                "Library crate `{name}@{version}` not found in {}",
+                registry.display()
+            ))
+        }
+        (None, None) => candidates
+            .iter()
+            .max_by(|a, b| compare_versions(&a.0, &b.0))
+            .ok_or_else(|| anyhow!("Library crate `{name}` not found in {}", registry.display()))?,
+    };
+    // Module discovery compares normalized paths, so resolve `..` and links.
+    Ok((chosen.1.canonicalize()?, chosen.2.canonicalize()?))
+}
+
+/// Splits `serde-json-1.0.0` into the crate name and version.
+fn split_crate_dir(dir_name: &str) -> Option<(&str, &str)> {
+    let index = dir_name
+        .match_indices('-')
+        .map(|(index, _)| index)
+        .find(|index| {
+            dir_name[index + 1..]
+                .chars()
+                .next()
+                .is_some_and(|ch| ch.is_ascii_digit())
+        })?;
+    Some((&dir_name[..index], &dir_name[index + 1..]))
+}
+
+/// Orders versions by their numeric components, e.g. `1.10.0` > `1.9.3`.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |version: &str| -> Vec<u64> {
+        version
+            .split(['.', '-', '+'])
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    parts(a).cmp(&parts(b))
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Lockfile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: String,
+}
+
+/// `(name, version)` pairs from the project's `Cargo.lock`, if any.
+fn locked_versions(project_root: &Path) -> Vec<(String, String)> {
+    fs::read_to_string(project_root.join("Cargo.lock"))
+        .ok()
+        .and_then(|contents| toml::from_str::<Lockfile>(&contents).ok())
+        .map(|lockfile| {
+            lockfile
+                .package
+                .into_iter()
+                .map(|package| (package.name, package.version))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Supported source files under `dir`, skipping hidden directories and
+/// [`LIBRARY_IGNORED_DIRS`]. Markdown is only included when listed in
+/// `languages`.
+fn library_files(dir: &Path, languages: &[Language], analyzers: &LanguageRegistry) -> Vec<PathBuf> {
+    let mut builder = WalkBuilder::new(dir);
+    builder.standard_filters(false).hidden(true);
+    builder.filter_entry(move |entry| {
+        entry.depth() == 0
+            || !entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_dir())
+            || !LIBRARY_IGNORED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref())
+    });
+    let mut files: Vec<PathBuf> = builder
+        .build()
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+        })
+        .map(|entry| entry.into_path())
+        .filter(|path| {
+            analyzers
+                .detect(path)
+                .is_some_and(|language| match language {
+                    _ if languages.contains(&language) => true,
+                    Language::Markdown => false,
+                    _ => languages.is_empty(),
+                })
+        })
+        .collect();
+    files.sort();
+    files
+}
@@ -1,12 +1,101 @@
-use serde::{Deserialize, Serialize};
+use crate::modules::ModDecl;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
-#[serde(rename_all = "lowercase")]
+/// A source language, serialized by its lowercase name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Language {
     Rust,
     JavaScript,
     TypeScript,
     Tsx,
+    Java,
+    Kotlin,
+    C,
+    Cpp,
+    /// Components whose `<script>` blocks are analyzed as JavaScript or
+    /// TypeScript.
+    Vue,
+    Svelte,
+    Astro,
+    /// JavaScript and TypeScript code blocks, when enabled.
+    Markdown,
+    /// A language added through a
+    /// [`LanguageAnalyzer`](crate::language::LanguageAnalyzer), by name.
+    Custom(&'static str),
+}
+
+impl Language {
+    pub const BUILTIN: &'static [Language] = &[
+        Language::Rust,
+        Language::JavaScript,
+        Language::TypeScript,
+        Language::Tsx,
+        Language::Java,
+        Language::Kotlin,
+        Language::C,
+        Language::Cpp,
+        Language::Vue,
+        Language::Svelte,
+        Language::Astro,
+        Language::Markdown,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::Rust => "rust",
+            Language::JavaScript => "javascript",
+            Language::TypeScript => "typescript",
+            Language::Tsx => "tsx",
+            Language::Java => "java",
+            Language::Kotlin => "kotlin",
+            Language::C => "c",
+            Language::Cpp => "cpp",
+            Language::Vue => "vue",
+            Language::Svelte => "svelte",
+            Language::Astro => "astro",
+            Language::Markdown => "markdown",
+            Language::Custom(name) => name,
+        }
+    }
+
+    /// The built-in language called `name`, or a custom one.
+    pub fn from_name(name: &str) -> Language {
+        Language::BUILTIN
+            .iter()
+            .copied()
+            .find(|language| language.name() == name)
+            .unwrap_or_else(|| Language::Custom(intern(name)))
+    }
+}
+
+/// Leaks each distinct custom language name once so `Language` stays `Copy`.
+fn intern(name: &str) -> &'static str {
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut names = NAMES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(name) = names.get(name) {
+        return name;
+    }
+    let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+    names.insert(name);
+    name
+}
+
+impl Serialize for Language {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Language {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Language::from_name(&name))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +108,9 @@ pub struct AnalysisResult {
     pub symbols: Vec<Symbol>,
     pub calls: Vec<CallEdge>,
     pub entrypoints: Vec<String>,
+    /// Problems that left the graph of a file incomplete, by file.
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -27,6 +119,11 @@ pub struct AnalysisStats {
     pub symbol_count: usize,
     pub call_count: usize,
     pub entrypoint_count: usize,
+    /// Entrypoints other than plain public API.
+    #[serde(default)]
+    pub program_entrypoint_count: usize,
+    #[serde(default)]
+    pub diagnostic_count: usize,
     pub reused_cache_files: usize,
     pub reanalyzed_files: usize,
 }
@@ -37,6 +134,9 @@ pub struct FileInfo {
     pub language: Language,
     pub hash: String,
     pub byte_size: usize,
+    /// Whether the file belongs to an analyzed library rather than the project.
+    #[serde(default)]
+    pub external: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,6 +159,20 @@ pub enum SymbolKind {
     Namespace,
     Function,
     Method,
+    Constant,
+    Static,
+    #[serde(rename = "type_alias")]
+    TypeAlias,
+    Union,
+    Macro,
+    /// Anonymous function: Rust closure, JS arrow function or function expression.
+    Closure,
+    /// Per-file pseudo-symbol owning top-level code and static initializers.
+    #[serde(rename = "module_init")]
+    ModuleInit,
+    /// Synthetic symbol for an external dependency (a crate, npm package,
+    /// standard library or runtime builtins) that unresolved calls point to.
+    External,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,7 +185,101 @@ pub struct Symbol {
     pub fq_name: String,
     pub container: Option<String>,
     pub is_exported: bool,
-    pub is_entrypoint: bool,
+    /// Why the symbol is an entrypoint; empty when it is not one.
+    #[serde(default)]
+    pub entrypoint_kinds: Vec<EntrypointKind>,
+    /// Rules that marked the symbol as an entrypoint, e.g. `main`,
+    /// `exported`, `rust-test` or `express-route`.
+    #[serde(default)]
+    pub entrypoint_rules: Vec<String>,
+    /// Declaration text up to the body, with whitespace collapsed.
+    #[serde(default)]
+    pub signature: Option<String>,
+    #[serde(default)]
+    pub parameters: Vec<Parameter>,
+    #[serde(default)]
+    pub return_type: Option<String>,
+    /// Generic/type parameter list as written, e.g. `<T: Clone>`.
+    #[serde(default)]
+    pub generics: Option<String>,
+    #[serde(default)]
+    pub modifiers: Vec<Modifier>,
+    /// Leading doc comment (`///`, `/** */`, JSDoc) with comment markers removed.
+    #[serde(default)]
+    pub doc: Option<String>,
+    /// Whether the symbol lives outside the project: in an analyzed library,
+    /// or a synthetic [`SymbolKind::External`] dependency.
+    #[serde(default)]
+    pub external: bool,
+    /// Whether this is a declaration without a body: a C/C++ prototype left
+    /// unpaired with its definition, or a function in a Rust `extern` block.
+    #[serde(default)]
+    pub declaration: bool,
+}
+
+impl Symbol {
+    pub fn is_entrypoint(&self) -> bool {
+        !self.entrypoint_kinds.is_empty()
+    }
+
+    /// Whether something actually runs the symbol, as opposed to it merely
+    /// being reachable as public API.
+    pub fn is_program_entrypoint(&self) -> bool {
+        self.entrypoint_kinds
+            .iter()
+            .any(|kind| *kind != EntrypointKind::PublicApi)
+    }
+
+    /// Records that `rule` marked the symbol as an entrypoint of `kind`.
+    pub fn mark_entrypoint(&mut self, kind: EntrypointKind, rule: &str) {
+        if let Err(position) = self.entrypoint_kinds.binary_search(&kind) {
+            self.entrypoint_kinds.insert(position, kind);
+        }
+        if !self
+            .entrypoint_rules
+            .iter()
+            .any(|existing| existing == rule)
+        {
+            self.entrypoint_rules.push(rule.to_string());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum EntrypointKind {
+    /// Program entry: `main`, `#[tokio::main]` and friends.
+    Main,
+    /// Exported/`pub` item reachable by library consumers.
+    PublicApi,
+    /// Test function or test block.
+    Test,
+    /// Handler invoked by a framework: HTTP routes, rendered React roots.
+    FrameworkHandler,
+    /// Function exported to foreign callers, e.g. `#[no_mangle] extern "C"`.
+    FfiExport,
+    /// Top-level module code that runs on import.
+    ModuleInit,
+    /// Marked by the user.
+    Manual,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Parameter {
+    pub name: String,
+    pub type_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Modifier {
+    Async,
+    Unsafe,
+    Const,
+    Static,
+    Extern,
+    /// A TypeScript abstract class or method.
+    Abstract,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,12 +288,110 @@ pub struct CallEdge {
     pub caller_id: String,
     pub callee_name: String,
     pub callee_id: Option<String>,
+    #[serde(default)]
+    pub kind: CallKind,
     pub file: String,
     pub span: Span,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CallKind {
+    /// A function, method or constructor call.
+    #[default]
+    Call,
+    /// A macro invocation such as `println!` or `vec!`.
+    Macro,
+    /// The caller defines an anonymous function (closure, callback) that
+    /// runs on its behalf; the edge is always resolved.
+    Defines,
+    /// A call across a language boundary: a foreign function declaration
+    /// linked to its definition (a Rust `extern "C"` function and its C
+    /// definition), or a JavaScript call into a Rust binding such as a Tauri
+    /// `invoke("command")`.
+    Ffi,
+    /// A JSX element of a component, such as `<UserCard user={u} />`,
+    /// rendering it. Resolved like a call.
+    Renders,
+}
+
+/// An entrypoint referenced by name, such as a route handler or a rendered
+/// component; resolved against all symbols once every file is parsed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EntrypointHint {
+    /// The name as referenced, e.g. `handler` or `routes.index`.
+    pub name: String,
+    pub kind: EntrypointKind,
+    pub rule: String,
+    pub file: String,
+}
+
+/// A binding introduced by a Rust `use` or a JavaScript/TypeScript
+/// `import ... from`, or re-exported by an `export ... from` statement.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImportBinding {
+    /// Module specifier as written, e.g. `@app/utils` or `./format`; for a
+    /// Rust `use`, the path of the parent module, e.g. `std::collections`.
+    pub source: String,
+    /// Name exported by the source module: a symbol name, `default`, or `*`
+    /// for the whole module.
+    pub imported: String,
+    /// Name the binding is visible under; for re-exports, the name it is
+    /// exported as (`*` for `export * from`).
+    pub local: String,
+    #[serde(default)]
+    pub reexport: bool,
+}
+
+/// A Rust function callable from JavaScript through a binding framework: a
+/// Tauri command, or a `#[wasm_bindgen]` or `#[napi]` export.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BindingExport {
+    pub symbol_id: String,
+    /// Name JavaScript uses: the command passed to `invoke`, or the exported
+    /// function name (`js_name`, camelCase for napi).
+    pub name: String,
+    /// Rule of the framework's entrypoint detector, e.g. `tauri-command`.
+    pub rule: String,
+}
+
+/// A problem found while analyzing a file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub file: String,
+    pub kind: DiagnosticKind,
+    pub message: String,
+    /// The region affected, when not the whole file.
+    pub span: Option<Span>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// A region Tree-sitter could not parse (an `ERROR` node) or a token it
+    /// had to assume (a `MISSING` node); code in it may be lost.
+    SyntaxError,
+    /// The file is not valid UTF-8; invalid bytes were replaced.
+    Encoding,
+    /// Code the analyzer cannot parse, such as a `<script lang="coffee">`
+    /// block in a Vue component.
+    Unsupported,
+    /// The file is larger than [`AnalysisConfig::max_file_size`] and was not
+    /// analyzed.
+    ///
+    /// [`AnalysisConfig::max_file_size`]: crate::AnalysisConfig::max_file_size
+    Oversized,
+}
+
 #[derive(Debug, Clone)]
 pub struct ParsedFile {
     pub symbols: Vec<Symbol>,
     pub calls: Vec<CallEdge>,
+    pub entrypoint_hints: Vec<EntrypointHint>,
+    pub imports: Vec<ImportBinding>,
+    pub binding_exports: Vec<BindingExport>,
+    pub diagnostics: Vec<Diagnostic>,
+    /// `mod` declarations of a Rust file, found while discovering module
+    /// paths.
+    pub mod_declarations: Vec<ModDecl>,
 }
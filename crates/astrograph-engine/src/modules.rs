@@ -0,0 +1,530 @@
+use crate::analysis::hash_bytes;
+use crate::language::detect_language;
+use crate::model::Language;
+use crate::parser::node_text;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tree_sitter::{Node, Parser};
+
+/// Module paths of the analyzed files, used as the prefix of their symbols'
+/// fully qualified names.
+///
+/// Rust files that belong to a Cargo package get crate-qualified paths such
+/// as `astrograph_engine::parser`: the package is the nearest `Cargo.toml`
+/// with a `[package]` table (which covers workspace members), its crate
+/// roots are `[lib]`/`[[bin]]` targets plus the usual `src/lib.rs`,
+/// `src/main.rs`, `src/bin`, `tests`, `examples` and `benches` files, and
+/// modules are followed through `mod` declarations and `#[path]` attributes.
+/// Every other file falls back to its path relative to the root.
+#[derive(Debug, Default)]
+pub struct ModulePaths {
+    paths: HashMap<PathBuf, String>,
+    /// `mod` declarations of each Rust file, kept for the cache.
+    declarations: HashMap<PathBuf, Vec<ModDecl>>,
+    /// Crate names (as written in code) of each package's dependencies,
+    /// leaving out the crates of analyzed packages.
+    dependencies: Vec<HashSet<String>>,
+    /// Directory and name (as written in code) of each package.
+    packages: Vec<(PathBuf, String)>,
+    file_packages: HashMap<PathBuf, usize>,
+}
+
+impl ModulePaths {
+    /// Discovers module paths for `files`, each of which lies under one of
+    /// `roots` (the project root, plus library roots). `known` holds the
+    /// `mod` declarations of previously parsed Rust files by content hash;
+    /// only files not in it are parsed.
+    pub fn discover(
+        roots: &[PathBuf],
+        files: &[PathBuf],
+        known: &HashMap<String, Vec<ModDecl>>,
+    ) -> Self {
+        let rust_files: Vec<&PathBuf> = files
+            .iter()
+            .filter(|path| detect_language(path) == Some(Language::Rust))
+            .collect();
+        let declarations: HashMap<PathBuf, Vec<ModDecl>> = rust_files
+            .par_iter()
+            .map(|path| {
+                let declarations = fs::read(path)
+                    .map(|source| match known.get(&hash_bytes(&source)) {
+                        Some(declarations) => declarations.clone(),
+                        None => mod_declarations(&source),
+                    })
+                    .unwrap_or_default();
+                ((*path).clone(), declarations)
+            })
+            .collect();
+
+        let mut packages: Vec<Package> = Vec::new();
+        let mut manifests: HashMap<PathBuf, Option<usize>> = HashMap::new();
+        let mut file_packages: Vec<(&PathBuf, usize)> = Vec::new();
+        for path in &rust_files {
+            let root = containing_root(roots, path);
+            let package = path
+                .ancestors()
+                .skip(1)
+                .take_while(|dir| dir.starts_with(root))
+                .find_map(|dir| {
+                    *manifests.entry(dir.to_path_buf()).or_insert_with(|| {
+                        let package = Package::load(dir)?;
+                        packages.push(package);
+                        Some(packages.len() - 1)
+                    })
+                });
+            if let Some(package) = package {
+                file_packages.push((path, package));
+            }
+        }
+
+        let mut module_paths = Self::default();
+        let mut walker = ModuleWalker {
+            declarations: &declarations,
+            paths: &mut module_paths.paths,
+        };
+        let mut ordered: Vec<&Package> = packages.iter().collect();
+        ordered.sort_by(|a, b| a.dir.cmp(&b.dir));
+        for package in ordered {
+            for target in &package.targets {
+                let child_dir = target.path.parent().unwrap_or(&package.dir);
+                walker.walk_file(&target.path, target.crate_name.clone(), child_dir);
+            }
+        }
+
+        // Files no `mod` declaration reaches keep their place in the package.
+        for (path, index) in file_packages {
+            module_paths.file_packages.insert(path.clone(), index);
+            let package = &packages[index];
+            module_paths.paths.entry(path.clone()).or_insert_with(|| {
+                let relative = path.strip_prefix(&package.dir).unwrap_or(path);
+                let relative = relative.strip_prefix("src").unwrap_or(relative);
+                let module = module_path_from_file(relative, Path::new(""));
+                join_module(&package.crate_name, &module)
+            });
+        }
+        for path in files {
+            module_paths
+                .paths
+                .entry(path.clone())
+                .or_insert_with(|| module_path_from_file(path, containing_root(roots, path)));
+        }
+        // Analyzed crates are never external, even when depended on by path.
+        let local_crates: HashSet<String> = packages
+            .iter()
+            .map(|package| package.crate_name.clone())
+            .collect();
+        for package in &mut packages {
+            package
+                .dependencies
+                .retain(|dependency| !local_crates.contains(dependency));
+        }
+        (module_paths.packages, module_paths.dependencies) = packages
+            .into_iter()
+            .map(|package| ((package.dir, package.name), package.dependencies))
+            .unzip();
+        module_paths.declarations = declarations;
+        module_paths
+    }
+
+    /// `mod` declarations of a Rust file passed to [`ModulePaths::discover`].
+    pub fn mod_declarations(&self, path: &Path) -> Vec<ModDecl> {
+        self.declarations.get(path).cloned().unwrap_or_default()
+    }
+
+    /// Directory and name (with `_` for `-`) of the Cargo package an analyzed
+    /// file belongs to.
+    pub fn package(&self, path: &Path) -> Option<(&Path, &str)> {
+        let index = self.file_packages.get(path)?;
+        let (dir, name) = self.packages.get(*index)?;
+        Some((dir, name))
+    }
+
+    /// Dependencies declared by the Cargo package an analyzed file belongs to,
+    /// other than analyzed crates.
+    pub fn dependencies(&self, path: &Path) -> Option<&HashSet<String>> {
+        let index = self.file_packages.get(path)?;
+        self.dependencies.get(*index)
+    }
+
+    /// Module path of a file passed to [`ModulePaths::discover`].
+    pub fn module_path(&self, path: &Path) -> String {
+        self.paths.get(path).cloned().unwrap_or_default()
+    }
+}
+
+/// The innermost of `roots` containing `path`.
+pub(crate) fn containing_root<'a>(roots: &'a [PathBuf], path: &Path) -> &'a Path {
+    roots
+        .iter()
+        .filter(|root| path.starts_with(root))
+        .max_by_key(|root| root.components().count())
+        .map(PathBuf::as_path)
+        .unwrap_or(Path::new(""))
+}
+
+/// Module path built from a file path, e.g. `src/api/mod.rs` -> `src::api`.
+pub(crate) fn module_path_from_file(path: &Path, root: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let mut components: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect();
+    if let Some(last) = components.last_mut() {
+        if let Some((stem, extension)) = last.rsplit_once('.') {
+            // A TypeScript declaration file `types.d.ts` describes module `types`.
+            let stem = match extension {
+                "ts" | "mts" | "cts" => stem.strip_suffix(".d").unwrap_or(stem),
+                _ => stem,
+            };
+            *last = stem.to_string();
+        }
+        if last == "mod" || last == "index" {
+            components.pop();
+        }
+    }
+    components.join("::")
+}
+
+/// Expands a leading `crate::`, `self::` or `super::` in a Rust path using
+/// the module path of the file it appears in. Returns `None` for paths that
+/// are not module-relative.
+pub(crate) fn qualify_rust_path(path: &str, module_path: &str) -> Option<String> {
+    let mut module: Vec<&str> = module_path.split("::").collect();
+    let mut rest = path;
+    if let Some(tail) = rest.strip_prefix("crate::") {
+        module.truncate(1);
+        rest = tail;
+    } else if let Some(tail) = rest.strip_prefix("self::") {
+        rest = tail;
+    } else if rest.starts_with("super::") {
+        while let Some(tail) = rest.strip_prefix("super::") {
+            module.pop()?;
+            rest = tail;
+        }
+    } else {
+        return None;
+    }
+    Some(join_module(&module.join("::"), rest))
+}
+
+pub(crate) fn join_module(prefix: &str, rest: &str) -> String {
+    match (prefix.is_empty(), rest.is_empty()) {
+        (_, true) => prefix.to_string(),
+        (true, false) => rest.to_string(),
+        (false, false) => format!("{prefix}::{rest}"),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Manifest {
+    package: Option<ManifestPackage>,
+    lib: Option<ManifestTarget>,
+    #[serde(default)]
+    bin: Vec<ManifestTarget>,
+    #[serde(flatten)]
+    dependencies: ManifestDependencies,
+    /// `[target.'cfg(...)'.dependencies]` tables.
+    #[serde(default)]
+    target: BTreeMap<String, ManifestDependencies>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ManifestDependencies {
+    #[serde(default)]
+    dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default)]
+    dev_dependencies: BTreeMap<String, toml::Value>,
+    #[serde(default)]
+    build_dependencies: BTreeMap<String, toml::Value>,
+}
+
+impl ManifestDependencies {
+    fn names(&self) -> impl Iterator<Item = String> + '_ {
+        self.dependencies
+            .keys()
+            .chain(self.dev_dependencies.keys())
+            .chain(self.build_dependencies.keys())
+            .map(|name| crate_name(name))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ManifestPackage {
+    name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ManifestTarget {
+    name: Option<String>,
+    path: Option<PathBuf>,
+}
+
+/// A Cargo package and the root files of its crates.
+#[derive(Debug)]
+struct Package {
+    dir: PathBuf,
+    /// Package name, with `_` for `-`.
+    name: String,
+    /// Name of the library crate, or of the package when there is none.
+    crate_name: String,
+    targets: Vec<CrateRoot>,
+    dependencies: HashSet<String>,
+}
+
+#[derive(Debug)]
+struct CrateRoot {
+    path: PathBuf,
+    crate_name: String,
+}
+
+impl Package {
+    /// Reads `dir/Cargo.toml`; virtual workspace manifests and unreadable
+    /// manifests yield `None`.
+    fn load(dir: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+        let manifest: Manifest = toml::from_str(&contents).ok()?;
+        let package_name = crate_name(&manifest.package?.name);
+        let mut targets = Vec::new();
+
+        let lib = manifest.lib.unwrap_or_default();
+        let lib_name = lib.name.as_deref().map(crate_name);
+        let lib_path = lib.path.unwrap_or_else(|| PathBuf::from("src/lib.rs"));
+        if dir.join(&lib_path).is_file() {
+            targets.push(CrateRoot {
+                path: normalize(&dir.join(lib_path)),
+                crate_name: lib_name.clone().unwrap_or_else(|| package_name.clone()),
+            });
+        }
+
+        for bin in &manifest.bin {
+            let name = bin.name.as_deref().map(crate_name);
+            let path = bin.path.clone().or_else(|| {
+                let name = bin.name.as_ref()?;
+                [
+                    PathBuf::from(format!("src/bin/{name}.rs")),
+                    PathBuf::from(format!("src/bin/{name}/main.rs")),
+                ]
+                .into_iter()
+                .find(|path| dir.join(path).is_file())
+            });
+            if let Some(path) = path {
+                targets.push(CrateRoot {
+                    path: normalize(&dir.join(path)),
+                    crate_name: name.unwrap_or_else(|| package_name.clone()),
+                });
+            }
+        }
+        if dir.join("src/main.rs").is_file() {
+            targets.push(CrateRoot {
+                path: dir.join("src/main.rs"),
+                crate_name: package_name.clone(),
+            });
+        }
+        if dir.join("build.rs").is_file() {
+            targets.push(CrateRoot {
+                path: dir.join("build.rs"),
+                crate_name: "build_script_build".to_string(),
+            });
+        }
+        for target_dir in ["src/bin", "tests", "examples", "benches"] {
+            targets.extend(auto_targets(&dir.join(target_dir)));
+        }
+
+        let dependencies = manifest
+            .dependencies
+            .names()
+            .chain(
+                manifest
+                    .target
+                    .values()
+                    .flat_map(ManifestDependencies::names),
+            )
+            .collect();
+        Some(Self {
+            dir: dir.to_path_buf(),
+            crate_name: lib_name.unwrap_or_else(|| package_name.clone()),
+            name: package_name,
+            targets,
+            dependencies,
+        })
+    }
+}
+
+/// `dir/*.rs` and `dir/*/main.rs`, named after the file or directory.
+fn auto_targets(dir: &Path) -> Vec<CrateRoot> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut targets: Vec<CrateRoot> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_stem()?.to_string_lossy().to_string();
+            if path.is_dir() {
+                let main = path.join("main.rs");
+                return main.is_file().then(|| CrateRoot {
+                    path: main,
+                    crate_name: crate_name(&name),
+                });
+            }
+            (path.extension()? == "rs").then(|| CrateRoot {
+                path,
+                crate_name: crate_name(&name),
+            })
+        })
+        .collect();
+    targets.sort_by(|a, b| a.path.cmp(&b.path));
+    targets
+}
+
+fn crate_name(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+/// An out-of-line `mod name;` declaration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModDecl {
+    /// Enclosing inline `mod name { ... }` blocks and their `#[path]`.
+    pub inline: Vec<(String, Option<String>)>,
+    pub name: String,
+    pub path: Option<String>,
+}
+
+fn mod_declarations(source: &[u8]) -> Vec<ModDecl> {
+    let mut parser = Parser::new();
+    if parser.set_language(&tree_sitter_rust::language()).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+    let mut declarations = Vec::new();
+    collect_mod_declarations(tree.root_node(), source, &mut Vec::new(), &mut declarations);
+    declarations
+}
+
+fn collect_mod_declarations(
+    node: Node,
+    source: &[u8],
+    inline: &mut Vec<(String, Option<String>)>,
+    declarations: &mut Vec<ModDecl>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if child.kind() != "mod_item" {
+            continue;
+        }
+        let Some(name) = child.child_by_field_name("name") else {
+            continue;
+        };
+        let name = node_text(name, source).to_string();
+        let path = path_attribute(child, source);
+        match child.child_by_field_name("body") {
+            Some(body) => {
+                inline.push((name, path));
+                collect_mod_declarations(body, source, inline, declarations);
+                inline.pop();
+            }
+            None => declarations.push(ModDecl {
+                inline: inline.clone(),
+                name,
+                path,
+            }),
+        }
+    }
+}
+
+/// Value of a `#[path = "..."]` attribute on the item.
+fn path_attribute(node: Node, source: &[u8]) -> Option<String> {
+    let mut current = node.prev_sibling();
+    while let Some(sibling) = current {
+        match sibling.kind() {
+            "attribute_item" => {
+                let attribute = node_text(sibling, source)
+                    .trim_start_matches("#[")
+                    .trim_end_matches(']');
+                if let Some((key, value)) = attribute.split_once('=') {
+                    if key.trim() == "path" {
+                        return Some(value.trim().trim_matches('"').to_string());
+                    }
+                }
+            }
+            "line_comment" | "block_comment" => {}
+            _ => break,
+        }
+        current = sibling.prev_sibling();
+    }
+    None
+}
+
+struct ModuleWalker<'a> {
+    declarations: &'a HashMap<PathBuf, Vec<ModDecl>>,
+    paths: &'a mut HashMap<PathBuf, String>,
+}
+
+impl ModuleWalker<'_> {
+    /// Assigns `module` to `file` and follows its `mod` declarations, whose
+    /// files are looked up under `child_dir`.
+    fn walk_file(&mut self, file: &Path, module: String, child_dir: &Path) {
+        let Some(declarations) = self.declarations.get(file) else {
+            return;
+        };
+        if self.paths.contains_key(file) {
+            return;
+        }
+        self.paths.insert(file.to_path_buf(), module.clone());
+        let file_dir = file.parent().unwrap_or(child_dir);
+
+        for declaration in declarations {
+            let mut dir = child_dir.to_path_buf();
+            let mut child_module = module.clone();
+            for (name, path) in &declaration.inline {
+                dir.push(path.as_deref().unwrap_or(name));
+                child_module = join_module(&child_module, name);
+            }
+            let child_module = join_module(&child_module, &declaration.name);
+
+            let (child_file, grandchild_dir) = match &declaration.path {
+                Some(path) => {
+                    let base = if declaration.inline.is_empty() {
+                        file_dir
+                    } else {
+                        &dir
+                    };
+                    let child_file = normalize(&base.join(path));
+                    let grandchild_dir = child_file.parent().unwrap_or(base).to_path_buf();
+                    (child_file, grandchild_dir)
+                }
+                None => {
+                    let nested = dir.join(&declaration.name);
+                    let flat = dir.join(format!("{}.rs", declaration.name));
+                    let child_file = if flat.is_file() {
+                        flat
+                    } else {
+                        nested.join("mod.rs")
+                    };
+                    (child_file, nested)
+                }
+            };
+            self.walk_file(&child_file, child_module, &grandchild_dir);
+        }
+    }
+}
+
+/// Resolves `.` and `..` components without touching the file system.
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
@@ -1,12 +1,25 @@
-use crate::language::tree_sitter_language;
-use crate::model::{CallEdge, Language, ParsedFile, Span, Symbol, SymbolKind};
+use crate::entrypoint::{
+    detectors_for, is_function_node, tauri_invoke_command, EntrypointDetector, BINDINGS,
+};
+use crate::language::LanguageAnalyzer;
+use crate::model::{
+    BindingExport, CallEdge, CallKind, Diagnostic, DiagnosticKind, EntrypointHint, EntrypointKind,
+    ImportBinding, Language, Modifier, Parameter, ParsedFile, Span, Symbol, SymbolKind,
+};
+use crate::tags::{Tag, TagKind, TagQuery};
 use anyhow::{anyhow, Result};
 use sha2::{Digest, Sha256};
-use std::path::Path;
-use tree_sitter::{Node, Parser};
+use std::collections::HashMap;
+use std::ops::Range;
+use tree_sitter::{Node, Parser, Point};
 
+/// Name of the per-file pseudo-symbol that owns top-level calls.
+const MODULE_INIT_NAME: &str = "{init}";
+
+/// How a container scopes the symbols below it: functions inside a `Type`
+/// or `Impl` container are methods.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ContainerKind {
+pub enum ContainerKind {
     Module,
     Namespace,
     Type,
@@ -19,62 +32,232 @@ struct Container {
     kind: ContainerKind,
 }
 
-struct ParseState<'a> {
+/// A function-like symbol currently being walked; calls are attributed to
+/// the innermost one.
+#[derive(Debug, Clone)]
+struct FunctionScope {
+    id: String,
+    name: String,
+    fq_name: String,
+}
+
+/// The state of a file being parsed: the enclosing containers and
+/// functions, and everything extracted so far.
+pub struct ParseContext<'a> {
     source: &'a [u8],
     file: String,
     module_path: String,
     language: Language,
+    analyzer: &'a dyn LanguageAnalyzer,
     symbols: Vec<Symbol>,
     calls: Vec<CallEdge>,
     containers: Vec<Container>,
-    functions: Vec<String>,
+    functions: Vec<FunctionScope>,
+    /// Span of the whole file, used for the module-init pseudo-symbol.
+    file_span: Span,
+    module_init: Option<String>,
+    entrypoint_hints: Vec<EntrypointHint>,
+    imports: Vec<ImportBinding>,
+    /// Inline handlers found by entrypoint detectors, keyed by node id and
+    /// marked once their closure symbol is created.
+    pending_handlers: Vec<(usize, &'static dyn EntrypointDetector)>,
+    binding_exports: Vec<BindingExport>,
+    /// Nodes tagged by the language's tags query, by node id.
+    tags: HashMap<usize, Tag>,
+    /// Whether only tagged nodes are definitions and calls.
+    tags_exclusive: bool,
+    /// Names exported apart from their declaration, such as `run` in
+    /// CommonJS `module.exports = { run }`; the top-level symbols with these
+    /// names are marked exported once the file is walked.
+    exported_names: Vec<String>,
 }
 
-pub fn analyze_file(path: &Path, root: &Path, language: Language) -> Result<ParsedFile> {
-    let source = std::fs::read(path)?;
-    let source_text = String::from_utf8_lossy(&source);
+/// Extracts symbols and calls from the contents of one file, recording
+/// `relative_path` as their file; `module_path` prefixes the fully qualified
+/// names of its symbols. Java and Kotlin names use the file's `package`
+/// declaration instead, and C and C++ names their namespaces. With a tags
+/// query, only the nodes it tags are definitions and calls. With `ranges`,
+/// only those byte ranges are parsed, as one document, keeping the file's
+/// positions; they are offsets into the source with invalid UTF-8 replaced,
+/// as by [`String::from_utf8_lossy`].
+pub fn analyze_file(
+    source: &[u8],
+    relative_path: &str,
+    analyzer: &dyn LanguageAnalyzer,
+    tags: Option<&TagQuery>,
+    module_path: &str,
+    ranges: Option<&[Range<usize>]>,
+) -> Result<ParsedFile> {
+    let mut diagnostics = Vec::new();
+    if let Err(err) = std::str::from_utf8(source) {
+        let start = point_at(source, err.valid_up_to());
+        diagnostics.push(Diagnostic {
+            file: relative_path.to_string(),
+            kind: DiagnosticKind::Encoding,
+            message: "Not valid UTF-8; invalid bytes were replaced".to_string(),
+            span: Some(span_from_points(start, start)),
+        });
+    }
+    let source_text = String::from_utf8_lossy(source);
 
     let mut parser = Parser::new();
     parser
-        .set_language(&tree_sitter_language(language))
+        .set_language(&analyzer.grammar())
         .map_err(|_| anyhow!("Failed to load Tree-sitter language"))?;
+    if let Some(ranges) = ranges {
+        let ranges = included_ranges(source_text.as_bytes(), ranges);
+        if ranges.is_empty() {
+            // No ranges would mean the whole file.
+            return Ok(ParsedFile {
+                symbols: Vec::new(),
+                calls: Vec::new(),
+                entrypoint_hints: Vec::new(),
+                imports: Vec::new(),
+                binding_exports: Vec::new(),
+                diagnostics,
+                mod_declarations: Vec::new(),
+            });
+        }
+        parser
+            .set_included_ranges(&ranges)
+            .map_err(|_| anyhow!("Invalid embedded code ranges"))?;
+    }
 
     let tree = parser
         .parse(source_text.as_ref(), None)
         .ok_or_else(|| anyhow!("Failed to parse file"))?;
 
-    let relative_path = path
-        .strip_prefix(root)
-        .unwrap_or(path)
-        .to_string_lossy()
-        .to_string();
-
-    let module_path = module_path_from_file(path, root);
-
-    let mut state = ParseState {
+    let mut state = ParseContext {
         source: source_text.as_bytes(),
-        file: relative_path,
-        module_path,
-        language,
+        file: relative_path.to_string(),
+        module_path: if analyzer.names_from_source() {
+            String::new()
+        } else {
+            module_path.to_string()
+        },
+        language: analyzer.language(),
+        analyzer,
         symbols: Vec::new(),
         calls: Vec::new(),
         containers: Vec::new(),
         functions: Vec::new(),
+        file_span: span_from_node(tree.root_node()),
+        module_init: None,
+        entrypoint_hints: Vec::new(),
+        imports: Vec::new(),
+        pending_handlers: Vec::new(),
+        binding_exports: Vec::new(),
+        tags: tags
+            .map(|query| query.tags(tree.root_node(), source_text.as_bytes()))
+            .unwrap_or_default(),
+        tags_exclusive: tags.is_some_and(|query| query.exclusive),
+        exported_names: Vec::new(),
     };
 
     let root_node = tree.root_node();
+    syntax_diagnostics(root_node, relative_path, &mut diagnostics);
     walk_node(root_node, &mut state);
+    for symbol in &mut state.symbols {
+        if symbol.container.is_none()
+            && symbol.kind != SymbolKind::Closure
+            && state.exported_names.contains(&symbol.name)
+        {
+            symbol.is_exported = true;
+            symbol.mark_entrypoint(EntrypointKind::PublicApi, "exported");
+        }
+    }
 
     Ok(ParsedFile {
         symbols: state.symbols,
         calls: state.calls,
+        entrypoint_hints: state.entrypoint_hints,
+        imports: state.imports,
+        binding_exports: state.binding_exports,
+        diagnostics,
+        mod_declarations: Vec::new(),
     })
 }
 
-fn walk_node(node: Node, state: &mut ParseState<'_>) {
-    if let Some(container_info) = container_info(node, state) {
-        if let Some(symbol) = container_info.symbol {
-            state.symbols.push(symbol.clone());
+/// Records the outermost `ERROR` and `MISSING` nodes under `node`.
+fn syntax_diagnostics(node: Node, file: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if !node.has_error() {
+        return;
+    }
+    let message = if node.is_error() {
+        "Syntax error".to_string()
+    } else if node.is_missing() {
+        format!("Missing `{}`", node.kind())
+    } else {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            syntax_diagnostics(child, file, diagnostics);
+        }
+        return;
+    };
+    diagnostics.push(Diagnostic {
+        file: file.to_string(),
+        kind: DiagnosticKind::SyntaxError,
+        message,
+        span: Some(span_from_node(node)),
+    });
+}
+
+/// The row and column of a byte offset within `source`.
+fn point_at(source: &[u8], offset: usize) -> Point {
+    let before = &source[..offset.min(source.len())];
+    let row = before.iter().filter(|byte| **byte == b'\n').count();
+    let line_start = before
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |index| index + 1);
+    Point::new(row, before.len() - line_start)
+}
+
+/// The span of a byte range within `source`.
+pub(crate) fn span_from_range(source: &[u8], range: &Range<usize>) -> Span {
+    span_from_points(point_at(source, range.start), point_at(source, range.end))
+}
+
+/// Tree-sitter ranges of the non-empty, non-overlapping byte ranges within
+/// `source`.
+fn included_ranges(source: &[u8], ranges: &[Range<usize>]) -> Vec<tree_sitter::Range> {
+    let point = |offset: usize| point_at(source, offset);
+    let mut included: Vec<tree_sitter::Range> = Vec::new();
+    for range in ranges {
+        let start = range.start.min(source.len());
+        let end = range.end.min(source.len());
+        if start >= end || included.last().is_some_and(|last| last.end_byte > start) {
+            continue;
+        }
+        included.push(tree_sitter::Range {
+            start_byte: start,
+            end_byte: end,
+            start_point: point(start),
+            end_point: point(end),
+        });
+    }
+    included
+}
+
+fn walk_node(node: Node, state: &mut ParseContext<'_>) {
+    let analyzer = state.analyzer;
+    let tag = state.tags.remove(&node.id());
+    let (definition, call) = match &tag {
+        Some(tag) => (tag.kind.is_definition(), !tag.kind.is_definition()),
+        None => (!state.tags_exclusive, !state.tags_exclusive),
+    };
+
+    let container_info = definition
+        .then(|| {
+            analyzer
+                .container(node, state)
+                .or_else(|| tag_container(node, state, tag.as_ref()?))
+        })
+        .flatten();
+    if let Some(container_info) = container_info {
+        if let Some(mut symbol) = container_info.symbol {
+            analyzer.describe(node, state.source, &mut symbol);
+            state.symbols.push(symbol);
         }
         state.containers.push(Container {
             name: container_info.name,
@@ -89,10 +272,31 @@ fn walk_node(node: Node, state: &mut ParseState<'_>) {
         return;
     }
 
-    if let Some(symbol) = function_symbol(node, state) {
-        let function_id = symbol.id.clone();
+    let symbol = definition
+        .then(|| {
+            analyzer
+                .function(node, state)
+                .or_else(|| tag_symbol(node, state, tag.as_ref()?))
+        })
+        .flatten();
+    if let Some(mut symbol) = symbol {
+        analyzer.describe(node, state.source, &mut symbol);
+        apply_entrypoint_rules(node, state, &mut symbol);
+        record_binding_export(node, state, &symbol);
+        if symbol.kind == SymbolKind::Closure {
+            // Link the enclosing function to the closure so calls made inside
+            // the closure stay reachable from it in the graph.
+            let span = span_from_node(node);
+            let mut edge = new_call_edge(state, symbol.name.clone(), CallKind::Defines, span);
+            edge.callee_id = Some(symbol.id.clone());
+            state.calls.push(edge);
+        }
+        state.functions.push(FunctionScope {
+            id: symbol.id.clone(),
+            name: symbol.name.clone(),
+            fq_name: symbol.fq_name.clone(),
+        });
         state.symbols.push(symbol);
-        state.functions.push(function_id);
 
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
@@ -102,8 +306,20 @@ fn walk_node(node: Node, state: &mut ParseState<'_>) {
         return;
     }
 
-    if let Some(call) = call_edge(node, state) {
-        state.calls.push(call);
+    let call = call
+        .then(|| {
+            analyzer
+                .call(node, state.source)
+                .or_else(|| tag_call(tag.as_ref()?))
+        })
+        .flatten();
+    if let Some((callee_name, kind)) = call {
+        state.add_call(callee_name, kind, node);
+        collect_entrypoint_handlers(node, state);
+    }
+
+    if !analyzer.visit(node, state) {
+        return;
     }
 
     let mut cursor = node.walk();
@@ -112,22 +328,431 @@ fn walk_node(node: Node, state: &mut ParseState<'_>) {
     }
 }
 
-struct ContainerInfo {
-    name: String,
-    kind: ContainerKind,
-    symbol: Option<Symbol>,
+/// Marks definitions matched by an entrypoint detector, and inline handlers
+/// registered by an earlier call.
+fn apply_entrypoint_rules(node: Node, state: &mut ParseContext<'_>, symbol: &mut Symbol) {
+    for detector in detectors_for(state.language) {
+        if detector.matches_definition(node, state.source) {
+            symbol.mark_entrypoint(detector.kind(), detector.rule());
+        }
+    }
+    state.pending_handlers.retain(|(id, detector)| {
+        if *id != node.id() {
+            return true;
+        }
+        symbol.mark_entrypoint(detector.kind(), detector.rule());
+        false
+    });
+}
+
+/// Records the JavaScript-facing name of a Rust function exported by a
+/// binding framework such as Tauri or wasm-bindgen.
+fn record_binding_export(node: Node, state: &mut ParseContext<'_>, symbol: &Symbol) {
+    let binding = BINDINGS.iter().find(|binding| {
+        symbol
+            .entrypoint_rules
+            .iter()
+            .any(|rule| rule == binding.rule())
+    });
+    if let Some(binding) = binding {
+        state.binding_exports.push(BindingExport {
+            symbol_id: symbol.id.clone(),
+            name: binding.exported_name(node, state.source, &symbol.name),
+            rule: binding.rule().to_string(),
+        });
+    }
+}
+
+/// Records handlers registered by a call such as `app.get("/", handler)`:
+/// inline functions are marked when walked, named ones become hints.
+fn collect_entrypoint_handlers(node: Node, state: &mut ParseContext<'_>) {
+    for detector in detectors_for(state.language) {
+        for handler in detector.handlers(node, state.source) {
+            if is_function_node(handler) {
+                state.pending_handlers.push((handler.id(), detector));
+                continue;
+            }
+            let name = normalize_call_name(node_text(handler, state.source));
+            state.entrypoint_hints.push(EntrypointHint {
+                name,
+                kind: detector.kind(),
+                rule: detector.rule().to_string(),
+                file: state.file.clone(),
+            });
+        }
+    }
+}
+
+/// The container a project query pattern tags, for a node the language's
+/// analyzer does not recognise.
+fn tag_container(node: Node, state: &ParseContext<'_>, tag: &Tag) -> Option<ContainerInfo> {
+    let TagKind::Container(kind, symbol_kind) = &tag.kind else {
+        return None;
+    };
+    if !tag.standalone {
+        return None;
+    }
+    let Some(symbol_kind) = symbol_kind else {
+        let name = tag.name.clone().unwrap_or_else(|| "impl".to_string());
+        return Some(ContainerInfo {
+            name,
+            kind: *kind,
+            symbol: None,
+        });
+    };
+    let name = tag.name.as_deref()?;
+    Some(ContainerInfo {
+        name: name.to_string(),
+        kind: *kind,
+        symbol: Some(new_symbol(state, name, symbol_kind.clone(), node, false)),
+    })
+}
+
+/// The function-like symbol a project query pattern tags, for a node the
+/// language's analyzer does not recognise.
+fn tag_symbol(node: Node, state: &ParseContext<'_>, tag: &Tag) -> Option<Symbol> {
+    if !tag.standalone {
+        return None;
+    }
+    match &tag.kind {
+        TagKind::Closure => Some(closure_symbol(node, state)),
+        TagKind::Symbol(kind) => {
+            let kind = match kind {
+                SymbolKind::Function if in_method_context(&state.containers) => SymbolKind::Method,
+                kind => kind.clone(),
+            };
+            Some(new_symbol(state, tag.name.as_deref()?, kind, node, false))
+        }
+        _ => None,
+    }
+}
+
+/// The call a project query pattern tags, named by its `@name` capture.
+fn tag_call(tag: &Tag) -> Option<(String, CallKind)> {
+    match tag.kind {
+        TagKind::Call(kind) if tag.standalone => {
+            Some((normalize_call_name(tag.name.as_deref()?), kind))
+        }
+        _ => None,
+    }
+}
+
+/// A node that scopes the symbols below it, and the symbol it defines, if
+/// any (a Rust `impl` block defines none).
+pub struct ContainerInfo {
+    pub name: String,
+    pub kind: ContainerKind,
+    pub symbol: Option<Symbol>,
+}
+
+impl<'a> ParseContext<'a> {
+    pub fn source(&self) -> &'a [u8] {
+        self.source
+    }
+
+    /// Root-relative path of the file.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// A symbol named `name` in the current containers. `main` and exported
+    /// symbols are marked as entrypoints.
+    pub fn new_symbol(
+        &self,
+        name: &str,
+        kind: SymbolKind,
+        node: Node,
+        is_exported: bool,
+    ) -> Symbol {
+        new_symbol(self, name, kind, node, is_exported)
+    }
+
+    /// A symbol for an anonymous function, nested under the enclosing one.
+    pub fn closure_symbol(&self, node: Node) -> Symbol {
+        closure_symbol(node, self)
+    }
+
+    /// Whether functions defined here are methods of a type.
+    pub fn in_method_context(&self) -> bool {
+        in_method_context(&self.containers)
+    }
+
+    /// Whether the walk is inside a function-like symbol.
+    pub fn in_function(&self) -> bool {
+        !self.functions.is_empty()
+    }
+
+    pub fn add_import(&mut self, binding: ImportBinding) {
+        self.imports.push(binding);
+    }
+
+    /// Records a call at `node`, made by the innermost function or by the
+    /// file's module-init pseudo-symbol.
+    pub fn add_call(&mut self, callee_name: String, kind: CallKind, node: Node) {
+        let edge = new_call_edge(self, callee_name, kind, span_from_node(node));
+        self.calls.push(edge);
+    }
+}
+
+pub(crate) struct RustAnalyzer;
+
+impl LanguageAnalyzer for RustAnalyzer {
+    fn language(&self) -> Language {
+        Language::Rust
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["rs"]
+    }
+
+    fn grammar(&self) -> tree_sitter::Language {
+        tree_sitter_rust::language()
+    }
+
+    fn tags_query(&self) -> Option<&'static str> {
+        Some(include_str!("../queries/rust.scm"))
+    }
+
+    fn container(&self, node: Node, cx: &mut ParseContext<'_>) -> Option<ContainerInfo> {
+        rust_container_info(node, cx)
+    }
+
+    fn function(&self, node: Node, cx: &mut ParseContext<'_>) -> Option<Symbol> {
+        rust_function_symbol(node, cx)
+    }
+
+    fn call(&self, node: Node, source: &[u8]) -> Option<(String, CallKind)> {
+        rust_call_name(node, source)
+    }
+
+    fn describe(&self, node: Node, source: &[u8], symbol: &mut Symbol) {
+        rust_describe_symbol(node, source, symbol);
+    }
+
+    fn visit(&self, node: Node, cx: &mut ParseContext<'_>) -> bool {
+        if node.kind() == "use_declaration" {
+            if let Some(argument) = node.child_by_field_name("argument") {
+                rust_use_bindings(argument, "", cx.source, &mut cx.imports);
+            }
+        }
+        if is_macro_token_tree(node) {
+            rust_token_tree_calls(node, cx);
+            return false;
+        }
+        true
+    }
+}
+
+/// JavaScript, TypeScript or TSX, which share one set of node kinds.
+pub(crate) struct JsAnalyzer(pub(crate) Language);
+
+impl LanguageAnalyzer for JsAnalyzer {
+    fn language(&self) -> Language {
+        self.0
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        match self.0 {
+            Language::TypeScript => &["ts"],
+            Language::Tsx => &["tsx"],
+            _ => &["js", "jsx", "cjs", "mjs"],
+        }
+    }
+
+    fn grammar(&self) -> tree_sitter::Language {
+        match self.0 {
+            Language::TypeScript => tree_sitter_typescript::language_typescript(),
+            Language::Tsx => tree_sitter_typescript::language_tsx(),
+            _ => tree_sitter_javascript::language(),
+        }
+    }
+
+    fn tags_query(&self) -> Option<&'static str> {
+        Some(match self.0 {
+            Language::TypeScript => concat!(
+                include_str!("../queries/javascript.scm"),
+                include_str!("../queries/typescript.scm")
+            ),
+            Language::Tsx => concat!(
+                include_str!("../queries/javascript.scm"),
+                include_str!("../queries/typescript.scm"),
+                include_str!("../queries/jsx.scm")
+            ),
+            _ => concat!(
+                include_str!("../queries/javascript.scm"),
+                include_str!("../queries/javascript-only.scm"),
+                include_str!("../queries/jsx.scm")
+            ),
+        })
+    }
+
+    /// Importing a module runs its top-level code; Rust has no equivalent of
+    /// module-level execution, and JVM initializers only run once something
+    /// uses the class.
+    fn module_init_is_entrypoint(&self) -> bool {
+        true
+    }
+
+    fn container(&self, node: Node, cx: &mut ParseContext<'_>) -> Option<ContainerInfo> {
+        js_container_info(node, cx)
+    }
+
+    fn function(&self, node: Node, cx: &mut ParseContext<'_>) -> Option<Symbol> {
+        js_function_symbol(node, cx)
+    }
+
+    fn call(&self, node: Node, source: &[u8]) -> Option<(String, CallKind)> {
+        // `require("./x")` is an import, recorded by `visit`.
+        if js_require_specifier(node, source).is_some() {
+            return None;
+        }
+        js_call_name(node, source)
+            .map(|name| (name, CallKind::Call))
+            .or_else(|| jsx_component_name(node, source).map(|name| (name, CallKind::Renders)))
+    }
+
+    fn describe(&self, node: Node, source: &[u8], symbol: &mut Symbol) {
+        js_describe_symbol(node, source, symbol);
+    }
+
+    fn visit(&self, node: Node, cx: &mut ParseContext<'_>) -> bool {
+        cx.imports.extend(js_import_bindings(node, cx.source));
+        cx.exported_names
+            .extend(js_commonjs_export_names(node, cx.source));
+        if let Some(command) = tauri_invoke_command(node, cx.source) {
+            cx.add_call(command, CallKind::Ffi, node);
+        }
+        true
+    }
+}
+
+pub(crate) struct JavaAnalyzer;
+
+impl LanguageAnalyzer for JavaAnalyzer {
+    fn language(&self) -> Language {
+        Language::Java
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["java"]
+    }
+
+    fn grammar(&self) -> tree_sitter::Language {
+        tree_sitter_java::language()
+    }
+
+    fn names_from_source(&self) -> bool {
+        true
+    }
+
+    fn container(&self, node: Node, cx: &mut ParseContext<'_>) -> Option<ContainerInfo> {
+        java_container_info(node, cx)
+    }
+
+    fn function(&self, node: Node, cx: &mut ParseContext<'_>) -> Option<Symbol> {
+        java_function_symbol(node, cx)
+    }
+
+    fn call(&self, node: Node, source: &[u8]) -> Option<(String, CallKind)> {
+        java_call_name(node, source).map(|name| (name, CallKind::Call))
+    }
+
+    fn describe(&self, node: Node, source: &[u8], symbol: &mut Symbol) {
+        java_describe_symbol(node, source, symbol);
+    }
+
+    fn visit(&self, node: Node, cx: &mut ParseContext<'_>) -> bool {
+        jvm_package_declaration(node, cx);
+        true
+    }
+}
+
+pub(crate) struct KotlinAnalyzer;
+
+impl LanguageAnalyzer for KotlinAnalyzer {
+    fn language(&self) -> Language {
+        Language::Kotlin
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["kt", "kts"]
+    }
+
+    fn grammar(&self) -> tree_sitter::Language {
+        tree_sitter_kotlin::language()
+    }
+
+    fn names_from_source(&self) -> bool {
+        true
+    }
+
+    fn container(&self, node: Node, cx: &mut ParseContext<'_>) -> Option<ContainerInfo> {
+        kotlin_container_info(node, cx)
+    }
+
+    fn function(&self, node: Node, cx: &mut ParseContext<'_>) -> Option<Symbol> {
+        kotlin_function_symbol(node, cx)
+    }
+
+    fn call(&self, node: Node, source: &[u8]) -> Option<(String, CallKind)> {
+        kotlin_call_name(node, source).map(|name| (name, CallKind::Call))
+    }
+
+    fn describe(&self, node: Node, source: &[u8], symbol: &mut Symbol) {
+        kotlin_describe_symbol(node, source, symbol);
+    }
+
+    fn visit(&self, node: Node, cx: &mut ParseContext<'_>) -> bool {
+        jvm_package_declaration(node, cx);
+        true
+    }
 }
 
-fn container_info(node: Node, state: &mut ParseState<'_>) -> Option<ContainerInfo> {
-    match state.language {
-        Language::Rust => rust_container_info(node, state),
-        Language::JavaScript | Language::TypeScript | Language::Tsx => {
-            js_container_info(node, state)
+/// C or C++. Headers are parsed as C++, whose grammar also covers C
+/// declarations.
+pub(crate) struct CAnalyzer(pub(crate) Language);
+
+impl LanguageAnalyzer for CAnalyzer {
+    fn language(&self) -> Language {
+        self.0
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        match self.0 {
+            Language::C => &["c"],
+            _ => &["h", "cpp", "cc", "cxx", "hpp", "hh", "hxx"],
+        }
+    }
+
+    fn grammar(&self) -> tree_sitter::Language {
+        match self.0 {
+            Language::C => tree_sitter_c::language(),
+            _ => tree_sitter_cpp::language(),
         }
     }
+
+    fn names_from_source(&self) -> bool {
+        true
+    }
+
+    fn container(&self, node: Node, cx: &mut ParseContext<'_>) -> Option<ContainerInfo> {
+        c_container_info(node, cx)
+    }
+
+    fn function(&self, node: Node, cx: &mut ParseContext<'_>) -> Option<Symbol> {
+        c_function_symbol(node, cx)
+    }
+
+    fn call(&self, node: Node, source: &[u8]) -> Option<(String, CallKind)> {
+        c_call_name(node, source).map(|name| (name, CallKind::Call))
+    }
+
+    fn describe(&self, node: Node, source: &[u8], symbol: &mut Symbol) {
+        c_describe_symbol(node, source, symbol);
+    }
 }
 
-fn rust_container_info(node: Node, state: &mut ParseState<'_>) -> Option<ContainerInfo> {
+fn rust_container_info(node: Node, state: &mut ParseContext<'_>) -> Option<ContainerInfo> {
     match node.kind() {
         "mod_item" => {
             let name = node_text(node.child_by_field_name("name")?, state.source);
@@ -182,9 +807,19 @@ fn rust_container_info(node: Node, state: &mut ParseState<'_>) -> Option<Contain
     }
 }
 
-fn js_container_info(node: Node, state: &mut ParseState<'_>) -> Option<ContainerInfo> {
+fn js_container_info(node: Node, state: &mut ParseContext<'_>) -> Option<ContainerInfo> {
+    let mut info = js_container(node, state)?;
+    if let Some(symbol) = info.symbol.as_mut() {
+        if js_is_ambient(node, &state.file) {
+            mark_declaration(symbol);
+        }
+    }
+    Some(info)
+}
+
+fn js_container(node: Node, state: &mut ParseContext<'_>) -> Option<ContainerInfo> {
     match node.kind() {
-        "class_declaration" => {
+        "class_declaration" | "abstract_class_declaration" => {
             let name = node_text(node.child_by_field_name("name")?, state.source);
             let symbol = new_symbol(state, name, SymbolKind::Class, node, js_is_exported(node));
             Some(ContainerInfo {
@@ -208,8 +843,14 @@ fn js_container_info(node: Node, state: &mut ParseState<'_>) -> Option<Container
                 symbol: Some(symbol),
             })
         }
-        "module_declaration" => {
-            let name = node_text(node.child_by_field_name("name")?, state.source);
+        // `namespace Util {}`, `declare module "lib" {}`.
+        "internal_module" | "module" => {
+            let name = node.child_by_field_name("name")?;
+            let name = match name.kind() {
+                "string" => name.named_child(0).unwrap_or(name),
+                _ => name,
+            };
+            let name = node_text(name, state.source);
             let symbol = new_symbol(
                 state,
                 name,
@@ -227,16 +868,83 @@ fn js_container_info(node: Node, state: &mut ParseState<'_>) -> Option<Container
     }
 }
 
-fn function_symbol(node: Node, state: &mut ParseState<'_>) -> Option<Symbol> {
-    match state.language {
-        Language::Rust => rust_function_symbol(node, state),
-        Language::JavaScript | Language::TypeScript | Language::Tsx => {
-            js_function_symbol(node, state)
+fn java_container_info(node: Node, state: &mut ParseContext<'_>) -> Option<ContainerInfo> {
+    let kind = match node.kind() {
+        "class_declaration" | "record_declaration" => SymbolKind::Class,
+        "interface_declaration" | "annotation_type_declaration" => SymbolKind::Interface,
+        "enum_declaration" => SymbolKind::Enum,
+        _ => return None,
+    };
+    let name = node_text(node.child_by_field_name("name")?, state.source);
+    let symbol = new_symbol(state, name, kind, node, java_is_exported(node));
+    Some(ContainerInfo {
+        name: name.to_string(),
+        kind: ContainerKind::Type,
+        symbol: Some(symbol),
+    })
+}
+
+fn kotlin_container_info(node: Node, state: &mut ParseContext<'_>) -> Option<ContainerInfo> {
+    let kind = match node.kind() {
+        "class_declaration" if has_child_kind(node, "interface") => SymbolKind::Interface,
+        "class_declaration" if has_child_kind(node, "enum") => SymbolKind::Enum,
+        "class_declaration" | "object_declaration" => SymbolKind::Class,
+        _ => return None,
+    };
+    let name = node_text(child_of_kind(node, "type_identifier")?, state.source);
+    let is_exported = kotlin_is_exported(node, state);
+    let symbol = new_symbol(state, name, kind, node, is_exported);
+    Some(ContainerInfo {
+        name: name.to_string(),
+        kind: ContainerKind::Type,
+        symbol: Some(symbol),
+    })
+}
+
+fn c_container_info(node: Node, state: &mut ParseContext<'_>) -> Option<ContainerInfo> {
+    match node.kind() {
+        // Anonymous namespaces add no name; `c_is_exported` hides their contents.
+        "namespace_definition" => {
+            let name = node_text(node.child_by_field_name("name")?, state.source);
+            let symbol = new_symbol(state, name, SymbolKind::Namespace, node, false);
+            Some(ContainerInfo {
+                name: name.to_string(),
+                kind: ContainerKind::Namespace,
+                symbol: Some(symbol),
+            })
         }
+        "class_specifier" | "struct_specifier" | "union_specifier" => {
+            // `struct Point p;` only refers to the type.
+            node.child_by_field_name("body")?;
+            let kind = match node.kind() {
+                "class_specifier" => SymbolKind::Class,
+                "struct_specifier" => SymbolKind::Struct,
+                _ => SymbolKind::Union,
+            };
+            let name = c_type_name(node, state.source)?;
+            let symbol = new_symbol(state, name, kind, node, c_is_exported(node, state.source));
+            Some(ContainerInfo {
+                name: name.to_string(),
+                kind: ContainerKind::Type,
+                symbol: Some(symbol),
+            })
+        }
+        _ => None,
     }
 }
 
-fn rust_function_symbol(node: Node, state: &mut ParseState<'_>) -> Option<Symbol> {
+/// Name of a class, struct, union or enum specifier; an anonymous one in
+/// `typedef struct { ... } Point;` is named by its typedef.
+fn c_type_name<'a>(node: Node, source: &'a [u8]) -> Option<&'a str> {
+    let name = node.child_by_field_name("name").or_else(|| {
+        node.parent()
+            .filter(|parent| parent.kind() == "type_definition")?
+            .child_by_field_name("declarator")
+    })?;
+    Some(node_text(name, source))
+}
+
+fn rust_function_symbol(node: Node, state: &mut ParseContext<'_>) -> Option<Symbol> {
     match node.kind() {
         "function_item" => {
             let name = node_text(node.child_by_field_name("name")?, state.source);
@@ -253,6 +961,19 @@ fn rust_function_symbol(node: Node, state: &mut ParseState<'_>) -> Option<Symbol
                 rust_is_exported(node, state.source),
             ))
         }
+        // Functions in an `extern` block are foreign declarations.
+        "function_signature_item" if rust_in_extern_block(node) => {
+            let name = node_text(node.child_by_field_name("name")?, state.source);
+            let mut symbol = new_symbol(
+                state,
+                name,
+                SymbolKind::Function,
+                node,
+                rust_is_exported(node, state.source),
+            );
+            mark_declaration(&mut symbol);
+            Some(symbol)
+        }
         "function_signature_item" => {
             let name = node_text(node.child_by_field_name("name")?, state.source);
             Some(new_symbol(
@@ -283,27 +1004,118 @@ fn rust_function_symbol(node: Node, state: &mut ParseState<'_>) -> Option<Symbol
                 rust_is_exported(node, state.source),
             ))
         }
-        _ => None,
-    }
-}
-
-fn js_function_symbol(node: Node, state: &mut ParseState<'_>) -> Option<Symbol> {
-    match node.kind() {
-        "function_declaration" => {
+        "const_item" | "static_item" | "type_item" | "associated_type" | "union_item" => {
             let name = node_text(node.child_by_field_name("name")?, state.source);
-            let kind = if in_method_context(&state.containers) {
-                SymbolKind::Method
-            } else {
-                SymbolKind::Function
+            let kind = match node.kind() {
+                "const_item" => SymbolKind::Constant,
+                "static_item" => SymbolKind::Static,
+                "union_item" => SymbolKind::Union,
+                _ => SymbolKind::TypeAlias,
             };
-            Some(new_symbol(state, name, kind, node, js_is_exported(node)))
-        }
-        "method_definition" => {
-            let name = node_text(node.child_by_field_name("name")?, state.source);
             Some(new_symbol(
                 state,
                 name,
-                SymbolKind::Method,
+                kind,
+                node,
+                rust_is_exported(node, state.source),
+            ))
+        }
+        "closure_expression" => Some(closure_symbol(node, state)),
+        "macro_definition" => {
+            let name = node_text(node.child_by_field_name("name")?, state.source);
+            Some(new_symbol(
+                state,
+                name,
+                SymbolKind::Macro,
+                node,
+                rust_has_attribute(node, state.source, "macro_export"),
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn js_function_symbol(node: Node, state: &mut ParseContext<'_>) -> Option<Symbol> {
+    let mut symbol = js_function_like_symbol(node, state)?;
+    let is_signature = matches!(
+        node.kind(),
+        "function_signature" | "method_signature" | "abstract_method_signature"
+    );
+    if symbol.kind != SymbolKind::Closure && (is_signature || js_is_ambient(node, &state.file)) {
+        mark_declaration(&mut symbol);
+    }
+    Some(symbol)
+}
+
+fn js_function_like_symbol(node: Node, state: &mut ParseContext<'_>) -> Option<Symbol> {
+    match node.kind() {
+        "function_declaration" => {
+            let name = node_text(node.child_by_field_name("name")?, state.source);
+            let kind = if in_method_context(&state.containers) {
+                SymbolKind::Method
+            } else {
+                SymbolKind::Function
+            };
+            Some(new_symbol(state, name, kind, node, js_is_exported(node)))
+        }
+        "method_definition" => {
+            let name = node_text(node.child_by_field_name("name")?, state.source);
+            let is_exported = js_is_exported(node) || js_in_commonjs_exports(node, state.source);
+            Some(new_symbol(
+                state,
+                name,
+                SymbolKind::Method,
+                node,
+                is_exported,
+            ))
+        }
+        // Properties of module-level object literals and class fields
+        // holding a function: `{ save: () => {} }`, `class A { handle = () => {} }`.
+        "pair" | "field_definition" | "public_field_definition" => {
+            if !js_is_function_value(node.child_by_field_name("value")?)
+                || (node.kind() == "pair" && !js_in_module_object(node, state.source))
+            {
+                return None;
+            }
+            let name = js_property_name(node)?;
+            let name = node_text(name, state.source);
+            let is_exported = js_is_exported(node) || js_in_commonjs_exports(node, state.source);
+            Some(new_symbol(
+                state,
+                name,
+                SymbolKind::Method,
+                node,
+                is_exported,
+            ))
+        }
+        // `exports.run = function () {}`, `module.exports.run = () => {}`.
+        "assignment_expression" => {
+            if !js_is_function_value(node.child_by_field_name("right")?) {
+                return None;
+            }
+            let name = js_commonjs_export_target(node, state.source)?;
+            let name = node_text(name, state.source);
+            Some(new_symbol(state, name, SymbolKind::Function, node, true))
+        }
+        // `declare const VERSION: string;`, and `export const x: T;` in a
+        // `.d.ts` file.
+        "variable_declarator"
+            if node.child_by_field_name("value").is_none()
+                && js_is_ambient(node, &state.file)
+                && node
+                    .parent()
+                    .and_then(|parent| parent.child_by_field_name("kind"))
+                    .is_some_and(|kind| kind.kind() == "const") =>
+        {
+            let name = node.child_by_field_name("name")?;
+            if name.kind() != "identifier" {
+                return None;
+            }
+            let name = node_text(name, state.source);
+            Some(new_symbol(
+                state,
+                name,
+                SymbolKind::Constant,
                 node,
                 js_is_exported(node),
             ))
@@ -337,22 +1149,298 @@ fn js_function_symbol(node: Node, state: &mut ParseState<'_>) -> Option<Symbol>
                 js_is_exported(node),
             ))
         }
+        "type_alias_declaration" => {
+            let name = node_text(node.child_by_field_name("name")?, state.source);
+            Some(new_symbol(
+                state,
+                name,
+                SymbolKind::TypeAlias,
+                node,
+                js_is_exported(node),
+            ))
+        }
+        // Overload signatures and `declare function`; paired with their
+        // implementation once every file is parsed.
+        "function_signature" => {
+            let name = node_text(node.child_by_field_name("name")?, state.source);
+            let kind = if in_method_context(&state.containers) {
+                SymbolKind::Method
+            } else {
+                SymbolKind::Function
+            };
+            Some(new_symbol(state, name, kind, node, js_is_exported(node)))
+        }
+        // Interface members are not symbols; in a class these are overloads
+        // and abstract methods.
+        "method_signature" | "abstract_method_signature"
+            if node
+                .parent()
+                .is_some_and(|parent| parent.kind() == "class_body") =>
+        {
+            let name = node_text(node.child_by_field_name("name")?, state.source);
+            Some(new_symbol(
+                state,
+                name,
+                SymbolKind::Method,
+                node,
+                js_is_exported(node),
+            ))
+        }
+        // Named-node check: `function` is also the keyword token's kind.
+        "arrow_function" | "function" | "function_expression" | "generator_function"
+            if node.is_named() =>
+        {
+            // `const handler = () => {}` is already named by its declarator,
+            // and `{ save: () => {} }` in a module-level object by its property.
+            let is_named_value = node.parent().is_some_and(|parent| match parent.kind() {
+                "variable_declarator" => true,
+                "pair" => {
+                    js_property_name(parent).is_some() && js_in_module_object(parent, state.source)
+                }
+                "field_definition" | "public_field_definition" => {
+                    js_property_name(parent).is_some()
+                }
+                "assignment_expression" => {
+                    js_commonjs_export_target(parent, state.source).is_some()
+                }
+                _ => false,
+            });
+            (!is_named_value).then(|| closure_symbol(node, state))
+        }
         _ => None,
     }
 }
 
-fn call_edge(node: Node, state: &mut ParseState<'_>) -> Option<CallEdge> {
-    let callee_name = match state.language {
-        Language::Rust => rust_call_name(node, state.source),
-        Language::JavaScript | Language::TypeScript | Language::Tsx => {
-            js_call_name(node, state.source)
+fn java_function_symbol(node: Node, state: &mut ParseContext<'_>) -> Option<Symbol> {
+    match node.kind() {
+        // Constructors are named after their class.
+        "method_declaration" | "constructor_declaration" | "compact_constructor_declaration" => {
+            let name = node_text(node.child_by_field_name("name")?, state.source);
+            Some(new_symbol(
+                state,
+                name,
+                SymbolKind::Method,
+                node,
+                java_is_exported(node),
+            ))
         }
-    }?;
+        "lambda_expression" => Some(closure_symbol(node, state)),
+        _ => None,
+    }
+}
 
-    let caller_id = state.functions.last()?.clone();
-    let span = span_from_node(node);
+fn kotlin_function_symbol(node: Node, state: &mut ParseContext<'_>) -> Option<Symbol> {
+    match node.kind() {
+        "function_declaration" => {
+            let name = node_text(child_of_kind(node, "simple_identifier")?, state.source);
+            let kind = if in_method_context(&state.containers) {
+                SymbolKind::Method
+            } else {
+                SymbolKind::Function
+            };
+            let is_exported = kotlin_is_exported(node, state);
+            Some(new_symbol(state, name, kind, node, is_exported))
+        }
+        "secondary_constructor" => {
+            let name = state.containers.last()?.name.clone();
+            let is_exported = kotlin_is_exported(node, state);
+            Some(new_symbol(
+                state,
+                &name,
+                SymbolKind::Method,
+                node,
+                is_exported,
+            ))
+        }
+        // `val handler = { ... }` is named by its property.
+        "property_declaration" => {
+            child_of_kind(node, "lambda_literal")
+                .or_else(|| child_of_kind(node, "anonymous_function"))?;
+            let variable = child_of_kind(node, "variable_declaration")?;
+            let name = node_text(child_of_kind(variable, "simple_identifier")?, state.source);
+            let is_exported = kotlin_is_exported(node, state);
+            Some(new_symbol(
+                state,
+                name,
+                SymbolKind::Function,
+                node,
+                is_exported,
+            ))
+        }
+        "lambda_literal" | "anonymous_function" => {
+            let is_property_value = node
+                .parent()
+                .is_some_and(|parent| parent.kind() == "property_declaration");
+            (!is_property_value).then(|| closure_symbol(node, state))
+        }
+        _ => None,
+    }
+}
+
+fn c_function_symbol(node: Node, state: &mut ParseContext<'_>) -> Option<Symbol> {
+    match node.kind() {
+        "function_definition" => c_function_like_symbol(node, state, false),
+        // Prototypes and in-class method declarations; inside function
+        // bodies declarations are local variables.
+        "declaration" | "field_declaration"
+            if state.functions.is_empty()
+                && node
+                    .parent()
+                    .is_none_or(|parent| parent.kind() != "friend_declaration") =>
+        {
+            c_function_like_symbol(node, state, true)
+        }
+        "enum_specifier" => {
+            node.child_by_field_name("body")?;
+            let name = c_type_name(node, state.source)?;
+            let is_exported = c_is_exported(node, state.source);
+            Some(new_symbol(state, name, SymbolKind::Enum, node, is_exported))
+        }
+        "lambda_expression" => Some(closure_symbol(node, state)),
+        _ => None,
+    }
+}
+
+/// A function definition or declaration. Out-of-line definitions such as
+/// `void Engine::run() {}` are methods named by the last segment, with the
+/// scope added to their fq name; they stay unexported until paired with their
+/// declaration in the class.
+fn c_function_like_symbol(
+    node: Node,
+    state: &mut ParseContext<'_>,
+    declaration: bool,
+) -> Option<Symbol> {
+    let declarator = c_function_declarator(node)?;
+    let mut name_node = declarator.child_by_field_name("declarator")?;
+    let mut scope = Vec::new();
+    while name_node.kind() == "qualified_identifier" {
+        if let Some(segment) = name_node.child_by_field_name("scope") {
+            scope.push(normalize_call_name(node_text(segment, state.source)));
+        }
+        name_node = name_node.child_by_field_name("name")?;
+    }
+    if name_node.kind() == "template_function" {
+        name_node = name_node.child_by_field_name("name")?;
+    }
+    let name = node_text(name_node, state.source);
+
+    let kind = if !scope.is_empty() || in_method_context(&state.containers) {
+        SymbolKind::Method
+    } else {
+        SymbolKind::Function
+    };
+    let is_exported = scope.is_empty() && c_is_exported(node, state.source);
+    let depth = state.containers.len();
+    state
+        .containers
+        .extend(scope.into_iter().map(|name| Container {
+            name,
+            kind: ContainerKind::Type,
+        }));
+    let mut symbol = new_symbol(state, name, kind, node, is_exported);
+    state.containers.truncate(depth);
+    if declaration {
+        mark_declaration(&mut symbol);
+    }
+    Some(symbol)
+}
+
+/// The `function_declarator` of a definition or declaration, looking through
+/// pointer and reference return types such as `char *name(...)`.
+fn c_function_declarator(node: Node) -> Option<Node> {
+    let mut current = node.child_by_field_name("declarator")?;
+    loop {
+        let next = match current.kind() {
+            "function_declarator" => {
+                let inner = current.child_by_field_name("declarator")?;
+                // `int (*pick(int k))(int)` returns a function pointer.
+                if inner.kind() != "parenthesized_declarator" {
+                    return Some(current);
+                }
+                inner
+            }
+            "pointer_declarator" | "reference_declarator" | "parenthesized_declarator" => current
+                .child_by_field_name("declarator")
+                .or_else(|| current.named_child(0))?,
+            _ => return None,
+        };
+        current = next;
+    }
+}
+
+/// Builds a symbol for an anonymous function. Its synthetic name records
+/// what it was passed to or assigned to (when known) and where it starts,
+/// e.g. `{closure:map@12:5}`, and it is nested under the enclosing function.
+fn closure_symbol(node: Node, state: &ParseContext<'_>) -> Symbol {
+    let start = node.start_position();
+    let position = format!("{}:{}", start.row + 1, start.column + 1);
+    let name = match closure_hint(node, state.source) {
+        Some(hint) => format!("{{closure:{hint}@{position}}}"),
+        None => format!("{{closure@{position}}}"),
+    };
+
+    let mut symbol = new_symbol(state, &name, SymbolKind::Closure, node, false);
+    if let Some(parent) = state.functions.last() {
+        symbol.fq_name = format!("{}::{}", parent.fq_name, name);
+        symbol.container = Some(parent.name.clone());
+        symbol.id = symbol_id(state, &symbol.kind, &symbol.fq_name, &symbol.span);
+    }
+    symbol
+}
+
+/// The callee a closure is passed to, or the property/field it is assigned to.
+fn closure_hint(node: Node, source: &[u8]) -> Option<String> {
+    let parent = node.parent()?;
+    let target = match parent.kind() {
+        "arguments" | "argument_list" => {
+            let call = parent.parent()?;
+            call.child_by_field_name("function")
+                .or_else(|| call.child_by_field_name("constructor"))
+                .or_else(|| call.child_by_field_name("name"))
+                .or_else(|| call.child_by_field_name("type"))?
+        }
+        // Kotlin arguments and trailing lambdas: `items.map { ... }`.
+        "value_argument" | "annotated_lambda" => {
+            let mut call = parent.parent()?;
+            while call.kind() != "call_expression" {
+                if !matches!(call.kind(), "value_arguments" | "call_suffix") {
+                    return None;
+                }
+                call = call.parent()?;
+            }
+            call.named_child(0)?
+        }
+        "variable_declarator" => parent.child_by_field_name("name")?,
+        "init_declarator" => parent.child_by_field_name("declarator")?,
+        "pair" => parent.child_by_field_name("key")?,
+        "assignment_expression" => parent.child_by_field_name("left")?,
+        "public_field_definition" | "field_definition" => parent
+            .child_by_field_name("name")
+            .or_else(|| parent.child_by_field_name("property"))?,
+        _ => return None,
+    };
+    let name = normalize_call_name(node_text(target, source));
+    let hint = name
+        .rsplit(['.', ':'])
+        .next()
+        .unwrap_or(&name)
+        .trim_matches(['"', '\'', '`']);
+    (!hint.is_empty()).then(|| hint.to_string())
+}
+
+fn new_call_edge(
+    state: &mut ParseContext<'_>,
+    callee_name: String,
+    kind: CallKind,
+    span: Span,
+) -> CallEdge {
+    let caller_id = match state.functions.last() {
+        Some(function) => function.id.clone(),
+        None => module_init_id(state),
+    };
     let id_seed = format!(
-        "call:{}:{}:{}:{}:{}:{}:{}:{}",
+        "call:{}:{}:{}:{}:{}:{}:{}:{}:{}",
+        call_kind_to_str(kind),
         caller_id,
         callee_name,
         state.file,
@@ -360,45 +1448,639 @@ fn call_edge(node: Node, state: &mut ParseState<'_>) -> Option<CallEdge> {
         span.start_col,
         span.end_line,
         span.end_col,
-        state.language_string()
+        state.language.name()
     );
 
-    Some(CallEdge {
+    CallEdge {
         id: hash_id(&id_seed),
         caller_id,
         callee_name,
         callee_id: None,
+        kind,
         file: state.file.clone(),
         span,
-    })
+    }
+}
+
+/// Id of the file's module-init pseudo-symbol, which owns calls made outside
+/// of any function (top-level statements, static initializers). It is created
+/// on first use so files without such calls do not get one.
+fn module_init_id(state: &mut ParseContext<'_>) -> String {
+    if let Some(id) = &state.module_init {
+        return id.clone();
+    }
+
+    let name = MODULE_INIT_NAME;
+    let kind = SymbolKind::ModuleInit;
+    let fq_name = build_fq_name(&state.module_path, &[], name);
+    let span = state.file_span.clone();
+    let id = symbol_id(state, &kind, &fq_name, &span);
+
+    let mut symbol = Symbol {
+        id: id.clone(),
+        name: name.to_string(),
+        kind,
+        file: state.file.clone(),
+        span,
+        fq_name,
+        container: None,
+        is_exported: false,
+        entrypoint_kinds: Vec::new(),
+        entrypoint_rules: Vec::new(),
+        signature: None,
+        parameters: Vec::new(),
+        return_type: None,
+        generics: None,
+        modifiers: Vec::new(),
+        doc: None,
+        external: false,
+        declaration: false,
+    };
+    if state.analyzer.module_init_is_entrypoint() {
+        symbol.mark_entrypoint(EntrypointKind::ModuleInit, "module-init");
+    }
+    state.symbols.push(symbol);
+    state.module_init = Some(id.clone());
+    id
+}
+
+fn rust_call_name(node: Node, source: &[u8]) -> Option<(String, CallKind)> {
+    match node.kind() {
+        "call_expression" => {
+            let function = node.child_by_field_name("function")?;
+            Some((
+                normalize_call_name(node_text(function, source)),
+                CallKind::Call,
+            ))
+        }
+        "method_call_expression" => {
+            let name = node.child_by_field_name("name")?;
+            Some((node_text(name, source).trim().to_string(), CallKind::Call))
+        }
+        "macro_invocation" => {
+            let name = node.child_by_field_name("macro")?;
+            Some((
+                normalize_call_name(node_text(name, source)),
+                CallKind::Macro,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Token trees of macro invocations and `macro_rules!` bodies are not parsed
+/// as expressions, so calls inside them are recovered from the raw tokens.
+fn is_macro_token_tree(node: Node) -> bool {
+    node.kind() == "token_tree"
+        && node
+            .parent()
+            .is_some_and(|parent| matches!(parent.kind(), "macro_invocation" | "macro_rule"))
+}
+
+/// Best-effort extraction of `path(...)`, `receiver.method(...)` and nested
+/// `path!(...)` patterns from a token tree.
+fn rust_token_tree_calls(tree: Node, state: &mut ParseContext<'_>) {
+    let mut cursor = tree.walk();
+    let tokens: Vec<Node> = tree.children(&mut cursor).collect();
+
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = tokens[index];
+        if token.kind() == "token_tree" {
+            rust_token_tree_calls(token, state);
+            index += 1;
+            continue;
+        }
+        if !matches!(token.kind(), "identifier" | "self" | "crate" | "super") {
+            index += 1;
+            continue;
+        }
+
+        let mut end = index;
+        while end + 2 < tokens.len()
+            && tokens[end + 1].kind() == "::"
+            && tokens[end + 2].kind() == "identifier"
+        {
+            end += 2;
+        }
+        let next = tokens.get(end + 1).copied();
+        let path = &state.source[token.start_byte()..tokens[end].end_byte()];
+        let path = normalize_call_name(std::str::from_utf8(path).unwrap_or(""));
+
+        match next {
+            Some(bang) if bang.kind() == "!" => {
+                if let Some(args) = tokens
+                    .get(end + 2)
+                    .filter(|args| args.kind() == "token_tree")
+                {
+                    let span = span_between(token, *args);
+                    let call = new_call_edge(state, path, CallKind::Macro, span);
+                    state.calls.push(call);
+                    rust_token_tree_calls(*args, state);
+                    index = end + 3;
+                    continue;
+                }
+            }
+            Some(args) if args.kind() == "token_tree" => {
+                let opens_with_paren = args.child(0).is_some_and(|open| open.kind() == "(");
+                let previous = index.checked_sub(1).map(|prev| tokens[prev].kind());
+                // `receiver.method(...)` keeps its receiver, like regular method calls.
+                let receiver = index
+                    .checked_sub(2)
+                    .map(|prev| tokens[prev])
+                    .filter(|receiver| {
+                        previous == Some(".") && matches!(receiver.kind(), "identifier" | "self")
+                    });
+                if opens_with_paren && previous != Some("fn") {
+                    let (start, name) = match receiver {
+                        Some(receiver) => (
+                            receiver,
+                            format!("{}.{}", node_text(receiver, state.source), path),
+                        ),
+                        None => (token, path),
+                    };
+                    let span = span_between(start, args);
+                    let call = new_call_edge(state, name, CallKind::Call, span);
+                    state.calls.push(call);
+                }
+            }
+            _ => {}
+        }
+        index = end + 1;
+    }
+}
+
+fn js_call_name(node: Node, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "call_expression" => {
+            let function = js_call_function(node)?;
+            Some(normalize_call_name(node_text(function, source)))
+        }
+        "new_expression" => {
+            let constructor = node.child_by_field_name("constructor")?;
+            Some(normalize_call_name(node_text(constructor, source)))
+        }
+        _ => None,
+    }
+}
+
+/// The component rendered by a JSX element: `UserCard` for
+/// `<UserCard user={u} />`, `Menu.Item` for `<Menu.Item>`. Lowercase names
+/// are HTML elements.
+fn jsx_component_name(node: Node, source: &[u8]) -> Option<String> {
+    if !matches!(
+        node.kind(),
+        "jsx_opening_element" | "jsx_self_closing_element"
+    ) {
+        return None;
+    }
+    let name = node.child_by_field_name("name")?;
+    if !matches!(
+        name.kind(),
+        "identifier" | "member_expression" | "nested_identifier"
+    ) {
+        return None;
+    }
+    let name = node_text(name, source);
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        .then(|| normalize_call_name(name))
+}
+
+/// The callee of a JS/TS call. TypeScript parses `await invoke<T>(...)` as a
+/// call of `await invoke`, so the `await` is looked through.
+pub(crate) fn js_call_function(node: Node) -> Option<Node> {
+    let function = node.child_by_field_name("function")?;
+    if function.kind() == "await_expression" {
+        return function.named_child(0);
+    }
+    Some(function)
+}
+
+fn java_call_name(node: Node, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "method_invocation" => {
+            let name = node_text(node.child_by_field_name("name")?, source);
+            Some(jvm_call_name(
+                node.child_by_field_name("object"),
+                name,
+                source,
+            ))
+        }
+        "object_creation_expression" => {
+            let class = node.child_by_field_name("type")?;
+            Some(normalize_call_name(node_text(class, source)))
+        }
+        _ => None,
+    }
+}
+
+fn kotlin_call_name(node: Node, source: &[u8]) -> Option<String> {
+    if node.kind() != "call_expression" {
+        return None;
+    }
+    let callee = node.named_child(0)?;
+    match callee.kind() {
+        "simple_identifier" => Some(node_text(callee, source).to_string()),
+        "navigation_expression" => {
+            let suffix = callee.named_child(1)?;
+            let name = node_text(child_of_kind(suffix, "simple_identifier")?, source);
+            Some(jvm_call_name(callee.named_child(0), name, source))
+        }
+        _ => None,
+    }
+}
+
+fn c_call_name(node: Node, source: &[u8]) -> Option<String> {
+    let name = match node.kind() {
+        "call_expression" => {
+            let function = node.child_by_field_name("function")?;
+            match function.kind() {
+                "identifier" | "qualified_identifier" => node_text(function, source),
+                "template_function" => node_text(function.child_by_field_name("name")?, source),
+                // `object.method()` and `object->method()` resolve by name.
+                "field_expression" => node_text(function.child_by_field_name("field")?, source),
+                _ => return None,
+            }
+        }
+        "new_expression" => node_text(node.child_by_field_name("type")?, source),
+        _ => return None,
+    };
+    Some(normalize_call_name(name.trim_start_matches("::")))
+}
+
+/// `receiver.name` when the receiver is a plain dotted name such as `helper`,
+/// `this` or `System.out`; just `name` after other expressions like calls.
+fn jvm_call_name(receiver: Option<Node>, name: &str, source: &[u8]) -> String {
+    let receiver = receiver
+        .map(|receiver| node_text(receiver, source))
+        .filter(|text| {
+            !text.is_empty()
+                && text
+                    .chars()
+                    .all(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '$' | '.'))
+        });
+    match receiver {
+        Some(receiver) => format!("{receiver}.{name}"),
+        None => name.to_string(),
+    }
+}
+
+/// Records a Java `package` or Kotlin `package` header as a namespace symbol
+/// and makes it the module path of the rest of the file.
+fn jvm_package_declaration(node: Node, state: &mut ParseContext<'_>) {
+    if !matches!(node.kind(), "package_declaration" | "package_header") {
+        return;
+    }
+    let Some(name_node) =
+        child_of_kind(node, "scoped_identifier").or_else(|| child_of_kind(node, "identifier"))
+    else {
+        return;
+    };
+    let segments: Vec<&str> = node_text(name_node, state.source)
+        .split('.')
+        .map(str::trim)
+        .collect();
+    let Some((name, parents)) = segments.split_last() else {
+        return;
+    };
+    state.module_path = parents.join("::");
+    let mut symbol = new_symbol(state, name, SymbolKind::Namespace, node, false);
+    symbol.signature = signature_text(node, None, state.source);
+    state.symbols.push(symbol);
+    state.module_path = segments.join("::");
+}
+
+/// Bindings of a `use` tree, with `source` set to the parent module path,
+/// e.g. `use std::fs::{self, File as F}` binds `fs` and `F` from `std` and
+/// `std::fs`.
+fn rust_use_bindings(node: Node, prefix: &str, source: &[u8], bindings: &mut Vec<ImportBinding>) {
+    let join = |path: &str| {
+        let path = path.trim_start_matches("::");
+        match prefix.is_empty() {
+            true => path.to_string(),
+            false => format!("{prefix}::{path}"),
+        }
+    };
+    let mut push = |full: String, alias: Option<&str>| {
+        let full = full.strip_suffix("::self").unwrap_or(&full);
+        let (parent, name) = full.rsplit_once("::").unwrap_or(("", full));
+        bindings.push(ImportBinding {
+            source: parent.to_string(),
+            imported: name.to_string(),
+            local: alias.unwrap_or(name).to_string(),
+            reexport: false,
+        });
+    };
+
+    match node.kind() {
+        "use_as_clause" => {
+            let Some(path) = node.child_by_field_name("path") else {
+                return;
+            };
+            let alias = node
+                .child_by_field_name("alias")
+                .map(|alias| node_text(alias, source));
+            push(join(node_text(path, source)), alias);
+        }
+        "scoped_use_list" => {
+            let prefix = node
+                .child_by_field_name("path")
+                .map(|path| join(node_text(path, source)))
+                .unwrap_or_else(|| prefix.to_string());
+            if let Some(list) = node.child_by_field_name("list") {
+                rust_use_bindings(list, &prefix, source, bindings);
+            }
+        }
+        "use_list" => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                rust_use_bindings(child, prefix, source, bindings);
+            }
+        }
+        "use_wildcard" => {
+            let path = node_text(node, source).trim_end_matches('*');
+            bindings.push(ImportBinding {
+                source: join(path.trim_end_matches("::")),
+                imported: "*".to_string(),
+                local: "*".to_string(),
+                reexport: false,
+            });
+        }
+        "self" => push(format!("{prefix}::self"), None),
+        _ => push(join(node_text(node, source)), None),
+    }
+}
+
+/// Bindings of an `import ... from` or `export ... from` statement.
+fn js_import_bindings(node: Node, source: &[u8]) -> Vec<ImportBinding> {
+    let reexport = match node.kind() {
+        "import_statement" => false,
+        "export_statement" => true,
+        "variable_declarator" => return js_require_bindings(node, source),
+        _ => return Vec::new(),
+    };
+    let Some(specifier) = node
+        .child_by_field_name("source")
+        .and_then(|source_node| source_node.named_child(0))
+    else {
+        return Vec::new();
+    };
+    let specifier = node_text(specifier, source).to_string();
+    let binding = |imported: &str, local: &str| ImportBinding {
+        source: specifier.clone(),
+        imported: imported.to_string(),
+        local: local.to_string(),
+        reexport,
+    };
+
+    let mut bindings = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "import_clause" => {
+                let mut clause_cursor = child.walk();
+                for part in child.named_children(&mut clause_cursor) {
+                    match part.kind() {
+                        "identifier" => bindings.push(binding("default", node_text(part, source))),
+                        "namespace_import" => {
+                            if let Some(name) = part.named_child(0) {
+                                bindings.push(binding("*", node_text(name, source)));
+                            }
+                        }
+                        "named_imports" => bindings.extend(
+                            js_specifier_names(part, source)
+                                .map(|(imported, local)| binding(imported, local)),
+                        ),
+                        _ => {}
+                    }
+                }
+            }
+            "export_clause" => bindings.extend(
+                js_specifier_names(child, source).map(|(imported, local)| binding(imported, local)),
+            ),
+            "namespace_export" => {
+                if let Some(name) = child.named_child(0) {
+                    bindings.push(binding("*", node_text(name, source)));
+                }
+            }
+            "*" => bindings.push(binding("*", "*")),
+            _ => {}
+        }
+    }
+    bindings
+}
+
+/// Bindings of a CommonJS `require`: `const api = require("./api")` binds
+/// the whole module, `const { get, post: send } = require("./api")` and
+/// `const get = require("./api").get` single exports.
+fn js_require_bindings(node: Node, source: &[u8]) -> Vec<ImportBinding> {
+    let (Some(name), Some(value)) = (
+        node.child_by_field_name("name"),
+        node.child_by_field_name("value"),
+    ) else {
+        return Vec::new();
+    };
+    let (call, member) = match value.kind() {
+        "member_expression" => match (
+            value.child_by_field_name("object"),
+            value.child_by_field_name("property"),
+        ) {
+            (Some(object), Some(property)) => (object, Some(node_text(property, source))),
+            _ => return Vec::new(),
+        },
+        _ => (value, None),
+    };
+    let Some(specifier) = js_require_specifier(call, source) else {
+        return Vec::new();
+    };
+    let binding = |imported: &str, local: &str| ImportBinding {
+        source: specifier.clone(),
+        imported: imported.to_string(),
+        local: local.to_string(),
+        reexport: false,
+    };
+
+    match (name.kind(), member) {
+        ("identifier", member) => vec![binding(member.unwrap_or("*"), node_text(name, source))],
+        ("object_pattern", None) => {
+            let mut cursor = name.walk();
+            let properties: Vec<Node> = name.named_children(&mut cursor).collect();
+            properties
+                .into_iter()
+                .filter_map(|property| match property.kind() {
+                    "shorthand_property_identifier_pattern" => {
+                        let name = node_text(property, source);
+                        Some(binding(name, name))
+                    }
+                    "pair_pattern" => {
+                        let key = property.child_by_field_name("key")?;
+                        let value = property.child_by_field_name("value")?;
+                        (value.kind() == "identifier")
+                            .then(|| binding(node_text(key, source), node_text(value, source)))
+                    }
+                    _ => None,
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The specifier of a `require("...")` call.
+fn js_require_specifier(node: Node, source: &[u8]) -> Option<String> {
+    if node.kind() != "call_expression"
+        || node_text(node.child_by_field_name("function")?, source) != "require"
+    {
+        return None;
+    }
+    let arguments = node.child_by_field_name("arguments")?;
+    if arguments.named_child_count() != 1 {
+        return None;
+    }
+    let argument = arguments.named_child(0)?;
+    if argument.kind() != "string" {
+        return None;
+    }
+    let fragment = argument.named_child(0)?;
+    (fragment.kind() == "string_fragment").then(|| node_text(fragment, source).to_string())
+}
+
+/// Whether `node` is `module.exports` or `exports`.
+fn js_is_commonjs_exports(node: Node, source: &[u8]) -> bool {
+    matches!(node_text(node, source), "exports" | "module.exports")
+        && matches!(node.kind(), "identifier" | "member_expression")
+}
+
+/// The exported name of `exports.run = ...` or `module.exports.run = ...`.
+fn js_commonjs_export_target<'a>(node: Node<'a>, source: &[u8]) -> Option<Node<'a>> {
+    let left = node.child_by_field_name("left")?;
+    if left.kind() != "member_expression"
+        || !js_is_commonjs_exports(left.child_by_field_name("object")?, source)
+    {
+        return None;
+    }
+    left.child_by_field_name("property")
 }
 
-fn rust_call_name(node: Node, source: &[u8]) -> Option<String> {
-    match node.kind() {
-        "call_expression" => {
-            let function = node.child_by_field_name("function")?;
-            Some(normalize_call_name(node_text(function, source)))
-        }
-        "method_call_expression" => {
-            let name = node.child_by_field_name("name")?;
-            Some(node_text(name, source).trim().to_string())
+/// Local names a CommonJS export assignment exports: `run` and `stop` in
+/// `module.exports = { run, halt: stop }`, `run` in `exports.start = run`
+/// and `module.exports = run`.
+fn js_commonjs_export_names(node: Node, source: &[u8]) -> Vec<String> {
+    if node.kind() != "assignment_expression" {
+        return Vec::new();
+    }
+    let (Some(left), Some(right)) = (
+        node.child_by_field_name("left"),
+        node.child_by_field_name("right"),
+    ) else {
+        return Vec::new();
+    };
+    if !js_is_commonjs_exports(left, source) && js_commonjs_export_target(node, source).is_none() {
+        return Vec::new();
+    }
+    match right.kind() {
+        "identifier" => vec![node_text(right, source).to_string()],
+        "object" if js_is_commonjs_exports(left, source) => {
+            let mut cursor = right.walk();
+            let properties: Vec<Node> = right.named_children(&mut cursor).collect();
+            properties
+                .into_iter()
+                .filter_map(|property| match property.kind() {
+                    "shorthand_property_identifier" => Some(node_text(property, source)),
+                    "pair" => property
+                        .child_by_field_name("value")
+                        .filter(|value| value.kind() == "identifier")
+                        .map(|value| node_text(value, source)),
+                    _ => None,
+                })
+                .map(str::to_string)
+                .collect()
         }
-        _ => None,
+        _ => Vec::new(),
     }
 }
 
-fn js_call_name(node: Node, source: &[u8]) -> Option<String> {
-    match node.kind() {
-        "call_expression" => {
-            let function = node.child_by_field_name("function")?;
-            Some(normalize_call_name(node_text(function, source)))
-        }
-        "new_expression" => {
-            let constructor = node.child_by_field_name("constructor")?;
-            Some(normalize_call_name(node_text(constructor, source)))
+/// Whether an object-literal member is directly in the object assigned to
+/// `module.exports`.
+fn js_in_commonjs_exports(node: Node, source: &[u8]) -> bool {
+    node.parent()
+        .filter(|object| object.kind() == "object")
+        .and_then(|object| object.parent())
+        .filter(|assignment| assignment.kind() == "assignment_expression")
+        .and_then(|assignment| assignment.child_by_field_name("left"))
+        .is_some_and(|left| js_is_commonjs_exports(left, source))
+}
+
+/// Whether an object-literal property belongs to a module-level object: one
+/// bound by a top-level declaration, exported, or assigned to
+/// `module.exports`, possibly nested in another such object. Objects built
+/// inside functions, like the options in `fetch(url, { onDone: () => {} })`,
+/// are not.
+fn js_in_module_object(pair: Node, source: &[u8]) -> bool {
+    let Some(mut node) = pair.parent().filter(|object| object.kind() == "object") else {
+        return false;
+    };
+    while let Some(parent) = node.parent() {
+        match parent.kind() {
+            "pair" | "parenthesized_expression" | "as_expression" | "satisfies_expression" => {}
+            "object" if node.kind() == "pair" => {}
+            "variable_declarator" => {
+                return parent
+                    .parent()
+                    .and_then(|declaration| declaration.parent())
+                    .is_some_and(|scope| matches!(scope.kind(), "program" | "export_statement"));
+            }
+            "export_statement" => return true,
+            "assignment_expression" => {
+                return parent
+                    .child_by_field_name("left")
+                    .is_some_and(|left| js_is_commonjs_exports(left, source))
+                    || js_commonjs_export_target(parent, source).is_some();
+            }
+            _ => return false,
         }
-        _ => None,
+        node = parent;
     }
+    false
+}
+
+fn js_is_function_value(node: Node) -> bool {
+    matches!(
+        node.kind(),
+        "arrow_function" | "function" | "function_expression"
+    )
+}
+
+/// The name of an object-literal property or class field, when it is an
+/// identifier.
+fn js_property_name(node: Node) -> Option<Node> {
+    let name = node
+        .child_by_field_name("key")
+        .or_else(|| node.child_by_field_name("property"))
+        .or_else(|| node.child_by_field_name("name"))?;
+    matches!(
+        name.kind(),
+        "property_identifier" | "private_property_identifier"
+    )
+    .then_some(name)
+}
+
+/// `(name, alias)` pairs of the `import_specifier`s or `export_specifier`s
+/// in a `{ ... }` list; the alias defaults to the name.
+fn js_specifier_names<'a>(
+    list: Node<'a>,
+    source: &'a [u8],
+) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
+    let mut cursor = list.walk();
+    let specifiers: Vec<Node<'a>> = list.named_children(&mut cursor).collect();
+    specifiers.into_iter().filter_map(move |specifier| {
+        let name = node_text(specifier.child_by_field_name("name")?, source);
+        let alias = specifier
+            .child_by_field_name("alias")
+            .map(|alias| node_text(alias, source))
+            .unwrap_or(name);
+        Some((name, alias))
+    })
 }
 
 fn normalize_call_name(value: &str) -> String {
@@ -408,7 +2090,7 @@ fn normalize_call_name(value: &str) -> String {
 }
 
 fn new_symbol(
-    state: &ParseState<'_>,
+    state: &ParseContext<'_>,
     name: &str,
     kind: SymbolKind,
     node: Node,
@@ -420,30 +2102,716 @@ fn new_symbol(
         .containers
         .last()
         .map(|container| container.name.clone());
-    let is_entrypoint = name == "main" || is_exported;
+    let id = symbol_id(state, &kind, &fq_name, &span);
+
+    let mut symbol = Symbol {
+        id,
+        name: name.to_string(),
+        kind,
+        file: state.file.clone(),
+        span,
+        fq_name,
+        container,
+        is_exported,
+        entrypoint_kinds: Vec::new(),
+        entrypoint_rules: Vec::new(),
+        signature: None,
+        parameters: Vec::new(),
+        return_type: None,
+        generics: None,
+        modifiers: Vec::new(),
+        doc: None,
+        external: false,
+        declaration: false,
+    };
+    if name == "main" {
+        symbol.mark_entrypoint(EntrypointKind::Main, "main");
+    }
+    if is_exported {
+        symbol.mark_entrypoint(EntrypointKind::PublicApi, "exported");
+    }
+    symbol
+}
+
+/// Flags a symbol as a declaration without a body; those are never
+/// entrypoints themselves.
+fn mark_declaration(symbol: &mut Symbol) {
+    symbol.declaration = true;
+    symbol.entrypoint_kinds.clear();
+    symbol.entrypoint_rules.clear();
+}
+
+fn symbol_id(state: &ParseContext<'_>, kind: &SymbolKind, fq_name: &str, span: &Span) -> String {
     let id_seed = format!(
         "symbol:{}:{}:{}:{}:{}:{}:{}:{}",
         state.file,
-        kind_to_str(&kind),
+        kind_to_str(kind),
         fq_name,
         span.start_line,
         span.start_col,
         span.end_line,
         span.end_col,
-        state.language_string()
+        state.language.name()
     );
+    hash_id(&id_seed)
+}
 
-    Symbol {
-        id: hash_id(&id_seed),
-        name: name.to_string(),
-        kind,
-        file: state.file.clone(),
-        span,
-        fq_name,
-        container,
-        is_exported,
-        is_entrypoint,
+fn rust_describe_symbol(node: Node, source: &[u8], symbol: &mut Symbol) {
+    // Consts and statics stop before their initializer, macros before their rules.
+    let body = node
+        .child_by_field_name("body")
+        .or_else(|| node.child_by_field_name("value"))
+        .or_else(|| {
+            (node.kind() == "macro_definition")
+                .then(|| node.child_by_field_name("name")?.next_sibling())
+                .flatten()
+        });
+    symbol.signature = signature_text(node, body, source)
+        .map(|text| text.trim_end_matches('=').trim_end().to_string());
+    symbol.generics = node
+        .child_by_field_name("type_parameters")
+        .map(|params| node_text(params, source).to_string());
+    symbol.return_type = node
+        .child_by_field_name("return_type")
+        .map(|ty| collapse_whitespace(node_text(ty, source)));
+    if let Some(params) = node.child_by_field_name("parameters") {
+        symbol.parameters = rust_parameters(params, source);
+    }
+    symbol.modifiers = rust_modifiers(node);
+    symbol.doc = rust_doc_comment(node, source);
+}
+
+fn rust_parameters(params: Node, source: &[u8]) -> Vec<Parameter> {
+    let mut parameters = Vec::new();
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        match param.kind() {
+            "parameter" => {
+                let name = param
+                    .child_by_field_name("pattern")
+                    .map(|pattern| node_text(pattern, source))
+                    .unwrap_or("_");
+                parameters.push(Parameter {
+                    name: name.to_string(),
+                    type_name: param
+                        .child_by_field_name("type")
+                        .map(|ty| collapse_whitespace(node_text(ty, source))),
+                });
+            }
+            // Untyped closure parameters: `|a, b| ...`.
+            "identifier" => parameters.push(Parameter {
+                name: node_text(param, source).to_string(),
+                type_name: None,
+            }),
+            "self_parameter" | "variadic_parameter" => parameters.push(Parameter {
+                name: collapse_whitespace(node_text(param, source)),
+                type_name: None,
+            }),
+            _ => {}
+        }
+    }
+    parameters
+}
+
+fn rust_modifiers(node: Node) -> Vec<Modifier> {
+    let mut modifiers = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.kind() == "function_modifiers" {
+            let mut inner = child.walk();
+            for modifier in child.children(&mut inner) {
+                modifiers.extend(rust_modifier(modifier.kind()));
+            }
+        } else {
+            modifiers.extend(rust_modifier(child.kind()));
+        }
+    }
+    if rust_in_extern_block(node) {
+        modifiers.push(Modifier::Extern);
+    }
+    modifiers.sort();
+    modifiers.dedup();
+    modifiers
+}
+
+fn rust_in_extern_block(node: Node) -> bool {
+    node.parent()
+        .and_then(|list| list.parent())
+        .is_some_and(|item| item.kind() == "foreign_mod_item")
+}
+
+fn rust_modifier(kind: &str) -> Option<Modifier> {
+    match kind {
+        "async" => Some(Modifier::Async),
+        "unsafe" => Some(Modifier::Unsafe),
+        "const" => Some(Modifier::Const),
+        "static" => Some(Modifier::Static),
+        "extern_modifier" => Some(Modifier::Extern),
+        _ => None,
+    }
+}
+
+/// Collects outer doc comments (`///`, `/** */`) directly above an item,
+/// skipping over attributes.
+fn rust_doc_comment(node: Node, source: &[u8]) -> Option<String> {
+    let mut blocks = Vec::new();
+    let mut current = node.prev_sibling();
+    while let Some(sibling) = current {
+        match sibling.kind() {
+            "attribute_item" => {}
+            "line_comment" | "block_comment" => {
+                let is_outer_doc = sibling.child_by_field_name("outer").is_some();
+                let Some(doc) = sibling.child_by_field_name("doc").filter(|_| is_outer_doc) else {
+                    break;
+                };
+                blocks.push(clean_doc_text(node_text(doc, source)));
+            }
+            _ => break,
+        }
+        current = sibling.prev_sibling();
+    }
+    blocks.reverse();
+    let doc = blocks.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+fn js_describe_symbol(node: Node, source: &[u8], symbol: &mut Symbol) {
+    // `const handler = () => {}` describes the function stored in the
+    // declarator, and likewise for properties, fields and assignments.
+    let function = match node.kind() {
+        "variable_declarator" | "pair" | "field_definition" | "public_field_definition" => {
+            node.child_by_field_name("value").unwrap_or(node)
+        }
+        "assignment_expression" => node.child_by_field_name("right").unwrap_or(node),
+        _ => node,
+    };
+
+    let signature = signature_text(node, function.child_by_field_name("body"), source)
+        .map(|text| text.trim_end_matches("=>").trim_end().to_string());
+    symbol.signature = signature;
+    symbol.generics = function
+        .child_by_field_name("type_parameters")
+        .map(|params| node_text(params, source).to_string());
+    symbol.return_type = function
+        .child_by_field_name("return_type")
+        .map(|ty| type_annotation_text(ty, source));
+    if let Some(params) = function.child_by_field_name("parameters") {
+        symbol.parameters = js_parameters(params, source);
+    } else if let Some(param) = function.child_by_field_name("parameter") {
+        symbol.parameters = vec![Parameter {
+            name: node_text(param, source).to_string(),
+            type_name: None,
+        }];
+    }
+
+    let mut modifiers = Vec::new();
+    for item in [node, function] {
+        let mut cursor = item.walk();
+        for child in item.children(&mut cursor) {
+            match child.kind() {
+                "async" => modifiers.push(Modifier::Async),
+                "static" => modifiers.push(Modifier::Static),
+                "abstract" => modifiers.push(Modifier::Abstract),
+                _ => {}
+            }
+        }
+    }
+    modifiers.sort();
+    modifiers.dedup();
+    symbol.modifiers = modifiers;
+
+    if let Some(comment) = js_doc_comment(node, source) {
+        apply_jsdoc_types(&comment, symbol);
+        symbol.doc = Some(clean_doc_text(
+            comment.trim_start_matches("/**").trim_end_matches("*/"),
+        ))
+        .filter(|doc| !doc.is_empty());
+    }
+}
+
+fn js_parameters(params: Node, source: &[u8]) -> Vec<Parameter> {
+    let mut parameters = Vec::new();
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        let (name, type_name) = match param.kind() {
+            "comment" => continue,
+            "required_parameter" | "optional_parameter" => {
+                let name = param
+                    .child_by_field_name("pattern")
+                    .map(|pattern| node_text(pattern, source))
+                    .unwrap_or_else(|| node_text(param, source));
+                let type_name = param
+                    .child_by_field_name("type")
+                    .map(|ty| type_annotation_text(ty, source));
+                (name, type_name)
+            }
+            "assignment_pattern" => {
+                let name = param
+                    .child_by_field_name("left")
+                    .map(|left| node_text(left, source))
+                    .unwrap_or_else(|| node_text(param, source));
+                (name, None)
+            }
+            _ => (node_text(param, source), None),
+        };
+        parameters.push(Parameter {
+            name: collapse_whitespace(name),
+            type_name,
+        });
+    }
+    parameters
+}
+
+/// Finds a `/** ... */` comment directly above the declaration, looking past
+/// the `export` / `const` wrappers the declaration may be nested in.
+fn js_doc_comment(node: Node, source: &[u8]) -> Option<String> {
+    let mut anchor = node;
+    while let Some(parent) = anchor.parent() {
+        let wraps_declaration = matches!(
+            parent.kind(),
+            "lexical_declaration"
+                | "variable_declaration"
+                | "export_statement"
+                | "expression_statement"
+        );
+        if !wraps_declaration {
+            break;
+        }
+        anchor = parent;
+    }
+
+    let comment = anchor
+        .prev_sibling()
+        .filter(|sibling| sibling.kind() == "comment")?;
+    let text = node_text(comment, source);
+    text.starts_with("/**").then(|| text.to_string())
+}
+
+/// Uses JSDoc `@param {Type} name` and `@returns {Type}` tags to fill in types
+/// the source itself does not annotate.
+fn apply_jsdoc_types(comment: &str, symbol: &mut Symbol) {
+    for line in comment.lines() {
+        let line = line.trim().trim_start_matches('*').trim();
+        let Some((tag, rest)) = line.split_once(char::is_whitespace) else {
+            continue;
+        };
+        let rest = rest.trim();
+        let Some(type_text) = rest.strip_prefix('{').and_then(|rest| rest.split_once('}')) else {
+            continue;
+        };
+        let (type_name, remainder) = (type_text.0.trim().to_string(), type_text.1.trim());
+        match tag {
+            "@param" | "@arg" | "@argument" => {
+                let name = remainder
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or_default()
+                    .trim_start_matches('[')
+                    .split(['=', ']'])
+                    .next()
+                    .unwrap_or_default();
+                if let Some(param) = symbol
+                    .parameters
+                    .iter_mut()
+                    .find(|param| param.name == name && param.type_name.is_none())
+                {
+                    param.type_name = Some(type_name);
+                }
+            }
+            "@returns" | "@return" if symbol.return_type.is_none() => {
+                symbol.return_type = Some(type_name);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn java_describe_symbol(node: Node, source: &[u8], symbol: &mut Symbol) {
+    let body = node.child_by_field_name("body");
+    symbol.signature = jvm_signature_text(node, body, source);
+    symbol.generics = node
+        .child_by_field_name("type_parameters")
+        .map(|params| node_text(params, source).to_string());
+    if node.kind() == "method_declaration" {
+        symbol.return_type = node
+            .child_by_field_name("type")
+            .map(|ty| collapse_whitespace(node_text(ty, source)));
+    }
+    if let Some(params) = node.child_by_field_name("parameters") {
+        symbol.parameters = java_parameters(params, source);
+    }
+    symbol.modifiers = jvm_modifiers(node);
+    symbol.doc = jvm_doc_comment(node, source);
+}
+
+fn java_parameters(params: Node, source: &[u8]) -> Vec<Parameter> {
+    // A lone untyped lambda parameter: `x -> ...`.
+    if params.kind() == "identifier" {
+        return vec![Parameter {
+            name: node_text(params, source).to_string(),
+            type_name: None,
+        }];
+    }
+    let mut parameters = Vec::new();
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        let (name, type_name) = match param.kind() {
+            "formal_parameter" => (
+                param.child_by_field_name("name"),
+                param.child_by_field_name("type"),
+            ),
+            // `int... values`
+            "spread_parameter" => (
+                child_of_kind(param, "variable_declarator")
+                    .and_then(|declarator| declarator.child_by_field_name("name")),
+                param.named_child(0),
+            ),
+            "identifier" => (Some(param), None),
+            _ => continue,
+        };
+        let Some(name) = name else {
+            continue;
+        };
+        let mut type_name = type_name.map(|ty| collapse_whitespace(node_text(ty, source)));
+        if param.kind() == "spread_parameter" {
+            type_name = type_name.map(|ty| format!("{ty}..."));
+        }
+        parameters.push(Parameter {
+            name: node_text(name, source).to_string(),
+            type_name,
+        });
     }
+    parameters
+}
+
+fn kotlin_describe_symbol(node: Node, source: &[u8], symbol: &mut Symbol) {
+    let body = ["function_body", "class_body", "enum_class_body"]
+        .into_iter()
+        .find_map(|kind| child_of_kind(node, kind));
+    symbol.signature = jvm_signature_text(node, body, source)
+        .map(|text| text.trim_end_matches('=').trim_end().to_string());
+    symbol.generics =
+        child_of_kind(node, "type_parameters").map(|params| node_text(params, source).to_string());
+    let params = child_of_kind(node, "function_value_parameters")
+        .or_else(|| child_of_kind(node, "primary_constructor"));
+    if let Some(params) = params {
+        symbol.parameters = kotlin_parameters(params, source);
+        // `fun name(...): Type`
+        if params.kind() == "function_value_parameters" {
+            symbol.return_type = params
+                .next_sibling()
+                .filter(|colon| colon.kind() == ":")
+                .and_then(|colon| colon.next_named_sibling())
+                .map(|ty| collapse_whitespace(node_text(ty, source)));
+        }
+    }
+    symbol.modifiers = jvm_modifiers(node);
+    symbol.doc = jvm_doc_comment(node, source);
+}
+
+fn kotlin_parameters(params: Node, source: &[u8]) -> Vec<Parameter> {
+    let mut parameters = Vec::new();
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        if !matches!(param.kind(), "parameter" | "class_parameter") {
+            continue;
+        }
+        let Some(name) = child_of_kind(param, "simple_identifier") else {
+            continue;
+        };
+        parameters.push(Parameter {
+            name: node_text(name, source).to_string(),
+            type_name: name
+                .next_sibling()
+                .filter(|colon| colon.kind() == ":")
+                .and_then(|colon| colon.next_named_sibling())
+                .map(|ty| collapse_whitespace(node_text(ty, source))),
+        });
+    }
+    parameters
+}
+
+/// Modifiers from a Java or Kotlin `modifiers` list: `static`, `native`,
+/// `suspend`, `const` and `external`.
+fn jvm_modifiers(node: Node) -> Vec<Modifier> {
+    let mut modifiers = Vec::new();
+    let Some(list) = child_of_kind(node, "modifiers") else {
+        return modifiers;
+    };
+    let mut cursor = list.walk();
+    for child in list.children(&mut cursor) {
+        // Kotlin wraps each keyword in a node such as `function_modifier`.
+        let keyword = if child.child_count() == 1 {
+            child
+                .child(0)
+                .map_or(child.kind(), |keyword| keyword.kind())
+        } else {
+            child.kind()
+        };
+        match keyword {
+            "static" => modifiers.push(Modifier::Static),
+            "suspend" => modifiers.push(Modifier::Async),
+            "const" => modifiers.push(Modifier::Const),
+            "native" | "external" => modifiers.push(Modifier::Extern),
+            _ => {}
+        }
+    }
+    modifiers.sort();
+    modifiers.dedup();
+    modifiers
+}
+
+/// Signature text starting after any annotations in the modifiers.
+fn jvm_signature_text(node: Node, body: Option<Node>, source: &[u8]) -> Option<String> {
+    let mut start = node.start_byte();
+    if let Some(list) = child_of_kind(node, "modifiers").filter(|list| list.start_byte() == start) {
+        let mut cursor = list.walk();
+        let first_keyword = list
+            .children(&mut cursor)
+            .find(|child| !matches!(child.kind(), "annotation" | "marker_annotation"));
+        start = match first_keyword {
+            Some(keyword) => keyword.start_byte(),
+            None => list
+                .next_sibling()
+                .map_or(list.end_byte(), |next| next.start_byte()),
+        };
+    }
+    let end = body.map_or(node.end_byte(), |body| body.start_byte());
+    signature_between(start, end, source)
+}
+
+/// Javadoc or KDoc (`/** ... */`) directly above the declaration.
+fn jvm_doc_comment(node: Node, source: &[u8]) -> Option<String> {
+    let mut previous = node.prev_sibling();
+    // The Kotlin grammar attaches a comment following the imports to the
+    // last import.
+    if let Some(imports) = previous.filter(|sibling| sibling.kind() == "import_list") {
+        previous = imports
+            .named_child(imports.named_child_count().saturating_sub(1))
+            .and_then(|import| import.named_child(import.named_child_count().saturating_sub(1)));
+    }
+    let comment = previous
+        .filter(|sibling| matches!(sibling.kind(), "block_comment" | "multiline_comment"))?;
+    let text = node_text(comment, source);
+    let doc = clean_doc_text(text.strip_prefix("/**")?.trim_end_matches("*/"));
+    (!doc.is_empty()).then_some(doc)
+}
+
+fn c_describe_symbol(node: Node, source: &[u8], symbol: &mut Symbol) {
+    let body = node.child_by_field_name("body");
+    symbol.signature = signature_text(node, body, source);
+    let mut anchor = node;
+    while let Some(parent) = anchor.parent().filter(|parent| {
+        matches!(
+            parent.kind(),
+            "template_declaration" | "linkage_specification" | "type_definition"
+        )
+    }) {
+        if parent.kind() == "template_declaration" {
+            symbol.generics = parent
+                .child_by_field_name("parameters")
+                .map(|params| node_text(params, source).to_string());
+        }
+        anchor = parent;
+    }
+    if let Some(declarator) = c_function_declarator(node) {
+        symbol.return_type = c_return_type(node, declarator, source);
+        if let Some(params) = declarator.child_by_field_name("parameters") {
+            symbol.parameters = c_parameters(params, source);
+        }
+    }
+    symbol.modifiers = c_modifiers(node, source);
+    symbol.doc = c_doc_comment(anchor, source);
+}
+
+/// The type before the declarator plus any pointer or reference markers
+/// around the function name, e.g. `const char *`. Constructors have none.
+fn c_return_type(node: Node, declarator: Node, source: &[u8]) -> Option<String> {
+    let ty = node.child_by_field_name("type")?;
+    let mut cursor = node.walk();
+    let start = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "type_qualifier" || child.id() == ty.id())?
+        .start_byte();
+    let mut current = node.child_by_field_name("declarator")?;
+    let base = std::str::from_utf8(&source[start..current.start_byte()]).ok()?;
+    let mut markers = String::new();
+    while current.id() != declarator.id() {
+        match current.kind() {
+            "pointer_declarator" => markers.push('*'),
+            "reference_declarator" => markers.push_str(
+                current
+                    .child(0)
+                    .map_or("&", |marker| node_text(marker, source)),
+            ),
+            _ => {}
+        }
+        current = current
+            .child_by_field_name("declarator")
+            .or_else(|| current.named_child(0))?;
+    }
+    let base = collapse_whitespace(base);
+    Some(if markers.is_empty() {
+        base
+    } else {
+        format!("{base} {markers}")
+    })
+}
+
+fn c_parameters(params: Node, source: &[u8]) -> Vec<Parameter> {
+    let mut parameters = Vec::new();
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        match param.kind() {
+            "parameter_declaration"
+            | "optional_parameter_declaration"
+            | "variadic_parameter_declaration" => {}
+            "variadic_parameter" => {
+                parameters.push(Parameter {
+                    name: "...".to_string(),
+                    type_name: None,
+                });
+                continue;
+            }
+            _ => continue,
+        }
+        // The type is the declaration without its name and default value.
+        let end = param
+            .child_by_field_name("default_value")
+            .map_or(param.end_byte(), |value| value.start_byte());
+        let name = param
+            .child_by_field_name("declarator")
+            .and_then(c_declarator_name);
+        let text = |start: usize, end: usize| String::from_utf8_lossy(&source[start..end]);
+        let type_text = match name {
+            Some(name) => format!(
+                "{}{}",
+                text(param.start_byte(), name.start_byte()),
+                text(name.end_byte(), end)
+            ),
+            None => text(param.start_byte(), end).to_string(),
+        };
+        let type_name = collapse_whitespace(&type_text)
+            .trim_end_matches('=')
+            .trim_end()
+            .to_string();
+        // `f(void)` takes no parameters.
+        if name.is_none() && type_name == "void" {
+            continue;
+        }
+        parameters.push(Parameter {
+            name: name.map_or("_", |name| node_text(name, source)).to_string(),
+            type_name: Some(type_name),
+        });
+    }
+    parameters
+}
+
+/// The identifier a declarator declares, e.g. `name` in `const char *name`.
+fn c_declarator_name(node: Node) -> Option<Node> {
+    match node.kind() {
+        "identifier" | "field_identifier" => Some(node),
+        _ => c_declarator_name(
+            node.child_by_field_name("declarator")
+                .or_else(|| node.named_child(0))?,
+        ),
+    }
+}
+
+/// `static`, `extern` and `constexpr` specifiers; functions declared inside
+/// an `extern "C"` block are `extern` too.
+fn c_modifiers(node: Node, source: &[u8]) -> Vec<Modifier> {
+    let mut modifiers = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if !matches!(child.kind(), "storage_class_specifier" | "type_qualifier") {
+            continue;
+        }
+        match node_text(child, source) {
+            "static" => modifiers.push(Modifier::Static),
+            "extern" => modifiers.push(Modifier::Extern),
+            "constexpr" => modifiers.push(Modifier::Const),
+            _ => {}
+        }
+    }
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.kind() == "linkage_specification" {
+            modifiers.push(Modifier::Extern);
+            break;
+        }
+        current = parent.parent();
+    }
+    modifiers.sort();
+    modifiers.dedup();
+    modifiers
+}
+
+/// Doc comments (`///`, `//!`, `/** */`, `/*! */`) directly above a
+/// declaration.
+fn c_doc_comment(node: Node, source: &[u8]) -> Option<String> {
+    let mut blocks = Vec::new();
+    let mut current = node.prev_sibling();
+    while let Some(sibling) = current.filter(|sibling| sibling.kind() == "comment") {
+        let text = node_text(sibling, source);
+        let doc = if let Some(line) = text
+            .strip_prefix("///")
+            .or_else(|| text.strip_prefix("//!"))
+        {
+            line
+        } else if let Some(block) = text
+            .strip_prefix("/**")
+            .or_else(|| text.strip_prefix("/*!"))
+        {
+            block.trim_end_matches("*/")
+        } else {
+            break;
+        };
+        blocks.push(clean_doc_text(doc));
+        current = sibling.prev_sibling();
+    }
+    blocks.reverse();
+    let doc = blocks.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// Text of `node` up to (not including) its body, or the whole node when it
+/// has none, with whitespace collapsed and any trailing `;` or `,` removed.
+fn signature_text(node: Node, body: Option<Node>, source: &[u8]) -> Option<String> {
+    let end = body.map_or(node.end_byte(), |body| body.start_byte());
+    signature_between(node.start_byte(), end, source)
+}
+
+fn signature_between(start: usize, end: usize, source: &[u8]) -> Option<String> {
+    let text = std::str::from_utf8(&source[start..end]).ok()?;
+    let signature = collapse_whitespace(text)
+        .trim_end_matches([';', ','])
+        .trim_end()
+        .to_string();
+    (!signature.is_empty()).then_some(signature)
+}
+
+fn type_annotation_text(node: Node, source: &[u8]) -> String {
+    collapse_whitespace(node_text(node, source).trim_start_matches(':'))
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Strips comment decoration (leading `*` on block comment lines, one
+/// leading space) and surrounding blank lines from doc comment text.
+fn clean_doc_text(value: &str) -> String {
+    let lines: Vec<&str> = value
+        .lines()
+        .map(|line| {
+            let line = line.trim_end();
+            let trimmed = line.trim_start();
+            let line = trimmed.strip_prefix('*').unwrap_or(line);
+            line.strip_prefix(' ').unwrap_or(line)
+        })
+        .collect();
+    lines.join("\n").trim().to_string()
 }
 
 fn build_fq_name(module_path: &str, containers: &[Container], name: &str) -> String {
@@ -477,6 +2845,73 @@ fn rust_is_exported(node: Node, source: &[u8]) -> bool {
     false
 }
 
+/// Whether an outer attribute such as `#[macro_export]` precedes the item.
+fn rust_has_attribute(node: Node, source: &[u8], name: &str) -> bool {
+    rust_attribute_paths(node, source)
+        .iter()
+        .any(|path| path == name)
+}
+
+/// Paths of the outer attributes preceding the item, e.g. `tokio::main` or
+/// `no_mangle` (including the `#[unsafe(no_mangle)]` spelling).
+pub(crate) fn rust_attribute_paths(node: Node, source: &[u8]) -> Vec<String> {
+    rust_attributes(node, source)
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// Outer attributes preceding the item as their path and the text inside
+/// their parentheses, e.g. `("wasm_bindgen", "js_name = greet")`.
+pub(crate) fn rust_attributes(node: Node, source: &[u8]) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut current = node.prev_sibling();
+    while let Some(sibling) = current {
+        match sibling.kind() {
+            "attribute_item" => {
+                let attribute = node_text(sibling, source)
+                    .trim_start_matches("#[")
+                    .trim_end_matches(']')
+                    .trim();
+                let attribute = attribute
+                    .strip_prefix("unsafe(")
+                    .and_then(|inner| inner.strip_suffix(')'))
+                    .unwrap_or(attribute);
+                let path = attribute.split(['(', '=', ' ']).next().unwrap_or_default();
+                let arguments = attribute[path.len()..]
+                    .trim()
+                    .strip_prefix('(')
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .unwrap_or_default();
+                attributes.push((path.to_string(), arguments.to_string()));
+            }
+            "line_comment" | "block_comment" => {}
+            _ => break,
+        }
+        current = sibling.prev_sibling();
+    }
+    attributes
+}
+
+/// Whether `node` only declares something implemented elsewhere: it is
+/// under a `declare` or in a `.d.ts` file.
+fn js_is_ambient(node: Node, file: &str) -> bool {
+    if [".d.ts", ".d.mts", ".d.cts"]
+        .iter()
+        .any(|suffix| file.ends_with(suffix))
+    {
+        return true;
+    }
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if ancestor.kind() == "ambient_declaration" {
+            return true;
+        }
+        current = ancestor.parent();
+    }
+    false
+}
+
 fn js_is_exported(node: Node) -> bool {
     let mut current = Some(node);
     while let Some(item) = current {
@@ -492,14 +2927,89 @@ fn js_is_exported(node: Node) -> bool {
     false
 }
 
-fn node_text<'a>(node: Node, source: &'a [u8]) -> &'a str {
+/// C and C++ functions and types are exported unless `static` or inside an
+/// anonymous namespace; class members follow the access specifier above
+/// them, defaulting to private in a `class` and public in a `struct`.
+fn c_is_exported(node: Node, source: &[u8]) -> bool {
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if ancestor.kind() == "namespace_definition"
+            && ancestor.child_by_field_name("name").is_none()
+        {
+            return false;
+        }
+        current = ancestor.parent();
+    }
+
+    let mut member = node;
+    while let Some(parent) = member.parent().filter(|parent| {
+        matches!(
+            parent.kind(),
+            "template_declaration" | "field_declaration" | "declaration" | "type_definition"
+        )
+    }) {
+        member = parent;
+    }
+    if let Some(list) = member
+        .parent()
+        .filter(|parent| parent.kind() == "field_declaration_list")
+    {
+        let mut sibling = member.prev_sibling();
+        while let Some(previous) = sibling {
+            if previous.kind() == "access_specifier" {
+                return node_text(previous, source) == "public";
+            }
+            sibling = previous.prev_sibling();
+        }
+        return list
+            .parent()
+            .is_some_and(|owner| owner.kind() != "class_specifier");
+    }
+
+    let mut cursor = node.walk();
+    let is_static = node.children(&mut cursor).any(|child| {
+        child.kind() == "storage_class_specifier" && node_text(child, source) == "static"
+    });
+    !is_static
+}
+
+/// Java declarations are exported when `public`.
+fn java_is_exported(node: Node) -> bool {
+    child_of_kind(node, "modifiers").is_some_and(|list| has_child_kind(list, "public"))
+}
+
+/// Kotlin declarations are public unless marked otherwise; local functions
+/// are never exported.
+fn kotlin_is_exported(node: Node, state: &ParseContext<'_>) -> bool {
+    if !state.functions.is_empty() {
+        return false;
+    }
+    let Some(list) = child_of_kind(node, "modifiers") else {
+        return true;
+    };
+    let mut cursor = list.walk();
+    let restricted = list
+        .children(&mut cursor)
+        .filter(|child| child.kind() == "visibility_modifier")
+        .any(|visibility| {
+            matches!(
+                node_text(visibility, state.source),
+                "private" | "internal" | "protected"
+            )
+        });
+    !restricted
+}
+
+pub(crate) fn node_text<'a>(node: Node, source: &'a [u8]) -> &'a str {
     let range = node.byte_range();
     std::str::from_utf8(&source[range]).unwrap_or("")
 }
 
 fn span_from_node(node: Node) -> Span {
-    let start = node.start_position();
-    let end = node.end_position();
+    span_from_points(node.start_position(), node.end_position())
+}
+
+fn span_from_points(start: Point, end: Point) -> Span {
     Span {
         start_line: start.row + 1,
         start_col: start.column + 1,
@@ -508,21 +3018,8 @@ fn span_from_node(node: Node) -> Span {
     }
 }
 
-fn module_path_from_file(path: &Path, root: &Path) -> String {
-    let relative = path.strip_prefix(root).unwrap_or(path);
-    let mut components: Vec<String> = relative
-        .components()
-        .map(|component| component.as_os_str().to_string_lossy().to_string())
-        .collect();
-    if let Some(last) = components.last_mut() {
-        if let Some((stem, _)) = last.rsplit_once('.') {
-            *last = stem.to_string();
-        }
-        if last == "mod" || last == "index" {
-            components.pop();
-        }
-    }
-    components.join("::")
+fn span_between(start: Node, end: Node) -> Span {
+    span_from_points(start.start_position(), end.end_position())
 }
 
 fn hash_id(value: &str) -> String {
@@ -542,9 +3039,39 @@ fn kind_to_str(kind: &SymbolKind) -> &'static str {
         SymbolKind::Namespace => "namespace",
         SymbolKind::Function => "function",
         SymbolKind::Method => "method",
+        SymbolKind::Constant => "constant",
+        SymbolKind::Static => "static",
+        SymbolKind::TypeAlias => "type_alias",
+        SymbolKind::Union => "union",
+        SymbolKind::Macro => "macro",
+        SymbolKind::Closure => "closure",
+        SymbolKind::ModuleInit => "module_init",
+        SymbolKind::External => "external",
+    }
+}
+
+fn call_kind_to_str(kind: CallKind) -> &'static str {
+    match kind {
+        CallKind::Call => "call",
+        CallKind::Macro => "macro",
+        CallKind::Defines => "defines",
+        CallKind::Ffi => "ffi",
+        CallKind::Renders => "renders",
     }
 }
 
+fn child_of_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    let child = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == kind);
+    child
+}
+
+fn has_child_kind(node: Node, kind: &str) -> bool {
+    child_of_kind(node, kind).is_some()
+}
+
 fn find_descendant<'a>(node: Node<'a>, kinds: &[&str]) -> Option<Node<'a>> {
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
@@ -557,18 +3084,3 @@ fn find_descendant<'a>(node: Node<'a>, kinds: &[&str]) -> Option<Node<'a>> {
     }
     None
 }
-
-trait LanguageName {
-    fn language_string(&self) -> &'static str;
-}
-
-impl LanguageName for ParseState<'_> {
-    fn language_string(&self) -> &'static str {
-        match self.language {
-            Language::Rust => "rust",
-            Language::JavaScript => "javascript",
-            Language::TypeScript => "typescript",
-            Language::Tsx => "tsx",
-        }
-    }
-}
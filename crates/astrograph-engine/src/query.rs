@@ -0,0 +1,208 @@
+use crate::model::{AnalysisResult, CallEdge, FileInfo, Symbol, SymbolKind};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// In-memory index over an analysis result for interactive graph queries.
+#[derive(Debug, Clone)]
+pub struct AnalysisIndex {
+    result: AnalysisResult,
+    symbols_by_id: HashMap<String, usize>,
+    outgoing: HashMap<String, Vec<usize>>,
+    incoming: HashMap<String, Vec<usize>>,
+}
+
+/// A call edge paired with the symbol on its other end, if resolved.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Neighbor<'a> {
+    pub call: &'a CallEdge,
+    pub symbol: Option<&'a Symbol>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SymbolQuery {
+    pub text: String,
+    pub kind: Option<SymbolKind>,
+    pub file: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl AnalysisIndex {
+    pub fn new(result: AnalysisResult) -> Self {
+        let mut symbols_by_id = HashMap::new();
+        for (index, symbol) in result.symbols.iter().enumerate() {
+            symbols_by_id.insert(symbol.id.clone(), index);
+        }
+
+        let mut outgoing: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut incoming: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, call) in result.calls.iter().enumerate() {
+            outgoing
+                .entry(call.caller_id.clone())
+                .or_default()
+                .push(index);
+            if let Some(callee_id) = &call.callee_id {
+                incoming.entry(callee_id.clone()).or_default().push(index);
+            }
+        }
+
+        Self {
+            result,
+            symbols_by_id,
+            outgoing,
+            incoming,
+        }
+    }
+
+    pub fn result(&self) -> &AnalysisResult {
+        &self.result
+    }
+
+    pub fn files(&self) -> &[FileInfo] {
+        &self.result.files
+    }
+
+    pub fn file(&self, path: &str) -> Option<&FileInfo> {
+        self.result.files.iter().find(|file| file.path == path)
+    }
+
+    pub fn symbol(&self, id: &str) -> Option<&Symbol> {
+        self.symbols_by_id
+            .get(id)
+            .map(|index| &self.result.symbols[*index])
+    }
+
+    pub fn symbols_in_file(&self, path: &str) -> Vec<&Symbol> {
+        self.result
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.file == path)
+            .collect()
+    }
+
+    /// Case-insensitive search over symbol names and fully-qualified names.
+    ///
+    /// Exact name matches rank first, then name prefix matches, then any
+    /// other substring match; ties are broken by `fq_name`.
+    pub fn search(&self, query: &SymbolQuery) -> Vec<&Symbol> {
+        let needle = query.text.trim().to_lowercase();
+        let mut matches: Vec<(u8, &Symbol)> = self
+            .result
+            .symbols
+            .iter()
+            .filter(|symbol| query.kind.as_ref().is_none_or(|kind| &symbol.kind == kind))
+            .filter(|symbol| query.file.as_ref().is_none_or(|file| &symbol.file == file))
+            .filter_map(|symbol| {
+                let name = symbol.name.to_lowercase();
+                let rank = if needle.is_empty() || name == needle {
+                    0
+                } else if name.starts_with(&needle) {
+                    1
+                } else if name.contains(&needle) || symbol.fq_name.to_lowercase().contains(&needle)
+                {
+                    2
+                } else {
+                    return None;
+                };
+                Some((rank, symbol))
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            a.0.cmp(&b.0)
+                .then(a.1.fq_name.cmp(&b.1.fq_name))
+                .then(a.1.id.cmp(&b.1.id))
+        });
+        let limit = query.limit.unwrap_or(usize::MAX);
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, symbol)| symbol)
+            .collect()
+    }
+
+    /// Calls made by the given symbol, including unresolved ones.
+    pub fn callees(&self, id: &str) -> Vec<Neighbor<'_>> {
+        self.outgoing
+            .get(id)
+            .map(|indices| {
+                indices
+                    .iter()
+                    .map(|index| {
+                        let call = &self.result.calls[*index];
+                        Neighbor {
+                            call,
+                            symbol: call.callee_id.as_deref().and_then(|id| self.symbol(id)),
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Resolved calls that target the given symbol.
+    pub fn callers(&self, id: &str) -> Vec<Neighbor<'_>> {
+        self.incoming
+            .get(id)
+            .map(|indices| {
+                indices
+                    .iter()
+                    .map(|index| {
+                        let call = &self.result.calls[*index];
+                        Neighbor {
+                            call,
+                            symbol: self.symbol(&call.caller_id),
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Shortest call path from `from` to `to` following resolved edges.
+    ///
+    /// Returns the symbol ids along the path, including both ends, or `None`
+    /// when `to` is not reachable within `max_depth` calls.
+    pub fn shortest_path(&self, from: &str, to: &str, max_depth: usize) -> Option<Vec<String>> {
+        if self.symbol(from).is_none() || self.symbol(to).is_none() {
+            return None;
+        }
+        if from == to {
+            return Some(vec![from.to_string()]);
+        }
+
+        let mut previous: HashMap<&str, &str> = HashMap::new();
+        let mut visited: HashSet<&str> = HashSet::from([from]);
+        let mut queue: VecDeque<(&str, usize)> = VecDeque::from([(from, 0)]);
+
+        while let Some((current, depth)) = queue.pop_front() {
+            if depth >= max_depth {
+                continue;
+            }
+            let Some(indices) = self.outgoing.get(current) else {
+                continue;
+            };
+            for index in indices {
+                let Some(next) = self.result.calls[*index].callee_id.as_deref() else {
+                    continue;
+                };
+                if !visited.insert(next) {
+                    continue;
+                }
+                previous.insert(next, current);
+                if next == to {
+                    let mut path = vec![to.to_string()];
+                    let mut cursor = to;
+                    while let Some(prev) = previous.get(cursor) {
+                        path.push(prev.to_string());
+                        cursor = prev;
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back((next, depth + 1));
+            }
+        }
+
+        None
+    }
+}
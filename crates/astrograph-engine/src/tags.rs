@@ -0,0 +1,234 @@
+use crate::language::LanguageRegistry;
+use crate::model::{CallKind, Language, SymbolKind};
+use crate::parser::{node_text, ContainerKind};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tree_sitter::{Language as TsLanguage, Node, Query, QueryCursor};
+
+/// A project query file adding patterns to a language's tags query, or
+/// replacing it.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QueryFile {
+    pub language: Language,
+    pub path: PathBuf,
+    /// Replace the bundled query and earlier files instead of adding to them.
+    #[serde(default)]
+    pub replace: bool,
+}
+
+/// What a node captured by a tags query defines or references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TagKind {
+    /// A container and the symbol it defines, if any.
+    Container(ContainerKind, Option<SymbolKind>),
+    Symbol(SymbolKind),
+    Closure,
+    Call(CallKind),
+}
+
+impl TagKind {
+    /// The kind of an `@definition.<kind>` capture.
+    fn definition(kind: &str) -> Option<Self> {
+        let tag = match kind {
+            "module" => TagKind::Container(ContainerKind::Module, Some(SymbolKind::Module)),
+            "namespace" => {
+                TagKind::Container(ContainerKind::Namespace, Some(SymbolKind::Namespace))
+            }
+            "class" => TagKind::Container(ContainerKind::Type, Some(SymbolKind::Class)),
+            "struct" => TagKind::Container(ContainerKind::Type, Some(SymbolKind::Struct)),
+            "enum" => TagKind::Container(ContainerKind::Type, Some(SymbolKind::Enum)),
+            "interface" => TagKind::Container(ContainerKind::Type, Some(SymbolKind::Interface)),
+            "trait" => TagKind::Container(ContainerKind::Type, Some(SymbolKind::Trait)),
+            "impl" => TagKind::Container(ContainerKind::Impl, None),
+            "function" => TagKind::Symbol(SymbolKind::Function),
+            "method" => TagKind::Symbol(SymbolKind::Method),
+            "constant" => TagKind::Symbol(SymbolKind::Constant),
+            "static" => TagKind::Symbol(SymbolKind::Static),
+            "type" => TagKind::Symbol(SymbolKind::TypeAlias),
+            "union" => TagKind::Symbol(SymbolKind::Union),
+            "macro" => TagKind::Symbol(SymbolKind::Macro),
+            "closure" => TagKind::Closure,
+            _ => return None,
+        };
+        Some(tag)
+    }
+
+    pub(crate) fn is_definition(&self) -> bool {
+        !matches!(self, TagKind::Call(_))
+    }
+}
+
+/// A node captured by a tags query.
+#[derive(Debug, Clone)]
+pub(crate) struct Tag {
+    pub(crate) kind: TagKind,
+    /// Text of the `@name` capture.
+    pub(crate) name: Option<String>,
+    /// Whether the pattern comes from a project file. Those tags build
+    /// symbols and calls themselves when the language's analyzer does not
+    /// recognise the node.
+    pub(crate) standalone: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Capture {
+    Tag(TagKind),
+    Name,
+    /// Helper captures and unsupported references such as
+    /// `@reference.class`.
+    Other,
+}
+
+/// The tags query of a language: its analyzer's bundled query, with the
+/// project's query files added or replacing it.
+#[derive(Debug)]
+pub struct TagQuery {
+    query: Query,
+    captures: Vec<Capture>,
+    /// Patterns from this index on come from project files.
+    standalone_from: usize,
+    /// Whether only tagged nodes are definitions and calls. Languages without
+    /// a bundled query keep their analyzer's extraction when patterns are
+    /// only added.
+    pub(crate) exclusive: bool,
+    /// Identifies the query in cache entries.
+    pub(crate) hash: String,
+}
+
+impl TagQuery {
+    /// Tags of the nodes under `root`, by node id. A node matched by several
+    /// patterns keeps the first tag.
+    pub(crate) fn tags(&self, root: Node, source: &[u8]) -> HashMap<usize, Tag> {
+        let mut tags = HashMap::new();
+        let mut cursor = QueryCursor::new();
+        for query_match in cursor.matches(&self.query, root, source) {
+            let mut tagged = None;
+            let mut name = None;
+            for capture in query_match.captures {
+                match &self.captures[capture.index as usize] {
+                    Capture::Tag(kind) if tagged.is_none() => tagged = Some((capture.node, kind)),
+                    Capture::Name => name = Some(node_text(capture.node, source).to_string()),
+                    _ => {}
+                }
+            }
+            if let Some((node, kind)) = tagged {
+                tags.entry(node.id()).or_insert_with(|| Tag {
+                    kind: kind.clone(),
+                    name,
+                    standalone: query_match.pattern_index >= self.standalone_from,
+                });
+            }
+        }
+        tags
+    }
+
+    fn compile(
+        language: Language,
+        grammar: &TsLanguage,
+        bundled: Option<&str>,
+        files: &[(&QueryFile, String)],
+    ) -> Result<Self> {
+        let invalid_bundled = || format!("Invalid bundled {} query", language.name());
+        let mut sources: Vec<&str> = bundled.into_iter().collect();
+        let mut has_bundled = bundled.is_some();
+        let mut exclusive = has_bundled;
+        for (file, source) in files {
+            // Compiled alone first so errors point at the right file.
+            let query = Query::new(grammar, source)
+                .map_err(|err| anyhow!("Invalid query {}: {err}", file.path.display()))?;
+            captures(&query).with_context(|| format!("Invalid query {}", file.path.display()))?;
+            if file.replace {
+                sources.clear();
+                has_bundled = false;
+                exclusive = true;
+            }
+            sources.push(source);
+        }
+
+        let source = sources.join("\n");
+        let query = Query::new(grammar, &source).with_context(invalid_bundled)?;
+        // Added patterns start after the bundled source, which comes first.
+        let standalone_from = match (has_bundled, bundled) {
+            (true, Some(bundled)) => (0..query.pattern_count())
+                .find(|&index| query.start_byte_for_pattern(index) >= bundled.len())
+                .unwrap_or(query.pattern_count()),
+            _ => 0,
+        };
+        let captures = captures(&query).with_context(invalid_bundled)?;
+        let mut hasher = Sha256::new();
+        hasher.update(format!("{exclusive}:{standalone_from}:{source}").as_bytes());
+        Ok(Self {
+            query,
+            captures,
+            standalone_from,
+            exclusive,
+            hash: hex::encode(hasher.finalize()),
+        })
+    }
+}
+
+fn captures(query: &Query) -> Result<Vec<Capture>> {
+    query
+        .capture_names()
+        .iter()
+        .map(|name| {
+            if *name == "name" {
+                return Ok(Capture::Name);
+            }
+            if let Some(kind) = name.strip_prefix("definition.") {
+                return TagKind::definition(kind)
+                    .map(Capture::Tag)
+                    .ok_or_else(|| anyhow!("Unknown capture `@{name}`"));
+            }
+            Ok(match *name {
+                "reference.call" => Capture::Tag(TagKind::Call(CallKind::Call)),
+                "reference.macro" => Capture::Tag(TagKind::Call(CallKind::Macro)),
+                "reference.render" => Capture::Tag(TagKind::Call(CallKind::Renders)),
+                _ => Capture::Other,
+            })
+        })
+        .collect()
+}
+
+/// Compiles the tags query of every registered language that has one.
+pub(crate) fn compile_queries(
+    registry: &LanguageRegistry,
+    files: &[QueryFile],
+) -> Result<HashMap<Language, TagQuery>> {
+    let mut by_language: HashMap<Language, Vec<(&QueryFile, String)>> = HashMap::new();
+    for file in files {
+        if registry.get(file.language).is_none() {
+            bail!(
+                "Unsupported language `{}` in query {}",
+                file.language.name(),
+                file.path.display()
+            );
+        }
+        let source = fs::read_to_string(&file.path)
+            .with_context(|| format!("Failed to read query {}", file.path.display()))?;
+        by_language
+            .entry(file.language)
+            .or_default()
+            .push((file, source));
+    }
+
+    let mut queries = HashMap::new();
+    for language in registry.languages() {
+        let Some(analyzer) = registry.get(language) else {
+            continue;
+        };
+        let files = by_language.remove(&language).unwrap_or_default();
+        let bundled = analyzer.tags_query();
+        if bundled.is_none() && files.is_empty() {
+            continue;
+        }
+        let query = TagQuery::compile(language, &analyzer.grammar(), bundled, &files)?;
+        queries.insert(language, query);
+    }
+    Ok(queries)
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use astrograph_engine::{AnalysisResult, CallKind, EntrypointKind};
+use common::{analyze, analyze_files, find};
+use std::path::PathBuf;
+
+/// `(caller file, callee name, callee fq name)` of every `ffi` edge.
+fn ffi_edges(result: &AnalysisResult) -> Vec<(String, String, Option<String>)> {
+    let mut edges: Vec<_> = result
+        .calls
+        .iter()
+        .filter(|call| call.kind == CallKind::Ffi)
+        .map(|call| {
+            let callee = call.callee_id.as_ref().and_then(|id| {
+                result
+                    .symbols
+                    .iter()
+                    .find(|symbol| &symbol.id == id)
+                    .map(|symbol| symbol.fq_name.clone())
+            });
+            (call.file.clone(), call.callee_name.clone(), callee)
+        })
+        .collect();
+    edges.sort();
+    edges
+}
+
+#[test]
+fn ui_invoke_calls_link_to_tauri_commands() {
+    let ui = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../apps/astrograph-ui");
+    let result = analyze(&ui);
+
+    let edges = ffi_edges(&result);
+    for (file, command) in [
+        ("src/App.tsx", "analyze_project_dir"),
+        ("src/components/SourcePreview.tsx", "read_file_content"),
+        ("src/components/SourcePreview.tsx", "open_file_in_editor"),
+        ("src/utils/exportGraph.ts", "write_export_file"),
+    ] {
+        let expected = (
+            file.to_string(),
+            command.to_string(),
+            Some(format!("astrograph_ui::{command}")),
+        );
+        assert!(edges.contains(&expected), "{expected:?} in {edges:?}");
+    }
+
+    let command = find(&result, "astrograph_ui::analyze_project_dir");
+    assert!(command
+        .entrypoint_kinds
+        .contains(&EntrypointKind::FrameworkHandler));
+    assert!(command
+        .entrypoint_rules
+        .contains(&"tauri-command".to_string()));
+}
+
+#[test]
+fn wasm_bindgen_and_napi_exports_link_from_imports() {
+    let result = analyze_files(&[
+        (
+            "wasm/src/lib.rs",
+            r#"
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub fn greet(name: &str) -> String { name.to_string() }
+
+#[wasm_bindgen(js_name = "parseInput")]
+pub fn parse_input(input: &str) {}
+
+#[wasm_bindgen]
+impl Counter {
+    pub fn increment(&mut self) {}
+    fn hidden(&self) {}
+}
+"#,
+        ),
+        (
+            "addon/src/lib.rs",
+            "#[napi]\npub fn sum_all(values: Vec<u32>) -> u32 { 0 }\n",
+        ),
+        ("addon/package.json", r#"{ "name": "native-addon" }"#),
+        (
+            "web/app.ts",
+            r#"
+import init, { greet, parseInput } from "../wasm/pkg/app";
+import * as addon from "native-addon";
+
+export async function run() {
+    await init();
+    greet("x");
+    parseInput("y");
+    addon.sumAll([1, 2]);
+}
+"#,
+        ),
+    ]);
+
+    assert_eq!(
+        ffi_edges(&result),
+        vec![
+            (
+                "web/app.ts".to_string(),
+                "addon.sumAll".to_string(),
+                Some("addon::src::lib::sum_all".to_string())
+            ),
+            (
+                "web/app.ts".to_string(),
+                "greet".to_string(),
+                Some("wasm::src::lib::greet".to_string())
+            ),
+            (
+                "web/app.ts".to_string(),
+                "parseInput".to_string(),
+                Some("wasm::src::lib::parse_input".to_string())
+            ),
+        ]
+    );
+
+    let rules = |fq_name: &str| find(&result, fq_name).entrypoint_rules.clone();
+    assert!(rules("wasm::src::lib::Counter::increment").contains(&"wasm-bindgen".to_string()));
+    assert!(!rules("wasm::src::lib::Counter::hidden").contains(&"wasm-bindgen".to_string()));
+    assert!(rules("addon::src::lib::sum_all").contains(&"napi".to_string()));
+}
+
+#[test]
+fn binding_exports_only_link_from_their_package() {
+    let result = analyze_files(&[
+        ("addon/Cargo.toml", "[package]\nname = \"native-addon\"\n"),
+        ("addon/package.json", r#"{ "name": "@app/native" }"#),
+        (
+            "addon/src/lib.rs",
+            "#[napi]\npub fn parse(input: String) {}\n#[napi]\npub fn format(input: String) {}\n",
+        ),
+        (
+            "web/package.json",
+            r#"{ "name": "web", "dependencies": { "date-fns": "^3.0.0" } }"#,
+        ),
+        (
+            "web/app.ts",
+            r#"
+import { parse } from "date-fns";
+import { format } from "@app/native";
+
+export function run() {
+    parse("2024-01-01");
+    format("x");
+}
+"#,
+        ),
+    ]);
+
+    assert_eq!(
+        ffi_edges(&result),
+        vec![(
+            "web/app.ts".to_string(),
+            "format".to_string(),
+            Some("native_addon::format".to_string())
+        )]
+    );
+    let run = find(&result, "web::app::run");
+    let parse = result
+        .calls
+        .iter()
+        .find(|call| call.caller_id == run.id && call.callee_name == "parse")
+        .unwrap();
+    assert_eq!(parse.kind, CallKind::Call);
+}
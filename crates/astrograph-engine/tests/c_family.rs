@@ -0,0 +1,273 @@
+mod common;
+
+use astrograph_engine::{AnalysisResult, CallKind, EntrypointKind, Modifier, Symbol, SymbolKind};
+use common::{analyze_files, find};
+
+fn callee_fq<'a>(
+    result: &'a AnalysisResult,
+    caller_fq: &str,
+    callee_name: &str,
+) -> Option<&'a str> {
+    common::callee_fq(result, find(result, caller_fq), callee_name)
+}
+
+const ENGINE_H: &str = r#"
+#pragma once
+#include <string>
+
+namespace app::core {
+
+/// Drives the main loop.
+class Engine {
+public:
+    Engine(int workers);
+    /// Runs the engine.
+    const char *run(int count, const std::string &name = "x");
+    static Engine *create();
+    template <typename T> T get(T value) { return value; }
+private:
+    void reset();
+};
+
+int clamp(int value);
+
+}  // namespace app::core
+"#;
+
+const ENGINE_CPP: &str = r#"
+#include "engine.h"
+
+namespace {
+int hidden() { return 0; }
+}
+
+namespace app::core {
+
+Engine::Engine(int workers) { reset(); }
+
+const char *Engine::run(int count, const std::string &name) {
+    this->reset();
+    clamp(count);
+    auto cb = [](int x) { return hidden(); };
+    return "";
+}
+
+Engine *Engine::create() { return new Engine(1); }
+
+void Engine::reset() {}
+
+int clamp(int value) { return value; }
+
+}  // namespace app::core
+
+int main(int argc, char **argv) {
+    auto *engine = app::core::Engine::create();
+    engine->run(3);
+    return 0;
+}
+"#;
+
+#[test]
+fn cpp_declarations_pair_with_their_definitions() {
+    let result = analyze_files(&[
+        ("include/engine.h", ENGINE_H),
+        ("src/engine.cpp", ENGINE_CPP),
+    ]);
+
+    let engine = find(&result, "app::core::Engine");
+    assert_eq!(engine.kind, SymbolKind::Class);
+    assert_eq!(engine.doc.as_deref(), Some("Drives the main loop."));
+
+    // Header declarations are merged into the out-of-line definitions.
+    let run = find(&result, "app::core::Engine::run");
+    assert_eq!(run.kind, SymbolKind::Method);
+    assert_eq!(run.file, "src/engine.cpp");
+    assert!(!run.declaration);
+    assert!(run.is_exported);
+    assert!(run.entrypoint_kinds.contains(&EntrypointKind::PublicApi));
+    assert_eq!(run.doc.as_deref(), Some("Runs the engine."));
+    assert_eq!(run.return_type.as_deref(), Some("const char *"));
+    let params: Vec<(&str, Option<&str>)> = run
+        .parameters
+        .iter()
+        .map(|param| (param.name.as_str(), param.type_name.as_deref()))
+        .collect();
+    assert_eq!(
+        params,
+        vec![
+            ("count", Some("int")),
+            ("name", Some("const std::string &"))
+        ]
+    );
+    assert_eq!(
+        find(&result, "app::core::Engine::create").modifiers,
+        vec![Modifier::Static]
+    );
+    assert!(!find(&result, "app::core::Engine::reset").is_exported);
+    assert_eq!(
+        find(&result, "app::core::Engine::get").generics.as_deref(),
+        Some("<typename T>")
+    );
+    assert_eq!(find(&result, "app::core::clamp").kind, SymbolKind::Function);
+    assert!(!find(&result, "hidden").is_exported);
+    assert!(find(&result, "main")
+        .entrypoint_kinds
+        .contains(&EntrypointKind::Main));
+    assert!(!result.symbols.iter().any(|symbol| symbol.declaration));
+
+    let run_fq = "app::core::Engine::run";
+    assert_eq!(
+        callee_fq(&result, run_fq, "reset"),
+        Some("app::core::Engine::reset")
+    );
+    assert_eq!(
+        callee_fq(&result, run_fq, "clamp"),
+        Some("app::core::clamp")
+    );
+    assert_eq!(
+        callee_fq(&result, "main", "app::core::Engine::create"),
+        Some("app::core::Engine::create")
+    );
+    assert_eq!(
+        callee_fq(&result, "main", "run"),
+        Some("app::core::Engine::run")
+    );
+    assert!(result.symbols.iter().any(
+        |symbol| symbol.kind == SymbolKind::Closure && symbol.name.starts_with("{closure:cb@")
+    ));
+}
+
+#[test]
+fn rust_extern_declarations_link_to_c_definitions() {
+    let result = analyze_files(&[
+        (
+            "native/math.h",
+            "int add(int a, int b);\nint rust_callback(int value);\n",
+        ),
+        (
+            "native/math.c",
+            r#"
+#include "math.h"
+
+typedef struct { int x; int y; } Point;
+
+static int twice(int v) { return v * 2; }
+
+/** Adds two numbers. */
+int add(int a, int b) { return twice(a) + rust_callback(b); }
+"#,
+        ),
+        (
+            "src/lib.rs",
+            r#"
+extern "C" {
+    pub fn add(a: i32, b: i32) -> i32;
+}
+
+#[no_mangle]
+pub extern "C" fn rust_callback(value: i32) -> i32 {
+    unsafe { add(value, 1) }
+}
+"#,
+        ),
+    ]);
+
+    let add = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.fq_name == "add")
+        .expect("C add exists");
+    assert_eq!(add.file, "native/math.c");
+    assert_eq!(add.doc.as_deref(), Some("Adds two numbers."));
+    assert!(!find(&result, "twice").is_exported);
+    assert_eq!(find(&result, "Point").kind, SymbolKind::Struct);
+
+    let rust_add = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.file == "src/lib.rs" && symbol.name == "add")
+        .expect("Rust declaration exists");
+    assert!(rust_add.declaration);
+    assert_eq!(rust_add.kind, SymbolKind::Function);
+    assert!(rust_add.modifiers.contains(&Modifier::Extern));
+    assert!(!rust_add.is_entrypoint());
+
+    // Each side calls its own declaration, which links to the definition.
+    let rust_callback = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.file == "src/lib.rs" && symbol.name == "rust_callback")
+        .unwrap();
+    let c_callback = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.file == "native/math.h" && symbol.name == "rust_callback")
+        .unwrap();
+    assert!(c_callback.declaration);
+    let ffi: Vec<(&str, &str)> = result
+        .calls
+        .iter()
+        .filter(|call| call.kind == CallKind::Ffi)
+        .map(|call| (call.caller_id.as_str(), call.callee_id.as_deref().unwrap()))
+        .collect();
+    assert_eq!(ffi.len(), 2);
+    assert!(ffi.contains(&(rust_add.id.as_str(), add.id.as_str())));
+    assert!(ffi.contains(&(c_callback.id.as_str(), rust_callback.id.as_str())));
+
+    let call_from = |caller: &Symbol, name: &str| {
+        result
+            .calls
+            .iter()
+            .find(|call| {
+                call.caller_id == caller.id
+                    && call.callee_name == name
+                    && call.kind == CallKind::Call
+            })
+            .and_then(|call| call.callee_id.clone())
+    };
+    assert_eq!(call_from(rust_callback, "add"), Some(rust_add.id.clone()));
+    assert_eq!(call_from(add, "rust_callback"), Some(c_callback.id.clone()));
+}
+
+#[test]
+fn rust_extern_declarations_skip_namespaced_cpp_functions() {
+    let result = analyze_files(&[
+        (
+            "native/engine.cpp",
+            r#"
+namespace ns {
+void init() {}
+extern "C" void start() {}
+class Worker {
+public:
+    void stop() {}
+};
+}
+"#,
+        ),
+        (
+            "src/lib.rs",
+            r#"
+extern "C" {
+    fn init();
+    fn start();
+    fn stop();
+}
+"#,
+        ),
+    ]);
+
+    let ffi: Vec<(&str, Option<&str>)> = result
+        .calls
+        .iter()
+        .filter(|call| call.kind == CallKind::Ffi)
+        .map(|call| {
+            let callee = call
+                .callee_id
+                .as_deref()
+                .map(|id| common::symbol(&result, id).fq_name.as_str());
+            (call.callee_name.as_str(), callee)
+        })
+        .collect();
+    assert_eq!(ffi, vec![("start", Some("ns::start"))]);
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use astrograph_engine::{AnalysisResult, CallKind, SymbolKind};
+use common::{analyze_files, find};
+
+fn callee_names(result: &AnalysisResult, caller_fq: &str) -> Vec<String> {
+    let caller = find(result, caller_fq);
+    let mut names: Vec<String> = result
+        .calls
+        .iter()
+        .filter(|call| call.caller_id == caller.id)
+        .map(|call| call.callee_name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn js_callbacks_become_closure_symbols() {
+    let result = analyze_files(&[(
+        "app.js",
+        r#"
+function render(items) {
+  return items.map((item) => format(item));
+}
+
+const handler = () => save();
+
+button.onclick = function () { track(); };
+"#,
+    )]);
+
+    let closures: Vec<&str> = result
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.kind == SymbolKind::Closure)
+        .map(|symbol| symbol.fq_name.as_str())
+        .collect();
+    assert_eq!(
+        closures,
+        vec![
+            "app::render::{closure:map@3:20}",
+            "app::{closure:onclick@8:18}"
+        ]
+    );
+
+    // The callback's call is attributed to the callback, and the enclosing
+    // function links to the callback.
+    assert_eq!(
+        callee_names(&result, "app::render"),
+        vec!["items.map", "{closure:map@3:20}"]
+    );
+    assert_eq!(
+        callee_names(&result, "app::render::{closure:map@3:20}"),
+        vec!["format"]
+    );
+    let defines = result
+        .calls
+        .iter()
+        .find(|call| call.kind == CallKind::Defines)
+        .expect("defines edge");
+    assert!(defines.callee_id.is_some());
+
+    // Top-level callbacks now own their calls instead of dropping them.
+    assert_eq!(
+        callee_names(&result, "app::{closure:onclick@8:18}"),
+        vec!["track"]
+    );
+    // Named arrow functions stay plain functions.
+    assert_eq!(callee_names(&result, "app::handler"), vec!["save"]);
+}
+
+#[test]
+fn rust_closures_are_nested_under_their_function() {
+    let result = analyze_files(&[(
+        "lib.rs",
+        r#"
+fn spawn_workers(jobs: Vec<Job>) {
+    std::thread::spawn(move || {
+        jobs.iter().for_each(|job: &Job| run(job));
+    });
+}
+"#,
+    )]);
+
+    let spawn = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name.starts_with("{closure:spawn@"))
+        .expect("spawn closure");
+    assert_eq!(spawn.container.as_deref(), Some("spawn_workers"));
+    assert_eq!(spawn.signature.as_deref(), Some("move ||"));
+
+    let for_each = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name.starts_with("{closure:for_each@"))
+        .expect("for_each closure");
+    assert!(for_each.fq_name.starts_with(&spawn.fq_name));
+    assert_eq!(for_each.parameters[0].type_name.as_deref(), Some("&Job"));
+    assert_eq!(callee_names(&result, &for_each.fq_name), vec!["run"]);
+}
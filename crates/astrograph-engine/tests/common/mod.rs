@@ -0,0 +1,78 @@
+//! Fixtures shared by the integration tests: temporary projects and lookups
+//! into analysis results.
+#![allow(dead_code)]
+
+use astrograph_engine::{analyze_project, AnalysisConfig, AnalysisResult, ProgressEvent, Symbol};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A temporary project holding `files`, removed when dropped.
+pub fn project<C: AsRef<[u8]>>(files: &[(&str, C)]) -> TempDir {
+    let root = tempfile::tempdir().expect("create temporary project");
+    write_files(root.path(), files);
+    root
+}
+
+/// Writes `files` under `root`, creating directories as needed.
+pub fn write_files<C: AsRef<[u8]>>(root: &Path, files: &[(&str, C)]) {
+    for (path, contents) in files {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+}
+
+/// Analyzes `root` with the default configuration.
+pub fn analyze(root: &Path) -> AnalysisResult {
+    analyze_with(root, |_| {})
+}
+
+/// Analyzes `root` after `configure` adjusts the default configuration.
+pub fn analyze_with(root: &Path, configure: impl FnOnce(&mut AnalysisConfig)) -> AnalysisResult {
+    let mut config = AnalysisConfig::new(root);
+    configure(&mut config);
+    analyze_project(config, None, None::<fn(ProgressEvent)>)
+        .expect("analyze should succeed")
+        .result
+}
+
+/// Analyzes a temporary project holding `files`.
+pub fn analyze_files(files: &[(&str, &str)]) -> AnalysisResult {
+    analyze(project(files).path())
+}
+
+/// The one symbol named `fq_name`.
+pub fn find<'a>(result: &'a AnalysisResult, fq_name: &str) -> &'a Symbol {
+    let mut symbols = result
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.fq_name == fq_name);
+    let symbol = symbols.next().unwrap_or_else(|| panic!("{fq_name} exists"));
+    assert!(symbols.next().is_none(), "{fq_name} is one symbol");
+    symbol
+}
+
+/// The symbol with `id`.
+pub fn symbol<'a>(result: &'a AnalysisResult, id: &str) -> &'a Symbol {
+    result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.id == id)
+        .unwrap_or_else(|| panic!("symbol {id} exists"))
+}
+
+/// The fq name `caller` resolves its call to `callee_name` to, if resolved.
+pub fn callee_fq<'a>(
+    result: &'a AnalysisResult,
+    caller: &Symbol,
+    callee_name: &str,
+) -> Option<&'a str> {
+    let call = result
+        .calls
+        .iter()
+        .find(|call| call.caller_id == caller.id && call.callee_name == callee_name)
+        .unwrap_or_else(|| panic!("{} calls {callee_name}", caller.fq_name));
+    let callee_id = call.callee_id.as_deref()?;
+    Some(symbol(result, callee_id).fq_name.as_str())
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use astrograph_engine::{EntrypointKind, SymbolKind};
+use common::{analyze_files, callee_fq, find};
+
+#[test]
+fn commonjs_modules_export_and_require() {
+    let result = analyze_files(&[
+        (
+            "scripts/util.js",
+            r#"
+function format(value) {
+    return String(value);
+}
+
+function internal() {}
+
+function request(url) {
+    fetch(url, { onDone: () => internal() });
+}
+
+exports.parse = function (text) {
+    return JSON.parse(text);
+};
+
+module.exports.stringify = (value) => JSON.stringify(value);
+
+module.exports = {
+    format,
+    print: format,
+    log(message) {
+        internal();
+    },
+    warn: (message) => console.warn(message),
+};
+"#,
+        ),
+        (
+            "scripts/build.js",
+            r#"
+const util = require("./util");
+const { parse, format: show } = require("./util");
+const stringify = require("./util").stringify;
+const fs = require("fs");
+
+class Builder {
+    run = () => {
+        util.log(show(parse(fs.readFileSync("a.json", "utf8"))));
+    };
+
+    #write = function (value) {
+        return stringify(value);
+    };
+}
+
+build();
+
+function build() {
+    new Builder().run();
+}
+"#,
+        ),
+    ]);
+
+    let exported = |fq_name: &str| find(&result, fq_name).is_exported;
+    assert!(exported("scripts::util::format"));
+    assert!(exported("scripts::util::parse"));
+    assert!(exported("scripts::util::stringify"));
+    assert!(exported("scripts::util::log"));
+    assert!(exported("scripts::util::warn"));
+    assert!(!exported("scripts::util::internal"));
+    // Names exported through `module.exports` are public API entrypoints.
+    assert!(find(&result, "scripts::util::format")
+        .entrypoint_kinds
+        .contains(&EntrypointKind::PublicApi));
+    assert_eq!(
+        find(&result, "scripts::util::parse").kind,
+        SymbolKind::Function
+    );
+    assert_eq!(find(&result, "scripts::util::log").kind, SymbolKind::Method);
+    let warn = find(&result, "scripts::util::warn");
+    assert_eq!(warn.kind, SymbolKind::Method);
+    assert_eq!(warn.parameters[0].name, "message");
+    // Functions in objects built inside a function stay closures.
+    let on_done = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name.contains("onDone"))
+        .expect("the callback is a symbol");
+    assert_eq!(on_done.kind, SymbolKind::Closure);
+    assert_eq!(
+        callee_fq(&result, on_done, "internal"),
+        Some("scripts::util::internal")
+    );
+
+    // Class fields holding functions are methods.
+    let run = find(&result, "scripts::build::Builder::run");
+    assert_eq!(run.kind, SymbolKind::Method);
+    let write = find(&result, "scripts::build::Builder::#write");
+    assert_eq!(write.kind, SymbolKind::Method);
+    assert!(!result
+        .symbols
+        .iter()
+        .any(|symbol| symbol.kind == SymbolKind::Closure && symbol.file == "scripts/build.js"));
+
+    // `require` bindings resolve like imports, and are not calls themselves.
+    assert_eq!(
+        callee_fq(&result, run, "util.log"),
+        Some("scripts::util::log")
+    );
+    assert_eq!(
+        callee_fq(&result, run, "show"),
+        Some("scripts::util::format")
+    );
+    assert_eq!(
+        callee_fq(&result, run, "parse"),
+        Some("scripts::util::parse")
+    );
+    assert_eq!(
+        callee_fq(&result, write, "stringify"),
+        Some("scripts::util::stringify")
+    );
+    assert!(result
+        .calls
+        .iter()
+        .all(|call| call.callee_name != "require"));
+    let read = result
+        .calls
+        .iter()
+        .find(|call| call.callee_name == "fs.readFileSync")
+        .unwrap();
+    let external = result
+        .symbols
+        .iter()
+        .find(|symbol| Some(&symbol.id) == read.callee_id.as_ref())
+        .expect("the Node built-in is an external symbol");
+    assert_eq!(external.kind, SymbolKind::External);
+}
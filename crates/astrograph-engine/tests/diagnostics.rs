@@ -0,0 +1,92 @@
+mod common;
+
+use astrograph_engine::{
+    analyze_project, AnalysisCache, AnalysisConfig, AnalysisOutput, DiagnosticKind, ProgressEvent,
+};
+use std::path::Path;
+
+fn analyze(root: &Path, cache: Option<AnalysisCache>) -> AnalysisOutput {
+    let mut config = AnalysisConfig::new(root);
+    config.max_file_size = Some(200);
+    analyze_project(config, cache, None::<fn(ProgressEvent)>).expect("analyze should succeed")
+}
+
+const VUE: &str = r#"<template>
+  <p>{{ message }}</p>
+</template>
+
+<script lang="coffee">
+message = "hi"
+</script>
+
+<script>
+export function mounted() {}
+</script>
+"#;
+
+#[test]
+fn incomplete_files_are_reported() {
+    let big = format!("export const data = \"{}\";\n", "x".repeat(300));
+    let files: &[(&str, &[u8])] = &[
+        ("src/ok.ts", b"export function ok() {}\n"),
+        (
+            "src/broken.ts",
+            b"export function first() {}\n\nfunction broken( {\n\nexport function last() {}\n",
+        ),
+        ("src/lib.rs", b"fn run() {\n    let x = 1\n}\n"),
+        ("src/latin1.js", b"// caf\xe9\nfunction greet() {}\n"),
+        ("src/Widget.vue", VUE.as_bytes()),
+        ("src/bundle.js", big.as_bytes()),
+    ];
+    let root = common::project(files);
+
+    let first = analyze(root.path(), None);
+    let second = analyze(root.path(), Some(first.cache.clone()));
+    assert_eq!(second.result.stats.reused_cache_files, 5);
+
+    for output in [first, second] {
+        let result = output.result;
+        assert_eq!(result.stats.diagnostic_count, result.diagnostics.len());
+        let diagnostics = |file: &str| -> Vec<_> {
+            result
+                .diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.file == file)
+                .collect()
+        };
+        assert!(diagnostics("src/ok.ts").is_empty());
+
+        let broken = diagnostics("src/broken.ts");
+        assert!(!broken.is_empty());
+        assert!(broken
+            .iter()
+            .all(|diagnostic| diagnostic.kind == DiagnosticKind::SyntaxError));
+        assert_eq!(broken[0].span.as_ref().unwrap().start_line, 3);
+        // Code outside the error region is still analyzed.
+        assert!(result.symbols.iter().any(|symbol| symbol.name == "first"));
+
+        let rust = diagnostics("src/lib.rs");
+        assert_eq!(rust.len(), 1);
+        assert_eq!(rust[0].message, "Missing `;`");
+        assert_eq!(rust[0].span.as_ref().unwrap().start_line, 2);
+
+        let latin1 = diagnostics("src/latin1.js");
+        assert_eq!(latin1.len(), 1);
+        assert_eq!(latin1[0].kind, DiagnosticKind::Encoding);
+        let span = latin1[0].span.as_ref().unwrap();
+        assert_eq!((span.start_line, span.start_col), (1, 7));
+        assert!(result.symbols.iter().any(|symbol| symbol.name == "greet"));
+
+        let vue = diagnostics("src/Widget.vue");
+        assert_eq!(vue.len(), 1);
+        assert_eq!(vue[0].kind, DiagnosticKind::Unsupported);
+        assert_eq!(vue[0].span.as_ref().unwrap().start_line, 5);
+        assert!(result.symbols.iter().any(|symbol| symbol.name == "mounted"));
+
+        let bundle = diagnostics("src/bundle.js");
+        assert_eq!(bundle.len(), 1);
+        assert_eq!(bundle[0].kind, DiagnosticKind::Oversized);
+        assert!(bundle[0].span.is_none());
+        assert!(result.files.iter().all(|file| file.path != "src/bundle.js"));
+    }
+}
@@ -54,7 +54,7 @@ fn manual_entrypoints_applied() {
         .find(|s| s.name == "helper")
         .expect("helper symbol exists");
     assert!(
-        helper.is_entrypoint,
+        helper.is_entrypoint(),
         "manual entrypoint should mark symbol as entrypoint"
     );
 }
@@ -0,0 +1,182 @@
+mod common;
+
+use astrograph_engine::{
+    analyze_project, AnalysisConfig, AnalysisResult, DiagnosticKind, Language, ProgressEvent,
+    Symbol,
+};
+use common::{analyze, analyze_files, analyze_with, project};
+use std::path::Path;
+
+fn find<'a>(result: &'a AnalysisResult, file: &str, name: &str) -> &'a Symbol {
+    result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.file == file && symbol.name == name)
+        .unwrap_or_else(|| panic!("{name} exists in {file}"))
+}
+
+const VUE: &str = r#"<template>
+  <button @click="save">Save</button>
+</template>
+
+<script setup lang="ts">
+import { persist } from "./store";
+
+function save(): void {
+  persist();
+}
+</script>
+
+<script type="application/ld+json">
+{ "name": "not code" }
+</script>
+"#;
+
+const SVELTE: &str = r#"<script>
+  export let count = 0;
+
+  function increment() {
+    count += 1;
+  }
+</script>
+
+<button on:click={increment}>{count}</button>
+"#;
+
+const ASTRO: &str = r#"---
+import { persist } from "./store";
+
+function load() {
+  persist();
+}
+---
+<html>
+  <script>
+    function hydrate() {}
+  </script>
+</html>
+"#;
+
+const MARKDOWN: &str = r#"# Usage
+
+```ts
+function example(): void {}
+```
+
+```rust
+fn not_analyzed() {}
+```
+"#;
+
+#[test]
+fn component_scripts_are_analyzed_with_original_lines() {
+    let result = analyze_files(&[
+        ("src/Button.vue", VUE),
+        ("src/Counter.svelte", SVELTE),
+        ("src/pages/index.astro", ASTRO),
+        ("src/store.ts", "export function persist() {}\n"),
+        ("README.md", MARKDOWN),
+    ]);
+
+    let language = |path: &str| {
+        result
+            .files
+            .iter()
+            .find(|file| file.path == path)
+            .unwrap_or_else(|| panic!("{path} is analyzed"))
+            .language
+    };
+    assert_eq!(language("src/Button.vue"), Language::Vue);
+    assert_eq!(language("src/Counter.svelte"), Language::Svelte);
+    assert_eq!(language("src/pages/index.astro"), Language::Astro);
+    // Markdown is opt-in.
+    assert!(result.files.iter().all(|file| file.path != "README.md"));
+
+    let save = find(&result, "src/Button.vue", "save");
+    assert_eq!(save.fq_name, "src::Button::save");
+    assert_eq!(save.span.start_line, 8);
+    assert_eq!(
+        find(&result, "src/Counter.svelte", "increment")
+            .span
+            .start_line,
+        4
+    );
+    let load = find(&result, "src/pages/index.astro", "load");
+    assert_eq!(load.span.start_line, 4);
+    assert_eq!(
+        find(&result, "src/pages/index.astro", "hydrate")
+            .span
+            .start_line,
+        10
+    );
+    assert_eq!(
+        result
+            .symbols
+            .iter()
+            .filter(|symbol| symbol.file == "src/Button.vue")
+            .count(),
+        1
+    );
+
+    // Imports resolve from the script blocks.
+    let persist = find(&result, "src/store.ts", "persist");
+    for caller in [save, load] {
+        assert!(result.calls.iter().any(
+            |call| call.caller_id == caller.id && call.callee_id.as_ref() == Some(&persist.id)
+        ));
+    }
+}
+
+#[test]
+fn markdown_code_blocks_are_opt_in() {
+    let result = analyze_with(project(&[("docs/guide.md", MARKDOWN)]).path(), |config| {
+        config.include_markdown = true
+    });
+
+    assert_eq!(result.files.len(), 1);
+    assert_eq!(result.files[0].language, Language::Markdown);
+    let example = find(&result, "docs/guide.md", "example");
+    assert_eq!(example.span.start_line, 4);
+    assert!(result
+        .symbols
+        .iter()
+        .all(|symbol| symbol.name != "not_analyzed"));
+}
+
+#[test]
+fn scripts_after_invalid_utf8_keep_their_positions() {
+    let vue: &[u8] = b"<template>\n  <p>caf\xe9 \xe9\xe9</p>\n</template>\n\n<script>\nexport function mounted() {}\n</script>\n";
+    let result = analyze(project(&[("src/Widget.vue", vue)]).path());
+
+    let mounted = find(&result, "src/Widget.vue", "mounted");
+    assert_eq!(mounted.span.start_line, 6);
+    assert_eq!(mounted.span.start_col, 8);
+    let kinds: Vec<DiagnosticKind> = result
+        .diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.kind)
+        .collect();
+    assert_eq!(kinds, vec![DiagnosticKind::Encoding]);
+}
+
+#[test]
+fn markdown_files_are_ignored_unless_enabled() {
+    let root = project(&[
+        ("README.md", MARKDOWN),
+        ("src/store.ts", "export function persist() {}\n"),
+    ]);
+    let mut config = AnalysisConfig::new(root.path());
+    config.report_skipped = true;
+    assert_eq!(config.detect(Path::new("README.md")), None);
+    let output = analyze_project(config.clone(), None, None::<fn(ProgressEvent)>).unwrap();
+    assert!(output
+        .skipped
+        .iter()
+        .all(|skipped| skipped.path != "README.md"));
+
+    config.include_markdown = true;
+    assert_eq!(
+        config.detect(Path::new("README.md")),
+        Some(Language::Markdown)
+    );
+}
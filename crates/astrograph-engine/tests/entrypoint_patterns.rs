@@ -0,0 +1,114 @@
+mod common;
+
+use astrograph_engine::{
+    analyze_project, AnalysisConfig, EntrypointKind, EntrypointPattern, ProgressEvent, Symbol,
+    SymbolKind,
+};
+use common::project;
+
+fn manual(symbols: &[Symbol]) -> Vec<&str> {
+    let mut names: Vec<&str> = symbols
+        .iter()
+        .filter(|symbol| symbol.entrypoint_kinds.contains(&EntrypointKind::Manual))
+        .map(|symbol| symbol.name.as_str())
+        .collect();
+    names.sort();
+    names
+}
+
+const FILES: &[(&str, &str)] = &[
+    (
+        "src/handlers/users.ts",
+        "function handle_list() {}\nfunction handle_create() {}\nfunction helper() {}\nclass handle_Box {}\n",
+    ),
+    (
+        "src/other.ts",
+        "function handle_other() {}\nfunction onClick() {}\nfunction onclick() {}\n",
+    ),
+];
+
+fn analyze(config: AnalysisConfig) -> Vec<Symbol> {
+    analyze_project(config, None, None::<fn(ProgressEvent)>)
+        .expect("analyze should succeed")
+        .result
+        .symbols
+}
+
+#[test]
+fn parse_splits_kind_file_and_name() {
+    assert_eq!(
+        EntrypointPattern::parse("function:src/handlers/**::handle_*"),
+        EntrypointPattern {
+            name: "handle_*".to_string(),
+            file: Some("src/handlers/**".to_string()),
+            kind: Some(SymbolKind::Function),
+        }
+    );
+    assert_eq!(
+        EntrypointPattern::parse("app::main"),
+        EntrypointPattern {
+            name: "app::main".to_string(),
+            file: None,
+            kind: None,
+        }
+    );
+    assert_eq!(EntrypointPattern::parse("re:a::b").name, "re:a::b");
+}
+
+#[test]
+fn glob_regex_file_and_kind_patterns_mark_entrypoints() {
+    let root = project(FILES);
+    let mut config = AnalysisConfig::new(root.path());
+    config.manual_entrypoints = vec![
+        "function:src/handlers/**::handle_*".to_string(),
+        "re:on[A-Z]\\w*".to_string(),
+    ];
+    let symbols = analyze(config);
+
+    assert_eq!(
+        manual(&symbols),
+        vec!["handle_create", "handle_list", "onClick"]
+    );
+}
+
+#[test]
+fn project_config_file_declares_entrypoint_rules() {
+    let mut files = FILES.to_vec();
+    files.push((
+        "astrograph.toml",
+        r#"
+[entrypoints]
+patterns = ["helper"]
+
+[[entrypoints.rules]]
+name = "handle_*"
+file = "src/*.ts"
+"#,
+    ));
+    let root = project(&files);
+    let config = AnalysisConfig::for_project(root.path()).expect("config loads");
+    let symbols = analyze(config);
+
+    assert_eq!(manual(&symbols), vec!["handle_other", "helper"]);
+}
+
+#[test]
+fn names_without_wildcards_match_literally() {
+    let root = project(&[("src/server.js", "start();\nfunction start() {}\n")]);
+    let mut config = AnalysisConfig::new(root.path());
+    config.manual_entrypoints = vec!["{init}".to_string()];
+    let symbols = analyze(config);
+
+    assert_eq!(manual(&symbols), vec!["{init}"]);
+}
+
+#[test]
+fn invalid_patterns_are_reported() {
+    let root = project(FILES);
+    let mut config = AnalysisConfig::new(root.path());
+    config.manual_entrypoints = vec!["re:(".to_string()];
+    let result = analyze_project(config, None, None::<fn(ProgressEvent)>);
+
+    let err = result.expect_err("invalid regex should fail");
+    assert!(err.to_string().contains("Invalid entrypoint regex"));
+}
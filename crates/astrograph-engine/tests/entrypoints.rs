@@ -0,0 +1,172 @@
+mod common;
+
+use astrograph_engine::{EntrypointKind, Symbol};
+use common::analyze_files;
+
+fn symbols_of(files: &[(&str, &str)]) -> Vec<Symbol> {
+    analyze_files(files).symbols
+}
+
+fn find<'a>(symbols: &'a [Symbol], name: &str) -> &'a Symbol {
+    symbols
+        .iter()
+        .find(|symbol| symbol.name == name)
+        .unwrap_or_else(|| panic!("symbol {name} exists"))
+}
+
+fn rules<'a>(symbols: &'a [Symbol], name: &str) -> Vec<&'a str> {
+    find(symbols, name)
+        .entrypoint_rules
+        .iter()
+        .map(String::as_str)
+        .collect()
+}
+
+#[test]
+fn rust_attributes_mark_entrypoints_with_their_rule() {
+    let symbols = symbols_of(&[(
+        "main.rs",
+        r#"
+#[tokio::main]
+async fn main() {}
+
+#[actix_web::get("/health")]
+async fn health() -> &'static str { "ok" }
+
+#[no_mangle]
+pub extern "C" fn astro_init() {}
+
+#[unsafe(no_mangle)]
+extern "C" fn astro_free() {}
+
+fn helper() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {}
+}
+"#,
+    )]);
+
+    assert_eq!(rules(&symbols, "main"), vec!["main", "async-main"]);
+    assert_eq!(rules(&symbols, "health"), vec!["http-handler"]);
+    assert_eq!(
+        rules(&symbols, "astro_init"),
+        vec!["exported", "ffi-export"]
+    );
+    assert_eq!(rules(&symbols, "astro_free"), vec!["ffi-export"]);
+    assert_eq!(rules(&symbols, "it_works"), vec!["rust-test"]);
+    assert!(rules(&symbols, "helper").is_empty());
+    assert!(!find(&symbols, "helper").is_entrypoint());
+
+    let init = find(&symbols, "astro_init");
+    assert_eq!(
+        init.entrypoint_kinds,
+        vec![EntrypointKind::PublicApi, EntrypointKind::FfiExport]
+    );
+    assert!(init.is_program_entrypoint());
+    assert_eq!(
+        find(&symbols, "health").entrypoint_kinds,
+        vec![EntrypointKind::FrameworkHandler]
+    );
+    assert_eq!(
+        find(&symbols, "it_works").entrypoint_kinds,
+        vec![EntrypointKind::Test]
+    );
+}
+
+#[test]
+fn public_api_is_not_a_program_entrypoint() {
+    let symbols = symbols_of(&[("lib.rs", "pub fn parse() {}\nfn main() {}\n")]);
+
+    let parse = find(&symbols, "parse");
+    assert_eq!(parse.entrypoint_kinds, vec![EntrypointKind::PublicApi]);
+    assert!(parse.is_entrypoint());
+    assert!(!parse.is_program_entrypoint());
+    assert_eq!(
+        find(&symbols, "main").entrypoint_kinds,
+        vec![EntrypointKind::Main]
+    );
+}
+
+#[test]
+fn js_registrations_mark_handlers_and_components() {
+    let symbols = symbols_of(&[
+        (
+            "server.js",
+            r#"
+const { listUsers } = require("./handlers");
+app.get("/users", listUsers);
+app.post("/users", (req, res) => res.send(create(req.body)));
+const cache = new Map();
+cache.get(key);
+function key() {}
+"#,
+        ),
+        ("handlers.js", "function listUsers(req, res) {}\n"),
+        (
+            "index.tsx",
+            r#"
+function App() { return <div />; }
+createRoot(document.getElementById("root")).render(<React.StrictMode><App /></React.StrictMode>);
+"#,
+        ),
+        (
+            "app.test.ts",
+            r#"
+describe("app", () => {
+  it("renders", () => {});
+});
+"#,
+        ),
+    ]);
+
+    assert_eq!(rules(&symbols, "listUsers"), vec!["express-route"]);
+    assert!(rules(&symbols, "key").is_empty());
+    assert_eq!(
+        rules(&symbols, "{closure:post@4:20}"),
+        vec!["express-route"]
+    );
+    assert_eq!(rules(&symbols, "App"), vec!["react-root"]);
+    assert_eq!(rules(&symbols, "{closure:describe@2:17}"), vec!["js-test"]);
+    assert_eq!(rules(&symbols, "{closure:it@3:17}"), vec!["js-test"]);
+}
+
+#[test]
+fn route_handlers_resolve_like_calls() {
+    let result = analyze_files(&[
+        (
+            "server.js",
+            r#"
+import { handler } from "./routes/users";
+import * as posts from "./routes/posts";
+app.get("/users", handler);
+app.get("/posts", posts.list);
+app.get("/health", health);
+app.get("/cleanup", cleanup);
+"#,
+        ),
+        ("routes/users.js", "export function handler(req, res) {}\n"),
+        (
+            "routes/posts.js",
+            "export function handler(req, res) {}\nexport function list(req, res) {}\n",
+        ),
+        ("routes/status.js", "function health(req, res) {}\n"),
+        ("jobs/a.js", "function cleanup() {}\n"),
+        ("jobs/b.js", "function cleanup() {}\n"),
+    ]);
+
+    let is_route = |fq_name: &str| {
+        common::find(&result, fq_name)
+            .entrypoint_rules
+            .contains(&"express-route".to_string())
+    };
+    assert!(is_route("routes::users::handler"));
+    assert!(!is_route("routes::posts::handler"));
+    assert!(is_route("routes::posts::list"));
+    // Unimported names resolve only when unambiguous.
+    assert!(is_route("routes::status::health"));
+    assert!(!is_route("jobs::a::cleanup"));
+    assert!(!is_route("jobs::b::cleanup"));
+}
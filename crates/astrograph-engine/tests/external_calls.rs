@@ -0,0 +1,167 @@
+mod common;
+
+use astrograph_engine::{AnalysisResult, SymbolKind};
+use common::{analyze_files, symbol};
+
+/// Name of the symbol each call to `callee_name` resolves to.
+fn callee(result: &AnalysisResult, callee_name: &str) -> Option<String> {
+    let call = result
+        .calls
+        .iter()
+        .find(|call| call.callee_name == callee_name)
+        .unwrap_or_else(|| panic!("call to {callee_name} exists"));
+    let callee_id = call.callee_id.as_ref()?;
+    let symbol = symbol(result, callee_id);
+    Some(format!("{:?}:{}", symbol.kind, symbol.name))
+}
+
+#[test]
+fn unresolved_library_calls_point_at_external_symbols() {
+    let result = analyze_files(&[
+        (
+            "Cargo.toml",
+            "[package]\nname = \"app\"\n\n[dependencies]\nreqwest = \"0.12\"\nserde-json = \"1\"\n",
+        ),
+        (
+            "src/main.rs",
+            r#"
+use std::fs;
+use reqwest::Client as Http;
+
+fn main() {
+    let text = fs::read_to_string("a.txt");
+    let client = Http::new();
+    reqwest::get("https://example.com");
+    serde_json::to_string(&text);
+    println!("{text:?}");
+    helper();
+    rand::random();
+    unknown();
+}
+
+fn helper() {}
+"#,
+        ),
+        ("web/local.ts", "export function local() {}\n"),
+        (
+            "web/app.ts",
+            r#"
+import { debounce } from "lodash/fp";
+import * as fs from "node:fs";
+import { local } from "./local";
+import { stringify } from "serde_json";
+
+export function run() {
+  debounce(local);
+  stringify(local);
+  fs.readFileSync("a");
+  console.log("ok");
+  local();
+  later();
+}
+"#,
+        ),
+    ]);
+
+    assert_eq!(
+        callee(&result, "fs::read_to_string").as_deref(),
+        Some("External:std")
+    );
+    assert_eq!(
+        callee(&result, "Http::new").as_deref(),
+        Some("External:reqwest")
+    );
+    assert_eq!(
+        callee(&result, "reqwest::get").as_deref(),
+        Some("External:reqwest")
+    );
+    assert_eq!(
+        callee(&result, "serde_json::to_string").as_deref(),
+        Some("External:serde_json")
+    );
+    assert_eq!(callee(&result, "println").as_deref(), Some("External:std"));
+    assert_eq!(
+        callee(&result, "helper").as_deref(),
+        Some("Function:helper")
+    );
+    // Not a declared dependency, nor anything the analysis knows about.
+    assert_eq!(callee(&result, "rand::random"), None);
+    assert_eq!(callee(&result, "unknown"), None);
+
+    assert_eq!(
+        callee(&result, "debounce").as_deref(),
+        Some("External:lodash")
+    );
+    assert_eq!(
+        callee(&result, "fs.readFileSync").as_deref(),
+        Some("External:fs")
+    );
+    assert_eq!(
+        callee(&result, "console.log").as_deref(),
+        Some("External:builtins")
+    );
+    assert_eq!(callee(&result, "local").as_deref(), Some("Function:local"));
+    assert_eq!(callee(&result, "later"), None);
+
+    let externals: Vec<&str> = result
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.kind == SymbolKind::External)
+        .map(|symbol| symbol.fq_name.as_str())
+        .collect();
+    assert_eq!(
+        externals,
+        vec![
+            "{external}::js::builtins",
+            "{external}::js::fs",
+            "{external}::js::lodash",
+            "{external}::js::serde_json",
+            "{external}::rust::reqwest",
+            "{external}::rust::serde_json",
+            "{external}::rust::std",
+        ]
+    );
+}
+
+#[test]
+fn workspace_path_dependencies_are_not_external() {
+    let result = analyze_files(&[
+        (
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.dependencies]\nshared-core = { path = \"crates/shared\" }\n",
+        ),
+        (
+            "crates/shared/Cargo.toml",
+            "[package]\nname = \"shared-core\"\n",
+        ),
+        ("crates/shared/src/lib.rs", "pub fn load() {}\n"),
+        (
+            "crates/app/Cargo.toml",
+            "[package]\nname = \"app\"\n\n[dependencies]\nshared-core = { workspace = true }\nanyhow = \"1\"\n",
+        ),
+        (
+            "crates/app/src/main.rs",
+            r#"
+fn main() {
+    shared_core::load();
+    shared_core::Settings::new().unwrap();
+    anyhow::bail!("x");
+}
+"#,
+        ),
+    ]);
+
+    assert_eq!(
+        callee(&result, "shared_core::load").as_deref(),
+        Some("Function:load")
+    );
+    assert_eq!(callee(&result, "shared_core::Settings::new"), None);
+
+    let externals: Vec<&str> = result
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.kind == SymbolKind::External)
+        .map(|symbol| symbol.fq_name.as_str())
+        .collect();
+    assert_eq!(externals, vec!["{external}::rust::anyhow"]);
+}
@@ -1,4 +1,6 @@
-use astrograph_engine::{analyze_project, AnalysisConfig};
+mod common;
+
+use astrograph_engine::{analyze_project, AnalysisConfig, WatchFilter};
 use std::fs;
 use std::path::PathBuf;
 
@@ -161,3 +163,93 @@ fn both_gitignore_and_astrographignore_are_honored() {
 
     let _ = fs::remove_dir_all(&root);
 }
+
+#[test]
+fn built_in_excludes_are_configurable_and_reported() {
+    let root = common::project(&[
+        ("src/main.ts", "export function main() {}\n"),
+        ("build/gen.ts", "export function gen() {}\n"),
+        ("vendor/lib.ts", "export function lib() {}\n"),
+        (".config/setup.ts", "export function setup() {}\n"),
+    ]);
+
+    let paths = |config: AnalysisConfig| {
+        let out = analyze_project(config, None, None::<fn(astrograph_engine::ProgressEvent)>)
+            .expect("analyze should succeed");
+        let mut files: Vec<String> = out.result.files.into_iter().map(|f| f.path).collect();
+        files.sort();
+        (files, out.skipped)
+    };
+
+    let mut config = AnalysisConfig::new(root.path());
+    config.report_skipped = true;
+    let (files, skipped) = paths(config);
+    assert_eq!(files, vec!["src/main.ts", "vendor/lib.ts"]);
+    let rule_for = |path: &str| {
+        skipped
+            .iter()
+            .find(|entry| entry.path == path)
+            .map(|entry| entry.rule.as_str())
+    };
+    assert_eq!(rule_for("build"), Some("ignored-dir:build"));
+    assert_eq!(rule_for(".config"), Some("hidden"));
+
+    let mut config = AnalysisConfig::new(root.path());
+    config.ignored_dirs.retain(|name| name != "build");
+    config.ignored_dirs.push("vendor".to_string());
+    config.include_hidden = true;
+    let (files, skipped) = paths(config);
+    assert_eq!(
+        files,
+        vec![".config/setup.ts", "build/gen.ts", "src/main.ts"]
+    );
+    assert!(
+        skipped.is_empty(),
+        "skipped paths are only recorded on request"
+    );
+}
+
+#[test]
+fn watch_filter_agrees_with_the_file_walk() {
+    let files: &[(&str, &str)] = &[
+        (".git/HEAD", "ref: refs/heads/main\n"),
+        (".gitignore", "out/\n"),
+        (".astrographignore", "generated.ts\n"),
+        ("astrograph.toml", "[files]\nexclude = [\"**/*.test.ts\"]\n"),
+        ("src/main.ts", "export function main() {}\n"),
+        ("src/main.test.ts", "function check() {}\n"),
+        ("src/generated.ts", "export function generated() {}\n"),
+        ("src/.gitignore", "local.ts\n"),
+        ("src/local.ts", "export function local() {}\n"),
+        ("out/bundle.js", "function bundle() {}\n"),
+        ("target/debug/build.rs", "fn main() {}\n"),
+        ("node_modules/pkg/index.js", "function pkg() {}\n"),
+        (".cache/tmp.ts", "function tmp() {}\n"),
+        ("docs/guide.md", "```ts\nfunction guide() {}\n```\n"),
+        ("README.txt", "notes\n"),
+    ];
+    let root = common::project(files);
+    let config = AnalysisConfig::for_project(root.path()).expect("config loads");
+    let analyzed: Vec<String> = analyze_project(
+        config.clone(),
+        None,
+        None::<fn(astrograph_engine::ProgressEvent)>,
+    )
+    .expect("analyze should succeed")
+    .result
+    .files
+    .into_iter()
+    .map(|file| file.path)
+    .collect();
+    assert_eq!(analyzed, vec!["src/main.ts"]);
+
+    let filter = WatchFilter::new(&config).expect("filter builds");
+    let root = root.path().canonicalize().unwrap();
+    let relevant: Vec<&str> = files
+        .iter()
+        .map(|(path, _)| *path)
+        .filter(|path| filter.is_relevant(&root.join(path)))
+        .collect();
+    assert_eq!(relevant, vec!["src/main.ts"]);
+    assert!(filter.is_relevant(&root.join("src/deleted.ts")));
+}
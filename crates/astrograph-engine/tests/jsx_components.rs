@@ -0,0 +1,67 @@
+mod common;
+
+use astrograph_engine::CallKind;
+use common::{analyze_files, symbol};
+
+#[test]
+fn jsx_elements_render_components() {
+    let result = analyze_files(&[
+            (
+                "src/App.tsx",
+                r#"
+import { UserCard } from "./UserCard";
+import * as Menu from "./Menu";
+
+export function App({ users }: { users: User[] }) {
+    return (
+        <div className="app">
+            {users.map((u) => <UserCard user={u} />)}
+            <Menu.Item label="Settings">
+                <span>more</span>
+            </Menu.Item>
+        </div>
+    );
+}
+"#,
+            ),
+            (
+                "src/UserCard.tsx",
+                "export function UserCard({ user }) {\n    return <p>{user.name}</p>;\n}\n",
+            ),
+            (
+                "src/Menu.jsx",
+                "export function Item({ label, children }) {\n    return <li>{label}{children}</li>;\n}\n",
+            ),
+        ],
+    );
+
+    let mut renders: Vec<(&str, Option<&str>)> = result
+        .calls
+        .iter()
+        .filter(|call| call.kind == CallKind::Renders)
+        .map(|call| {
+            let callee = call
+                .callee_id
+                .as_deref()
+                .map(|id| symbol(&result, id).fq_name.as_str());
+            (call.callee_name.as_str(), callee)
+        })
+        .collect();
+    renders.sort();
+    assert_eq!(
+        renders,
+        vec![
+            ("Menu.Item", Some("src::Menu::Item")),
+            ("UserCard", Some("src::UserCard::UserCard")),
+        ]
+    );
+
+    // The element inside the callback is rendered by the closure.
+    let user_card = result
+        .calls
+        .iter()
+        .find(|call| call.callee_name == "UserCard")
+        .unwrap();
+    let caller = symbol(&result, &user_card.caller_id);
+    assert!(caller.fq_name.starts_with("src::App::App::"));
+}
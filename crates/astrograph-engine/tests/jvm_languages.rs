@@ -0,0 +1,209 @@
+mod common;
+
+use astrograph_engine::{AnalysisResult, EntrypointKind, Modifier, SymbolKind};
+use common::{analyze_files, find};
+
+fn callee_fq<'a>(
+    result: &'a AnalysisResult,
+    caller_fq: &str,
+    callee_name: &str,
+) -> Option<&'a str> {
+    common::callee_fq(result, find(result, caller_fq), callee_name)
+}
+
+#[test]
+fn java_symbols_use_package_names() {
+    let result = analyze_files(&[
+            (
+                "src/main/java/com/example/app/Greeter.java",
+                r#"
+package com.example.app;
+
+import com.example.util.Strings;
+
+/** Greets people. */
+@Service
+public class Greeter<T> extends Base {
+    private final List<String> names = new ArrayList<>();
+
+    public Greeter(String name) { super(name); }
+
+    /** Says hello. */
+    @Override
+    public static <U> String greet(final String who, int... times) {
+        Strings.trim(who);
+        helper();
+        names.forEach(item -> log(item));
+        return new Greeter<>("x").toString();
+    }
+
+    private void helper() {}
+
+    interface Listener { void onEvent(); }
+    enum Color { RED; void paint() {} }
+    record Point(int x, int y) {}
+
+    public static void main(String[] args) {}
+}
+"#,
+            ),
+            (
+                "src/main/java/com/example/util/Strings.java",
+                "package com.example.util;\n\npublic final class Strings {\n    public static String trim(String value) { return value; }\n}\n",
+            ),
+        ],
+    );
+
+    let greeter = find(&result, "com::example::app::Greeter");
+    assert_eq!(greeter.kind, SymbolKind::Class);
+    assert_eq!(greeter.doc.as_deref(), Some("Greets people."));
+    assert_eq!(
+        greeter.signature.as_deref(),
+        Some("public class Greeter<T> extends Base")
+    );
+    assert_eq!(
+        find(&result, "com::example::app").kind,
+        SymbolKind::Namespace
+    );
+    assert_eq!(
+        find(&result, "com::example::app::Greeter::Greeter").kind,
+        SymbolKind::Method
+    );
+    assert_eq!(
+        find(&result, "com::example::app::Greeter::Listener").kind,
+        SymbolKind::Interface
+    );
+    assert_eq!(
+        find(&result, "com::example::app::Greeter::Color").kind,
+        SymbolKind::Enum
+    );
+    find(&result, "com::example::app::Greeter::Color::paint");
+    find(&result, "com::example::app::Greeter::Point");
+
+    let greet = find(&result, "com::example::app::Greeter::greet");
+    assert_eq!(greet.kind, SymbolKind::Method);
+    assert_eq!(
+        greet.signature.as_deref(),
+        Some("public static <U> String greet(final String who, int... times)")
+    );
+    assert_eq!(greet.return_type.as_deref(), Some("String"));
+    assert_eq!(greet.generics.as_deref(), Some("<U>"));
+    assert_eq!(greet.modifiers, vec![Modifier::Static]);
+    assert_eq!(greet.doc.as_deref(), Some("Says hello."));
+    let params: Vec<(&str, Option<&str>)> = greet
+        .parameters
+        .iter()
+        .map(|param| (param.name.as_str(), param.type_name.as_deref()))
+        .collect();
+    assert_eq!(
+        params,
+        vec![("who", Some("String")), ("times", Some("int..."))]
+    );
+
+    let greet_fq = "com::example::app::Greeter::greet";
+    assert_eq!(
+        callee_fq(&result, greet_fq, "Strings.trim"),
+        Some("com::example::util::Strings::trim")
+    );
+    assert_eq!(
+        callee_fq(&result, greet_fq, "helper"),
+        Some("com::example::app::Greeter::helper")
+    );
+    assert_eq!(
+        callee_fq(&result, greet_fq, "Greeter"),
+        Some("com::example::app::Greeter")
+    );
+    assert!(result
+        .symbols
+        .iter()
+        .any(|symbol| symbol.kind == SymbolKind::Closure
+            && symbol.name.starts_with("{closure:forEach@")));
+
+    let main = find(&result, "com::example::app::Greeter::main");
+    assert!(main.entrypoint_kinds.contains(&EntrypointKind::Main));
+    assert!(!find(&result, "com::example::app::Greeter::helper").is_entrypoint());
+}
+
+#[test]
+fn kotlin_symbols_use_package_names() {
+    let result = analyze_files(&[(
+        "app/src/Greeter.kt",
+        r#"
+package com.example.app
+
+import com.example.util.log
+
+/** Greets people. */
+class Greeter(private val name: String) : Base(name) {
+    constructor(id: Int) : this("x")
+
+    suspend fun greet(who: String): String {
+        helper(who)
+        items.map { it.trim() }
+        return Registry.register()
+    }
+
+    private fun helper(value: String) {}
+}
+
+interface Shape { fun area(): Double }
+enum class Color { RED; fun paint() {} }
+object Registry { fun register(): String = "" }
+
+val handler = { x: Int -> x + 1 }
+
+fun main() { Greeter("x").greet("a") }
+"#,
+    )]);
+
+    let greeter = find(&result, "com::example::app::Greeter");
+    assert_eq!(greeter.kind, SymbolKind::Class);
+    assert_eq!(greeter.doc.as_deref(), Some("Greets people."));
+    let params: Vec<&str> = greeter
+        .parameters
+        .iter()
+        .map(|param| param.name.as_str())
+        .collect();
+    assert_eq!(params, vec!["name"]);
+    find(&result, "com::example::app::Greeter::Greeter");
+    assert_eq!(
+        find(&result, "com::example::app::Shape").kind,
+        SymbolKind::Interface
+    );
+    assert_eq!(
+        find(&result, "com::example::app::Color").kind,
+        SymbolKind::Enum
+    );
+    assert_eq!(
+        find(&result, "com::example::app::handler").kind,
+        SymbolKind::Function
+    );
+
+    let greet = find(&result, "com::example::app::Greeter::greet");
+    assert_eq!(greet.kind, SymbolKind::Method);
+    assert_eq!(greet.return_type.as_deref(), Some("String"));
+    assert_eq!(greet.modifiers, vec![Modifier::Async]);
+    assert!(greet.is_exported);
+    assert!(!find(&result, "com::example::app::Greeter::helper").is_exported);
+
+    let greet_fq = "com::example::app::Greeter::greet";
+    assert_eq!(
+        callee_fq(&result, greet_fq, "helper"),
+        Some("com::example::app::Greeter::helper")
+    );
+    assert_eq!(
+        callee_fq(&result, greet_fq, "Registry.register"),
+        Some("com::example::app::Registry::register")
+    );
+    assert!(result.symbols.iter().any(
+        |symbol| symbol.kind == SymbolKind::Closure && symbol.name.starts_with("{closure:map@")
+    ));
+    assert_eq!(
+        callee_fq(&result, "com::example::app::main", "Greeter"),
+        Some("com::example::app::Greeter")
+    );
+    assert_eq!(
+        callee_fq(&result, "com::example::app::main", "greet"),
+        Some("com::example::app::Greeter::greet")
+    );
+}
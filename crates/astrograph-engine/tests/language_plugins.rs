@@ -0,0 +1,127 @@
+mod common;
+
+use astrograph_engine::tree_sitter::{Language as TsLanguage, Node};
+use astrograph_engine::{
+    analyze_project, AnalysisConfig, AnalysisResult, CallKind, EntrypointKind, Language,
+    LanguageAnalyzer, ParseContext, ProgressEvent, Symbol, SymbolKind,
+};
+use common::{analyze_with, project};
+
+/// Arduino sketches: C++ where `setup` and `loop` are called by the runtime.
+struct ArduinoAnalyzer;
+
+impl LanguageAnalyzer for ArduinoAnalyzer {
+    fn language(&self) -> Language {
+        Language::from_name("arduino")
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["ino"]
+    }
+
+    fn grammar(&self) -> TsLanguage {
+        tree_sitter_cpp::language()
+    }
+
+    fn function(&self, node: Node, cx: &mut ParseContext<'_>) -> Option<Symbol> {
+        if node.kind() != "function_definition" {
+            return None;
+        }
+        let declarator = node.child_by_field_name("declarator")?;
+        let name = declarator.child_by_field_name("declarator")?;
+        let name = name.utf8_text(cx.source()).ok()?;
+        let mut symbol = cx.new_symbol(name, SymbolKind::Function, node, false);
+        if matches!(name, "setup" | "loop") {
+            symbol.mark_entrypoint(EntrypointKind::FrameworkHandler, "arduino");
+        }
+        Some(symbol)
+    }
+
+    fn call(&self, node: Node, source: &[u8]) -> Option<(String, CallKind)> {
+        if node.kind() != "call_expression" {
+            return None;
+        }
+        let function = node.child_by_field_name("function")?;
+        Some((function.utf8_text(source).ok()?.to_string(), CallKind::Call))
+    }
+}
+
+fn analyze(files: &[(&str, &str)], config: impl FnOnce(&mut AnalysisConfig)) -> AnalysisResult {
+    analyze_with(project(files).path(), |analysis_config| {
+        analysis_config.analyzers.register(ArduinoAnalyzer);
+        config(analysis_config);
+    })
+}
+
+const SKETCH: &str = r#"
+void blink(int pin) {}
+
+void setup() {}
+
+void loop() {
+    blink(13);
+}
+"#;
+
+#[test]
+fn registered_analyzers_add_languages() {
+    let result = analyze(
+        &[
+            ("sketch/sketch.ino", SKETCH),
+            ("src/lib.rs", "pub fn run() {}\n"),
+        ],
+        |_| {},
+    );
+
+    let sketch = result
+        .files
+        .iter()
+        .find(|file| file.path == "sketch/sketch.ino")
+        .expect("sketch is analyzed");
+    assert_eq!(sketch.language.name(), "arduino");
+    assert_eq!(
+        serde_json::to_value(sketch.language).unwrap(),
+        serde_json::json!("arduino")
+    );
+
+    let find = |name: &str| {
+        result
+            .symbols
+            .iter()
+            .find(|symbol| symbol.file == "sketch/sketch.ino" && symbol.name == name)
+            .unwrap_or_else(|| panic!("{name} exists"))
+    };
+    let (blink, looping) = (find("blink"), find("loop"));
+    assert_eq!(blink.fq_name, "sketch::sketch::blink");
+    assert!(looping.entrypoint_rules.contains(&"arduino".to_string()));
+    assert!(!blink.is_entrypoint());
+    let call = result
+        .calls
+        .iter()
+        .find(|call| call.caller_id == looping.id && call.callee_name == "blink")
+        .expect("loop calls blink");
+    assert_eq!(call.callee_id.as_ref(), Some(&blink.id));
+
+    // Built-in languages are still analyzed.
+    assert!(result
+        .symbols
+        .iter()
+        .any(|symbol| symbol.file == "src/lib.rs" && symbol.name == "run"));
+}
+
+#[test]
+fn language_filter_accepts_registered_languages_only() {
+    let result = analyze(
+        &[("sketch.ino", SKETCH), ("lib.rs", "pub fn run() {}\n")],
+        |config| config.languages = vec![Language::from_name("arduino")],
+    );
+    assert_eq!(result.files.len(), 1);
+    assert_eq!(result.files[0].path, "sketch.ino");
+
+    let root = project::<&str>(&[]);
+    let mut config = AnalysisConfig::new(root.path());
+    config.languages = vec![Language::from_name("cobol")];
+    let err = analyze_project(config, None, None::<fn(ProgressEvent)>)
+        .expect_err("unknown languages are rejected");
+    assert!(err.to_string().contains("cobol"), "{err}");
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use astrograph_engine::{analyze_project, AnalysisConfig, AnalysisResult, ProgressEvent, Symbol};
+use common::{analyze_with, project, symbol, write_files};
+
+fn callee<'a>(result: &'a AnalysisResult, callee_name: &str) -> &'a Symbol {
+    let call = result
+        .calls
+        .iter()
+        .find(|call| call.callee_name == callee_name)
+        .unwrap_or_else(|| panic!("call to {callee_name} exists"));
+    let callee_id = call.callee_id.as_ref().expect("call is resolved");
+    symbol(result, callee_id)
+}
+
+#[test]
+fn library_crates_and_packages_resolve_as_external_symbols() {
+    let base = project::<&str>(&[]);
+    let root = base.path().join("project");
+    let cargo_home = base.path().join("cargo");
+    write_files(
+        &cargo_home.join("registry/src/index.crates.io-6f17d22bba15001f"),
+        &[
+            ("foo-1.1.0/Cargo.toml", "[package]\nname = \"foo\"\n"),
+            ("foo-1.1.0/src/lib.rs", "pub fn old() {}\n"),
+            ("foo-1.2.0/Cargo.toml", "[package]\nname = \"foo\"\n"),
+            (
+                "foo-1.2.0/src/lib.rs",
+                "pub mod util;\npub fn bar() { util::helper(); }\nfn main() {}\n",
+            ),
+            ("foo-1.2.0/src/util.rs", "pub fn helper() {}\n"),
+        ],
+    );
+    write_files(
+        &root,
+        &[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"app\"\n\n[dependencies]\nfoo = \"1\"\n",
+            ),
+            (
+                "Cargo.lock",
+                "[[package]]\nname = \"foo\"\nversion = \"1.2.0\"\n",
+            ),
+            (
+                "src/main.rs",
+                "fn main() { foo::bar(); helper(); }\nfn helper() {}\n",
+            ),
+            (
+                "web/app.js",
+                "import { format } from \"fmt-lib\";\nfunction run() { format(); }\n",
+            ),
+            (
+                "node_modules/fmt-lib/package.json",
+                r#"{ "name": "fmt-lib", "main": "dist/index.js" }"#,
+            ),
+            (
+                "node_modules/fmt-lib/dist/index.js",
+                "export function format() {}\n",
+            ),
+        ],
+    );
+
+    let result = analyze_with(&root, |config| {
+        config.libraries.crates = vec!["foo".to_string()];
+        config.libraries.packages = vec!["fmt-lib".to_string()];
+        config.libraries.cargo_home = Some(cargo_home);
+    });
+
+    let bar = callee(&result, "foo::bar");
+    assert_eq!(bar.fq_name, "foo::bar");
+    assert_eq!(bar.file, "{library}/cargo/foo-1.2.0/src/lib.rs");
+    assert!(bar.external);
+
+    let helper = callee(&result, "helper");
+    assert_eq!(helper.fq_name, "app::helper");
+    assert!(!helper.external);
+
+    let format = callee(&result, "format");
+    assert_eq!(format.file, "{library}/npm/fmt-lib/dist/index.js");
+    assert!(format.external);
+
+    for file in &result.files {
+        assert_eq!(
+            file.external,
+            file.path.starts_with("{library}/"),
+            "{}",
+            file.path
+        );
+    }
+    assert!(!result
+        .symbols
+        .iter()
+        .any(|symbol| symbol.fq_name == "foo::old"));
+    assert!(result
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.external)
+        .all(|symbol| !symbol.is_entrypoint()));
+}
+
+#[test]
+fn missing_library_is_an_error() {
+    let root = project(&[("index.js", "function run() {}\n")]);
+    let mut config = AnalysisConfig::new(root.path());
+    config.libraries.packages = vec!["left-pad".to_string()];
+    let err = analyze_project(config, None, None::<fn(ProgressEvent)>)
+        .expect_err("missing package fails");
+    assert!(err.to_string().contains("left-pad"), "{err}");
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use astrograph_engine::{AnalysisResult, CallKind, SymbolKind};
+use common::analyze_files;
+
+fn analyze_rust(source: &str) -> AnalysisResult {
+    analyze_files(&[("lib.rs", source)])
+}
+
+fn calls_from<'a>(result: &'a AnalysisResult, caller: &str) -> Vec<(&'a str, CallKind)> {
+    let caller_id = &result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name == caller)
+        .expect("caller exists")
+        .id;
+    let mut calls: Vec<(&str, CallKind)> = result
+        .calls
+        .iter()
+        .filter(|call| &call.caller_id == caller_id)
+        .map(|call| (call.callee_name.as_str(), call.kind))
+        .collect();
+    calls.sort_by(|a, b| a.0.cmp(b.0));
+    calls
+}
+
+#[test]
+fn macro_invocations_and_nested_calls_become_edges() {
+    let result = analyze_rust(
+        r#"
+macro_rules! define_handler {
+    ($name:ident) => { fn $name() {} register($name) };
+}
+
+fn run() {
+    println!("{}", compute(1));
+    tokio::select! { v = rx.recv() => handle::it(v), }
+    define_handler!(ping);
+}
+"#,
+    );
+
+    assert_eq!(
+        calls_from(&result, "run"),
+        vec![
+            ("compute", CallKind::Call),
+            ("define_handler", CallKind::Macro),
+            ("handle::it", CallKind::Call),
+            ("println", CallKind::Macro),
+            ("rx.recv", CallKind::Call),
+            ("tokio::select", CallKind::Macro),
+        ]
+    );
+    assert_eq!(
+        calls_from(&result, "define_handler"),
+        vec![("register", CallKind::Call)]
+    );
+}
+
+#[test]
+fn macro_edges_resolve_only_to_macros() {
+    let result = analyze_rust(
+        r#"
+macro_rules! log { () => {}; }
+fn log() {}
+
+fn run() {
+    log!();
+    log();
+}
+"#,
+    );
+
+    let macro_id = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name == "log" && symbol.kind == SymbolKind::Macro)
+        .map(|symbol| symbol.id.clone());
+    let function_id = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name == "log" && symbol.kind == SymbolKind::Function)
+        .map(|symbol| symbol.id.clone());
+
+    for call in result.calls.iter().filter(|call| call.callee_name == "log") {
+        if call.kind == CallKind::Macro {
+            assert_eq!(call.callee_id, macro_id);
+        } else {
+            assert_eq!(call.callee_id, function_id);
+        }
+    }
+}
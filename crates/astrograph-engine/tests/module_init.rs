@@ -0,0 +1,68 @@
+mod common;
+
+use astrograph_engine::{EntrypointKind, SymbolKind};
+use common::analyze_files;
+
+#[test]
+fn top_level_js_calls_belong_to_module_init_entrypoint() {
+    let result = analyze_files(&[
+        (
+            "server.js",
+            r#"
+const app = express();
+registerRoutes(app);
+app.listen(3000);
+
+function registerRoutes(router) {
+  router.get("/", handler);
+}
+"#,
+        ),
+        ("util.js", "export function noop() {}\n"),
+    ]);
+
+    let inits: Vec<_> = result
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.kind == SymbolKind::ModuleInit)
+        .collect();
+    assert_eq!(inits.len(), 1, "only files with top-level calls get one");
+    let init = inits[0];
+    assert_eq!(init.name, "{init}");
+    assert_eq!(init.file, "server.js");
+    assert_eq!(init.entrypoint_kinds, vec![EntrypointKind::ModuleInit]);
+
+    let owned: Vec<&str> = result
+        .calls
+        .iter()
+        .filter(|call| call.caller_id == init.id)
+        .map(|call| call.callee_name.as_str())
+        .collect();
+    assert!(owned.contains(&"express"));
+    assert!(owned.contains(&"registerRoutes"));
+    assert!(owned.contains(&"app.listen"));
+    assert!(!owned.contains(&"router.get"));
+
+    let register = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name == "registerRoutes")
+        .expect("registerRoutes exists");
+    assert!(result
+        .calls
+        .iter()
+        .any(|call| call.caller_id == init.id && call.callee_id.as_ref() == Some(&register.id)));
+}
+
+#[test]
+fn rust_module_init_is_not_an_entrypoint() {
+    let result = analyze_files(&[("lib.rs", "thread_local! { static COUNT: u32 = 0; }\n")]);
+
+    let init = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.kind == SymbolKind::ModuleInit)
+        .expect("module init exists");
+    assert!(!init.is_entrypoint());
+    assert!(result.calls.iter().any(|call| call.caller_id == init.id));
+}
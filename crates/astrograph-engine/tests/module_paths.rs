@@ -0,0 +1,133 @@
+mod common;
+
+use astrograph_engine::{analyze_project, AnalysisConfig, AnalysisResult, ProgressEvent};
+use common::{analyze_files, find, project, write_files};
+
+fn fq_names(result: &AnalysisResult) -> Vec<&str> {
+    result
+        .symbols
+        .iter()
+        .map(|symbol| symbol.fq_name.as_str())
+        .collect()
+}
+
+fn callee_fq<'a>(result: &'a AnalysisResult, caller_fq: &str, callee_name: &str) -> &'a str {
+    common::callee_fq(result, find(result, caller_fq), callee_name).expect("call is resolved")
+}
+
+#[test]
+fn rust_fq_names_follow_crates_and_modules() {
+    let result = analyze_files(&[
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\n",
+            ),
+            (
+                "crates/core-lib/Cargo.toml",
+                "[package]\nname = \"core-lib\"\n",
+            ),
+            (
+                "crates/core-lib/src/lib.rs",
+                r#"
+pub mod parser;
+#[path = "platform/unix.rs"]
+mod platform;
+mod util {
+    pub mod text;
+}
+pub fn run() {}
+"#,
+            ),
+            (
+                "crates/core-lib/src/parser/mod.rs",
+                "mod lexer;\npub fn parse() { lexer::lex(); self::lexer::lex(); super::run(); }\n",
+            ),
+            (
+                "crates/core-lib/src/parser/lexer.rs",
+                "pub fn lex() { crate::util::text::trim(); }\n",
+            ),
+            ("crates/core-lib/src/platform/unix.rs", "pub fn open() {}\n"),
+            ("crates/core-lib/src/util/text.rs", "pub fn trim() {}\n"),
+            (
+                "crates/tool/Cargo.toml",
+                "[package]\nname = \"tool\"\n\n[[bin]]\nname = \"tool-cli\"\npath = \"cli.rs\"\n",
+            ),
+            (
+                "crates/tool/cli.rs",
+                "fn main() { core_lib::parser::parse(); helpers::parse(); }\nmod helpers { pub fn parse() {} }\n",
+            ),
+            ("scripts/gen.rs", "fn gen() {}\n"),
+        ],
+    );
+
+    let names = fq_names(&result);
+    for expected in [
+        "core_lib::run",
+        "core_lib::parser::parse",
+        "core_lib::parser::lexer::lex",
+        "core_lib::platform::open",
+        "core_lib::util::text::trim",
+        "tool_cli::main",
+        "tool_cli::helpers::parse",
+        "scripts::gen::gen",
+    ] {
+        assert!(names.contains(&expected), "{expected} in {names:?}");
+    }
+
+    assert_eq!(
+        callee_fq(&result, "core_lib::parser::parse", "self::lexer::lex"),
+        "core_lib::parser::lexer::lex"
+    );
+    assert_eq!(
+        callee_fq(&result, "core_lib::parser::parse", "super::run"),
+        "core_lib::run"
+    );
+    assert_eq!(
+        callee_fq(
+            &result,
+            "core_lib::parser::lexer::lex",
+            "crate::util::text::trim"
+        ),
+        "core_lib::util::text::trim"
+    );
+    assert_eq!(
+        callee_fq(&result, "tool_cli::main", "core_lib::parser::parse"),
+        "core_lib::parser::parse"
+    );
+    assert_eq!(
+        callee_fq(&result, "tool_cli::main", "helpers::parse"),
+        "tool_cli::helpers::parse"
+    );
+}
+
+#[test]
+fn cached_mod_declarations_follow_edits() {
+    let root = project(&[
+        ("Cargo.toml", "[package]\nname = \"krate\"\n"),
+        ("src/lib.rs", "#[path = \"imp.rs\"]\nmod sys;\n"),
+        ("src/imp.rs", "pub fn open() {}\n"),
+    ]);
+    let analyze = |cache| {
+        analyze_project(
+            AnalysisConfig::new(root.path()),
+            cache,
+            None::<fn(ProgressEvent)>,
+        )
+        .expect("analyze should succeed")
+    };
+
+    let first = analyze(None);
+    let declarations = &first.cache.files["src/lib.rs"].mod_declarations;
+    assert_eq!(declarations.len(), 1);
+    assert_eq!(declarations[0].name, "sys");
+    let second = analyze(Some(first.cache));
+    assert_eq!(second.result.stats.reused_cache_files, 2);
+    find(&second.result, "krate::sys::open");
+
+    write_files(
+        root.path(),
+        &[("src/lib.rs", "#[path = \"imp.rs\"]\nmod os;\n")],
+    );
+    let third = analyze(Some(second.cache));
+    find(&third.result, "krate::os::open");
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use astrograph_engine::config::OutputFormat;
+use astrograph_engine::{
+    analyze_project, AnalysisConfig, AnalysisResult, ProjectConfig, ProjectSettings,
+};
+use common::project;
+use std::path::Path;
+
+fn analyze(root: &Path) -> AnalysisResult {
+    let config = AnalysisConfig::for_project(root).expect("config loads");
+    let out = analyze_project(config, None, None::<fn(astrograph_engine::ProgressEvent)>)
+        .expect("analyze should succeed");
+    out.result
+}
+
+#[test]
+fn include_exclude_and_languages_filter_files() {
+    let root = project(&[
+        (
+            "astrograph.toml",
+            r#"
+languages = ["typescript"]
+
+[files]
+include = ["src/**"]
+exclude = ["**/*.test.ts"]
+"#,
+        ),
+        ("src/app.ts", "export function app() {}\n"),
+        ("src/app.test.ts", "function check() {}\n"),
+        ("src/legacy.js", "function legacy() {}\n"),
+        ("scripts/build.ts", "function build() {}\n"),
+    ]);
+    let result = analyze(root.path());
+
+    let files: Vec<&str> = result.files.iter().map(|file| file.path.as_str()).collect();
+    assert_eq!(files, vec!["src/app.ts"]);
+}
+
+#[test]
+fn resolution_prefers_the_callers_file() {
+    let files: &[(&str, &str)] = &[
+        (
+            "a.ts",
+            "function run() { helper(); }\nfunction helper() {}\n",
+        ),
+        ("0.ts", "function helper() {}\n"),
+    ];
+    let callee_file = |root: &Path| {
+        let result = analyze(root);
+        let call = result
+            .calls
+            .iter()
+            .find(|call| call.callee_name == "helper")
+            .expect("call exists");
+        let callee = call.callee_id.as_ref().expect("call resolves");
+        common::symbol(&result, callee).file.clone()
+    };
+
+    let root = project(files);
+    assert_eq!(callee_file(root.path()), "0.ts");
+
+    let mut with_config = files.to_vec();
+    with_config.push(("astrograph.toml", "[resolution]\nprefer_same_file = true\n"));
+    let root = project(&with_config);
+    assert_eq!(callee_file(root.path()), "a.ts");
+}
+
+#[test]
+fn output_and_cache_paths_resolve_against_root() {
+    let root = project(&[(
+        "astrograph.toml",
+        r#"
+[output]
+path = "out/graph.json"
+format = "compact"
+
+[cache]
+path = ".cache.json"
+"#,
+    )]);
+    let settings = ProjectSettings::for_project(root.path()).expect("config loads");
+
+    let root = root.path();
+    assert_eq!(settings.output_path(), Some(root.join("out/graph.json")));
+    assert_eq!(settings.project.output.format, OutputFormat::Compact);
+    assert_eq!(settings.cache_path(false), Some(root.join(".cache.json")));
+}
+
+#[test]
+fn cache_defaults_to_the_frontend_unless_configured() {
+    let root = project(&[("src/app.ts", "export function app() {}\n")]);
+    let settings = ProjectSettings::for_project(root.path()).expect("config loads");
+    let default_cache = root.path().join(".astrograph-cache.json");
+    assert_eq!(settings.output_path(), None);
+    assert_eq!(settings.cache_path(false), None);
+    assert_eq!(settings.cache_path(true), Some(default_cache.clone()));
+
+    let root = project(&[("astrograph.toml", "[cache]\n")]);
+    let settings = ProjectSettings::for_project(root.path()).expect("config loads");
+    let default_cache = root.path().join(".astrograph-cache.json");
+    assert_eq!(settings.cache_path(false), Some(default_cache));
+
+    let root = project(&[("astrograph.toml", "[cache]\nenabled = false\n")]);
+    let settings = ProjectSettings::for_project(root.path()).expect("config loads");
+    assert_eq!(settings.cache_path(true), None);
+}
+
+#[test]
+fn unknown_keys_are_rejected() {
+    let root = project(&[("astrograph.toml", "[files]\nincludes = [\"src/**\"]\n")]);
+    let err = ProjectConfig::load(root.path()).expect_err("typo is reported");
+
+    assert!(format!("{err:#}").contains("includes"));
+}
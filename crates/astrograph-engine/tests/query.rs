@@ -0,0 +1,86 @@
+mod common;
+
+use astrograph_engine::{AnalysisIndex, SymbolKind, SymbolQuery};
+use common::analyze_files;
+
+fn build_index(source: &str) -> AnalysisIndex {
+    AnalysisIndex::new(analyze_files(&[("app.ts", source)]))
+}
+
+fn symbol_id(index: &AnalysisIndex, name: &str) -> String {
+    index
+        .search(&SymbolQuery {
+            text: name.to_string(),
+            ..SymbolQuery::default()
+        })
+        .first()
+        .map(|symbol| symbol.id.clone())
+        .unwrap_or_else(|| panic!("symbol {name} exists"))
+}
+
+const CHAIN: &str = r#"
+export function start() { middle(); }
+function middle() { finish(); other(); }
+function finish() {}
+function other() {}
+function unrelated() {}
+"#;
+
+#[test]
+fn search_ranks_exact_matches_first_and_filters_kind() {
+    let index = build_index(CHAIN);
+
+    let results = index.search(&SymbolQuery {
+        text: "FIN".to_string(),
+        ..SymbolQuery::default()
+    });
+    assert_eq!(results.first().map(|s| s.name.as_str()), Some("finish"));
+
+    let classes = index.search(&SymbolQuery {
+        text: String::new(),
+        kind: Some(SymbolKind::Class),
+        ..SymbolQuery::default()
+    });
+    assert!(classes.is_empty());
+}
+
+#[test]
+fn callers_and_callees_follow_resolved_edges() {
+    let index = build_index(CHAIN);
+    let middle = symbol_id(&index, "middle");
+
+    let callees: Vec<&str> = index
+        .callees(&middle)
+        .iter()
+        .filter_map(|neighbor| neighbor.symbol.map(|s| s.name.as_str()))
+        .collect();
+    assert_eq!(callees.len(), 2);
+    assert!(callees.contains(&"finish") && callees.contains(&"other"));
+
+    let callers: Vec<&str> = index
+        .callers(&middle)
+        .iter()
+        .filter_map(|neighbor| neighbor.symbol.map(|s| s.name.as_str()))
+        .collect();
+    assert_eq!(callers, vec!["start"]);
+}
+
+#[test]
+fn shortest_path_respects_depth_and_reachability() {
+    let index = build_index(CHAIN);
+    let start = symbol_id(&index, "start");
+    let finish = symbol_id(&index, "finish");
+    let unrelated = symbol_id(&index, "unrelated");
+
+    let path = index
+        .shortest_path(&start, &finish, 8)
+        .expect("path exists");
+    let names: Vec<&str> = path
+        .iter()
+        .filter_map(|id| index.symbol(id).map(|s| s.name.as_str()))
+        .collect();
+    assert_eq!(names, vec!["start", "middle", "finish"]);
+
+    assert!(index.shortest_path(&start, &finish, 1).is_none());
+    assert!(index.shortest_path(&start, &unrelated, 8).is_none());
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use astrograph_engine::{Modifier, Symbol, SymbolKind};
+use common::analyze_files;
+
+fn analyze_rust(source: &str) -> Vec<Symbol> {
+    analyze_files(&[("items.rs", source)]).symbols
+}
+
+fn find<'a>(symbols: &'a [Symbol], fq_name: &str) -> &'a Symbol {
+    symbols
+        .iter()
+        .find(|symbol| symbol.fq_name == fq_name)
+        .unwrap_or_else(|| panic!("symbol {fq_name} exists"))
+}
+
+#[test]
+fn extracts_consts_statics_aliases_unions_and_macros() {
+    let symbols = analyze_rust(
+        r#"
+pub const MAX: usize = 10;
+static mut COUNTER: u32 = 0;
+pub type Alias<T> = Vec<T>;
+pub union Bits { a: u32, b: f32 }
+
+#[macro_export]
+macro_rules! define_handler {
+    ($name:ident) => {};
+}
+
+macro_rules! internal { () => {}; }
+"#,
+    );
+
+    let max = find(&symbols, "items::MAX");
+    assert_eq!(max.kind, SymbolKind::Constant);
+    assert!(max.is_exported);
+    assert_eq!(max.signature.as_deref(), Some("pub const MAX: usize"));
+
+    let counter = find(&symbols, "items::COUNTER");
+    assert_eq!(counter.kind, SymbolKind::Static);
+    assert_eq!(counter.modifiers, vec![Modifier::Static]);
+    assert!(!counter.is_exported);
+
+    let alias = find(&symbols, "items::Alias");
+    assert_eq!(alias.kind, SymbolKind::TypeAlias);
+    assert_eq!(alias.generics.as_deref(), Some("<T>"));
+
+    assert_eq!(find(&symbols, "items::Bits").kind, SymbolKind::Union);
+
+    let handler = find(&symbols, "items::define_handler");
+    assert_eq!(handler.kind, SymbolKind::Macro);
+    assert!(handler.is_exported, "#[macro_export] exports the macro");
+    assert_eq!(
+        handler.signature.as_deref(),
+        Some("macro_rules! define_handler")
+    );
+    assert!(!find(&symbols, "items::internal").is_exported);
+}
+
+#[test]
+fn extracts_associated_consts_and_types() {
+    let symbols = analyze_rust(
+        r#"
+trait Shape {
+    const SIDES: u8;
+    type Output: Clone;
+}
+
+impl Shape for Square {
+    const SIDES: u8 = 4;
+    type Output = u8;
+}
+"#,
+    );
+
+    let trait_const = find(&symbols, "items::Shape::SIDES");
+    assert_eq!(trait_const.kind, SymbolKind::Constant);
+    assert_eq!(trait_const.container.as_deref(), Some("Shape"));
+    assert_eq!(
+        find(&symbols, "items::Shape::Output").kind,
+        SymbolKind::TypeAlias
+    );
+
+    assert_eq!(
+        find(&symbols, "items::Square::SIDES").kind,
+        SymbolKind::Constant
+    );
+    assert_eq!(
+        find(&symbols, "items::Square::Output").kind,
+        SymbolKind::TypeAlias
+    );
+}
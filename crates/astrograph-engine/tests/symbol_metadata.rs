@@ -0,0 +1,143 @@
+mod common;
+
+use astrograph_engine::{Modifier, Symbol};
+
+fn analyze_files(files: &[(&str, &str)]) -> Vec<Symbol> {
+    common::analyze_files(files).symbols
+}
+
+fn find<'a>(symbols: &'a [Symbol], name: &str) -> &'a Symbol {
+    symbols
+        .iter()
+        .find(|symbol| symbol.name == name)
+        .unwrap_or_else(|| panic!("symbol {name} exists"))
+}
+
+#[test]
+fn rust_functions_capture_signature_parameters_and_docs() {
+    let symbols = analyze_files(&[(
+        "lib.rs",
+        r#"
+/// Loads the thing.
+///
+/// Second paragraph.
+#[inline]
+pub async unsafe fn load<T: Clone>(&self, path: &str, mut items: Vec<T>) -> Result<(), Error>
+where
+    T: Send,
+{
+}
+
+/** Block docs. */
+pub const fn answer() -> u32 { 42 }
+
+// Not a doc comment.
+pub struct Plain<'a> { value: &'a str }
+"#,
+    )]);
+
+    let load = find(&symbols, "load");
+    assert_eq!(
+        load.signature.as_deref(),
+        Some("pub async unsafe fn load<T: Clone>(&self, path: &str, mut items: Vec<T>) -> Result<(), Error> where T: Send")
+    );
+    assert_eq!(load.generics.as_deref(), Some("<T: Clone>"));
+    assert_eq!(load.return_type.as_deref(), Some("Result<(), Error>"));
+    assert_eq!(load.modifiers, vec![Modifier::Async, Modifier::Unsafe]);
+    let params: Vec<(&str, Option<&str>)> = load
+        .parameters
+        .iter()
+        .map(|param| (param.name.as_str(), param.type_name.as_deref()))
+        .collect();
+    assert_eq!(
+        params,
+        vec![
+            ("&self", None),
+            ("path", Some("&str")),
+            ("items", Some("Vec<T>"))
+        ]
+    );
+    assert_eq!(
+        load.doc.as_deref(),
+        Some("Loads the thing.\n\nSecond paragraph.")
+    );
+
+    let answer = find(&symbols, "answer");
+    assert_eq!(answer.modifiers, vec![Modifier::Const]);
+    assert_eq!(answer.doc.as_deref(), Some("Block docs."));
+
+    let plain = find(&symbols, "Plain");
+    assert_eq!(plain.generics.as_deref(), Some("<'a>"));
+    assert_eq!(plain.signature.as_deref(), Some("pub struct Plain<'a>"));
+    assert!(plain.doc.is_none());
+}
+
+#[test]
+fn typescript_functions_capture_types_and_jsdoc() {
+    let symbols = analyze_files(&[
+        (
+            "api.ts",
+            r#"
+/**
+ * Adds numbers.
+ */
+export async function add<T>(a: number, b = 2, ...rest: T[]): Promise<number> {
+  return a + b;
+}
+
+export const handler = async (event: Event): Promise<void> => {};
+
+class Service {
+  static create(name?: string) {}
+}
+"#,
+        ),
+        (
+            "legacy.js",
+            r#"
+/**
+ * Formats a value.
+ * @param {string} value the input
+ * @returns {string}
+ */
+function format(value) {
+  return value;
+}
+"#,
+        ),
+    ]);
+
+    let add = find(&symbols, "add");
+    assert_eq!(add.modifiers, vec![Modifier::Async]);
+    assert_eq!(add.generics.as_deref(), Some("<T>"));
+    assert_eq!(add.return_type.as_deref(), Some("Promise<number>"));
+    assert_eq!(add.doc.as_deref(), Some("Adds numbers."));
+    let params: Vec<(&str, Option<&str>)> = add
+        .parameters
+        .iter()
+        .map(|param| (param.name.as_str(), param.type_name.as_deref()))
+        .collect();
+    assert_eq!(
+        params,
+        vec![("a", Some("number")), ("b", None), ("...rest", Some("T[]"))]
+    );
+
+    let handler = find(&symbols, "handler");
+    assert_eq!(
+        handler.signature.as_deref(),
+        Some("handler = async (event: Event): Promise<void>")
+    );
+    assert_eq!(handler.modifiers, vec![Modifier::Async]);
+
+    let create = find(&symbols, "create");
+    assert_eq!(create.modifiers, vec![Modifier::Static]);
+    assert_eq!(create.parameters[0].type_name.as_deref(), Some("string"));
+
+    let format = find(&symbols, "format");
+    assert_eq!(format.parameters[0].type_name.as_deref(), Some("string"));
+    assert_eq!(format.return_type.as_deref(), Some("string"));
+    assert_eq!(
+        format.doc.as_deref(),
+        Some("Formats a value.\n@param {string} value the input\n@returns {string}")
+    );
+}
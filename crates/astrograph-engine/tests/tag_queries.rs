@@ -0,0 +1,147 @@
+mod common;
+
+use astrograph_engine::{
+    analyze_project, AnalysisConfig, AnalysisResult, CallKind, ProgressEvent, SymbolKind,
+};
+
+/// Analyzes `files` with the project's `astrograph.toml`, whose query files
+/// may fail to compile.
+fn analyze_files(files: &[(&str, &str)]) -> anyhow::Result<AnalysisResult> {
+    let root = common::project(files);
+    let config = AnalysisConfig::for_project(root.path())?;
+    Ok(analyze_project(config, None, None::<fn(ProgressEvent)>)?.result)
+}
+
+#[test]
+fn project_queries_add_definitions() {
+    let result = analyze_files(&[
+        (
+            "astrograph.toml",
+            "[[queries]]\nlanguage = \"typescript\"\npath = \"queries/handlers.scm\"\n",
+        ),
+        (
+            "queries/handlers.scm",
+            r#"
+(assignment_expression
+  left: (member_expression property: (property_identifier) @name)
+  right: (arrow_function)) @definition.function
+"#,
+        ),
+        (
+            "src/app.ts",
+            r#"
+function save() {}
+
+export const handlers: Record<string, () => void> = {};
+handlers.onClick = () => save();
+"#,
+        ),
+    ])
+    .expect("analyze should succeed");
+
+    let on_click = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name == "onClick")
+        .expect("onClick is a symbol");
+    assert_eq!(on_click.kind, SymbolKind::Function);
+    assert_eq!(on_click.fq_name, "src::app::onClick");
+
+    // The bundled query still applies: the arrow function is a closure of
+    // `onClick`, and its call resolves.
+    let closure = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.kind == SymbolKind::Closure)
+        .expect("closure exists");
+    assert!(closure.fq_name.starts_with("src::app::onClick::"));
+    let save = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name == "save")
+        .unwrap();
+    assert!(result
+        .calls
+        .iter()
+        .any(|call| call.caller_id == closure.id && call.callee_id.as_ref() == Some(&save.id)));
+}
+
+#[test]
+fn project_queries_replace_the_bundled_query() {
+    let result = analyze_files(&[
+        (
+            "astrograph.toml",
+            "[[queries]]\nlanguage = \"rust\"\npath = \"rust.scm\"\nreplace = true\n",
+        ),
+        (
+            "rust.scm",
+            r#"
+(function_item name: (identifier) @name) @definition.function
+(call_expression function: (identifier) @name) @reference.call
+"#,
+        ),
+        (
+            "src/lib.rs",
+            r#"
+pub struct Config;
+
+pub fn run() {
+    helper();
+    let double = |x: i32| x * 2;
+    vec![double(1)];
+}
+
+fn helper() {}
+"#,
+        ),
+    ])
+    .expect("analyze should succeed");
+
+    let mut kinds: Vec<(&str, &SymbolKind)> = result
+        .symbols
+        .iter()
+        .map(|symbol| (symbol.name.as_str(), &symbol.kind))
+        .collect();
+    kinds.sort_by_key(|(name, _)| *name);
+    assert_eq!(
+        kinds,
+        vec![
+            ("helper", &SymbolKind::Function),
+            ("run", &SymbolKind::Function)
+        ]
+    );
+    // Built-in details still apply to tagged nodes.
+    let run = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name == "run")
+        .unwrap();
+    assert!(run.is_exported);
+
+    let mut calls: Vec<(&str, CallKind)> = result
+        .calls
+        .iter()
+        .map(|call| (call.callee_name.as_str(), call.kind))
+        .collect();
+    calls.sort_by_key(|(name, _)| *name);
+    assert_eq!(
+        calls,
+        vec![("double", CallKind::Call), ("helper", CallKind::Call)]
+    );
+}
+
+#[test]
+fn unknown_definition_captures_are_rejected() {
+    let err = analyze_files(&[
+        (
+            "astrograph.toml",
+            "[[queries]]\nlanguage = \"rust\"\npath = \"rust.scm\"\n",
+        ),
+        ("rust.scm", "(struct_item) @definition.widget\n"),
+        ("src/lib.rs", "pub fn run() {}\n"),
+    ])
+    .expect_err("the query is invalid");
+    let message = format!("{err:#}");
+    assert!(message.contains("rust.scm"), "{message}");
+    assert!(message.contains("@definition.widget"), "{message}");
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use astrograph_engine::{Modifier, Symbol, SymbolKind};
+use common::{analyze_files, find};
+
+const SHAPES: &str = r#"
+export type Point = { x: number; y: number };
+
+export abstract class Shape {
+    abstract area(): number;
+
+    describe(): string {
+        return `area ${this.area()}`;
+    }
+}
+
+/** Parses a point. */
+export function parse(text: string): Point;
+export function parse(parts: number[]): Point;
+export function parse(input: any): Point {
+    return toPoint(input);
+}
+
+function toPoint(input: any): Point {
+    return input;
+}
+
+export class Scale {
+    apply(value: number): number;
+    apply(value: Point): Point;
+    apply(value: any): any {
+        return value;
+    }
+}
+
+declare function greet(name: string): void;
+
+export namespace Util {
+    export function help() {}
+}
+"#;
+
+const GLOBALS: &str = r#"
+declare module "lib" {
+    export function load(path: string): Promise<void>;
+}
+
+declare const VERSION: string;
+
+interface Options {
+    verbose: boolean;
+}
+
+export function configure(options: Options): void;
+"#;
+
+#[test]
+fn typescript_type_level_symbols() {
+    let result = analyze_files(&[("src/shapes.ts", SHAPES), ("src/globals.d.ts", GLOBALS)]);
+
+    assert_eq!(
+        find(&result, "src::shapes::Point").kind,
+        SymbolKind::TypeAlias
+    );
+
+    let shape = find(&result, "src::shapes::Shape");
+    assert_eq!(shape.kind, SymbolKind::Class);
+    assert!(shape.modifiers.contains(&Modifier::Abstract));
+    let area = find(&result, "src::shapes::Shape::area");
+    assert_eq!(area.kind, SymbolKind::Method);
+    assert!(area.declaration);
+    assert!(area.modifiers.contains(&Modifier::Abstract));
+    assert!(!find(&result, "src::shapes::Shape::describe").declaration);
+
+    // Overload signatures merge into the implementation.
+    let parse = find(&result, "src::shapes::parse");
+    assert!(!parse.declaration);
+    assert!(parse.is_exported);
+    assert_eq!(parse.doc.as_deref(), Some("Parses a point."));
+    assert!(result
+        .calls
+        .iter()
+        .any(|call| call.caller_id == parse.id && call.callee_name == "toPoint"));
+    let apply = find(&result, "src::shapes::Scale::apply");
+    assert_eq!(apply.kind, SymbolKind::Method);
+    assert!(!apply.declaration);
+
+    assert!(find(&result, "src::shapes::greet").declaration);
+    assert_eq!(
+        find(&result, "src::shapes::Util").kind,
+        SymbolKind::Namespace
+    );
+    assert_eq!(
+        find(&result, "src::shapes::Util::help").kind,
+        SymbolKind::Function
+    );
+
+    // Everything in a declaration file is a declaration.
+    let globals: Vec<&Symbol> = result
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.file == "src/globals.d.ts")
+        .collect();
+    assert!(!globals.is_empty());
+    assert!(globals.iter().all(|symbol| symbol.declaration));
+    let version = globals
+        .iter()
+        .find(|symbol| symbol.name == "VERSION")
+        .expect("ambient constant");
+    assert_eq!(version.kind, SymbolKind::Constant);
+    let lib = globals
+        .iter()
+        .find(|symbol| symbol.name == "lib")
+        .expect("ambient module");
+    assert_eq!(lib.kind, SymbolKind::Namespace);
+    assert_eq!(lib.fq_name, "src::globals::lib");
+    assert!(globals
+        .iter()
+        .any(|symbol| symbol.fq_name == "src::globals::lib::load"));
+}
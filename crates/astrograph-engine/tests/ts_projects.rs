@@ -0,0 +1,100 @@
+mod common;
+
+use astrograph_engine::AnalysisResult;
+use common::{analyze_files, symbol};
+
+fn callee_fq<'a>(result: &'a AnalysisResult, callee_name: &str) -> Option<&'a str> {
+    let call = result
+        .calls
+        .iter()
+        .find(|call| call.callee_name == callee_name)
+        .unwrap_or_else(|| panic!("call to {callee_name} exists"));
+    let callee_id = call.callee_id.as_deref()?;
+    Some(symbol(result, callee_id).fq_name.as_str())
+}
+
+#[test]
+fn imports_resolve_through_tsconfig_paths_and_workspace_packages() {
+    let result = analyze_files(&[
+        (
+            "pnpm-workspace.yaml",
+            "packages:\n  - packages/*\n  - apps/*\n",
+        ),
+        (
+            "packages/utils/package.json",
+            r#"{
+  "name": "@app/utils",
+  "exports": {
+    ".": { "types": "./dist/index.d.ts", "import": "./dist/index.js" },
+    "./strings": "./dist/strings.js"
+  }
+}"#,
+        ),
+        (
+            "packages/utils/src/index.ts",
+            "export * from \"./format\";\nexport { slug as slugify } from \"./strings\";\n",
+        ),
+        (
+            "packages/utils/src/format.ts",
+            "export function format() {}\n",
+        ),
+        (
+            "packages/utils/src/strings.ts",
+            "export function slug() {}\n",
+        ),
+        (
+            "apps/web/tsconfig.json",
+            r#"{
+  // Path aliases for the web app.
+  "extends": "./tsconfig.base.json",
+  "compilerOptions": { "baseUrl": "." },
+}"#,
+        ),
+        (
+            "apps/web/tsconfig.base.json",
+            r#"{ "compilerOptions": { "paths": { "~/*": ["src/*"] } } }"#,
+        ),
+        (
+            "apps/web/src/lib/api.ts",
+            "export function fetchUsers() {}\n",
+        ),
+        (
+            "apps/web/src/legacy.ts",
+            "function format() {}\nfunction slug() {}\n",
+        ),
+        (
+            "apps/web/src/main.ts",
+            r#"
+import { format, slugify } from "@app/utils";
+import * as api from "~/lib/api";
+import { slug } from "@app/utils/strings";
+
+function run() {
+  format();
+  slugify();
+  api.fetchUsers();
+  slug();
+  missing();
+}
+"#,
+        ),
+    ]);
+
+    assert_eq!(
+        callee_fq(&result, "format"),
+        Some("packages::utils::src::format::format")
+    );
+    assert_eq!(
+        callee_fq(&result, "slugify"),
+        Some("packages::utils::src::strings::slug")
+    );
+    assert_eq!(
+        callee_fq(&result, "api.fetchUsers"),
+        Some("apps::web::src::lib::api::fetchUsers")
+    );
+    assert_eq!(
+        callee_fq(&result, "slug"),
+        Some("packages::utils::src::strings::slug")
+    );
+    assert_eq!(callee_fq(&result, "missing"), None);
+}